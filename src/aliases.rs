@@ -0,0 +1,77 @@
+use crate::bump::{BumpError, clear_git_cache, ensure_directory_exists, git_cmd};
+use crate::version::{Aliases, Version};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Expand `{prefix}`, `{major}`, `{minor}`, `{patch}` and `{version}` in an alias.
+fn render(template: &str, version: &Version, tag_name: &str) -> String {
+    let component = |value: Option<u32>| value.map(|n| n.to_string()).unwrap_or_default();
+    template
+        .replace("{prefix}", &version.prefix)
+        .replace("{major}", &version.base.major.to_string())
+        .replace("{minor}", &component(version.base.minor))
+        .replace("{patch}", &component(version.base.patch))
+        .replace("{version}", tag_name)
+}
+
+/// Alias tags and pointer files for a release, or nothing for a pre-release or
+/// without `[aliases]`. Files are relative to the bumpfile's directory.
+fn targets(version: &Version, tag_name: &str, bumpfile: &Path) -> (Vec<String>, Vec<PathBuf>) {
+    let Some(aliases) = version
+        .aliases
+        .as_ref()
+        .filter(|_| version.phase.name.is_empty())
+    else {
+        return (Vec::new(), Vec::new());
+    };
+    let Aliases { tags, files } = aliases;
+    let dir = bumpfile.parent().unwrap_or(Path::new("."));
+    (
+        tags.iter()
+            .map(|tag| render(tag, version, tag_name))
+            .filter(|tag| tag != tag_name)
+            .collect(),
+        files.iter().map(|file| dir.join(file)).collect(),
+    )
+}
+
+/// Print what [`update`] would do.
+pub fn dry_run(version: &Version, tag_name: &str, bumpfile: &Path) {
+    let (tags, files) = targets(version, tag_name, bumpfile);
+    for tag in tags {
+        println!("dry run: would move tag {tag} to {tag_name}");
+    }
+    for file in files {
+        println!("dry run: would write {tag_name} to {}", file.display());
+    }
+}
+
+/// Point every `[aliases].tags` entry at the release just tagged as `tag_name`, and
+/// write `tag_name` into every `[aliases].files` entry. Pre-releases leave them alone.
+pub fn update(version: &Version, tag_name: &str, bumpfile: &Path) -> Result<(), BumpError> {
+    let (tags, files) = targets(version, tag_name, bumpfile);
+    for tag in &tags {
+        // Lightweight tags on the release commit; `git describe` prefers the
+        // annotated release tag, so they never become the latest tag.
+        let output = git_cmd()
+            .args(["tag", "--force", tag, &format!("{tag_name}^{{commit}}")])
+            .output()
+            .map_err(|e| BumpError::Git(format!("failed to move tag '{tag}': {e}")))?;
+        if !output.status.success() {
+            return Err(BumpError::Git(format!(
+                "failed to move tag '{tag}': {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+        println!("Moved alias tag {tag} to {tag_name}");
+    }
+    if !tags.is_empty() {
+        clear_git_cache();
+    }
+    for file in files {
+        ensure_directory_exists(&file)?;
+        fs::write(&file, format!("{tag_name}\n")).map_err(BumpError::IoError)?;
+        println!("Wrote {tag_name} to {}", file.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::bump::{BumpError, load_bumpfile};
+use crate::print::{self, PrintOptions};
+use crate::version::Version;
+use clap::ArgMatches;
+use std::collections::BTreeMap;
+
+/// Placeholders every template can use, from the bumpfile version.
+pub fn builtins(version: &Version) -> Result<BTreeMap<String, String>, BumpError> {
+    let component = |value: Option<u32>| value.map(|n| n.to_string()).unwrap_or_default();
+    let channel = if version.phase.name.is_empty() {
+        "stable".to_string()
+    } else {
+        version.phase.name.clone()
+    };
+    Ok(BTreeMap::from([
+        (
+            "version".to_string(),
+            print::to_string(version, &PrintOptions::no_prefix())?,
+        ),
+        (
+            "tag".to_string(),
+            print::to_string(version, &PrintOptions::default())?,
+        ),
+        ("major".to_string(), version.base.major.to_string()),
+        ("minor".to_string(), component(version.base.minor)),
+        ("patch".to_string(), component(version.base.patch)),
+        ("channel".to_string(), channel),
+    ]))
+}
+
+/// Replace every `{name}` in `template`; unknown names are an error so a typo never
+/// ends up in a published file name.
+pub fn render(template: &str, values: &BTreeMap<String, String>) -> Result<String, BumpError> {
+    let mut output = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        output.push_str(&rest[..open]);
+        let close = rest[open..].find('}').ok_or_else(|| {
+            BumpError::ParseError(format!("unclosed '{{' in template '{template}'"))
+        })?;
+        let name = &rest[open + 1..open + close];
+        let value = values.get(name).ok_or_else(|| {
+            let known: Vec<&str> = values.keys().map(String::as_str).collect();
+            BumpError::LogicError(format!(
+                "unknown placeholder {{{name}}} in template '{template}' (known: {})",
+                known.join(", ")
+            ))
+        })?;
+        output.push_str(value);
+        rest = &rest[open + close + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Print the artifact file name `--template` renders to, without newline.
+pub fn run(matches: &ArgMatches) -> Result<(), BumpError> {
+    let version = load_bumpfile(matches)?.version()?;
+    let template = matches
+        .get_one::<String>("template")
+        .expect("TEMPLATE is required");
+    let mut values = builtins(&version)?;
+    for var in matches.get_many::<String>("var").into_iter().flatten() {
+        let (name, value) = var.split_once('=').ok_or_else(|| {
+            BumpError::ParseError(format!("--var expects NAME=VALUE, got '{var}'"))
+        })?;
+        values.insert(name.to_string(), value.to_string());
+    }
+    let rendered = render(template, &values).map_err(|err| match err {
+        BumpError::LogicError(message) => {
+            BumpError::LogicError(format!("{message}; add others with --var NAME=VALUE"))
+        }
+        err => err,
+    })?;
+    print!("{rendered}");
+    Ok(())
+}
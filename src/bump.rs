@@ -1,12 +1,29 @@
-use crate::bumpfile::BumpFile;
-use crate::lang::{self, Language};
+use crate::aliases;
+use crate::bumpfile::{self, BumpFile};
+use crate::changelog;
+use crate::git_backend;
+use crate::history;
+use crate::issues;
+use crate::lang::{self, Language, ModuleFormat, OutputOptions, Packing};
+use crate::notify;
 use crate::print::{self, PrintOptions};
-use crate::version::Version;
+use crate::provenance;
+use crate::push;
+use crate::update;
+use crate::version::{LatestTag, Version, VersionMode};
+use crate::version_code;
+use crate::workspace;
 use clap::ArgMatches;
 use std::{
-    fmt, fs, io,
-    path::{Path, PathBuf},
+    collections::HashSet,
+    fmt, fs,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
     process::Command as ProcessCommand,
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
 pub enum BumpType {
@@ -23,6 +40,7 @@ pub enum BumpError {
     ParseError(String),
     LogicError(String),
     Git(String),
+    Http(String),
 }
 
 impl fmt::Display for BumpError {
@@ -38,6 +56,7 @@ impl fmt::Display for BumpError {
             Self::ParseError(field) => write!(f, "bump error: parse >> {field}"),
             Self::LogicError(msg) => write!(f, "bump error >> {msg}"),
             Self::Git(msg) => write!(f, "bump error: git >> {msg}"),
+            Self::Http(msg) => write!(f, "bump error: http >> {msg}"),
         }
     }
 }
@@ -60,11 +79,55 @@ pub fn resolve_path(input_path: &str) -> PathBuf {
     }
 }
 
+/// `path` with symlinks and `..` resolved, even when the file itself does not exist yet.
+fn canonical_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = fs::canonicalize(path) {
+        return canonical;
+    }
+    let parent = path.parent().map(canonical_path);
+    match (parent, path.components().next_back()) {
+        (Some(parent), Some(Component::Normal(name))) => parent.join(name),
+        (Some(parent), Some(Component::CurDir)) => parent,
+        (Some(parent), Some(Component::ParentDir)) => {
+            parent.parent().map_or(parent.clone(), Path::to_path_buf)
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Refuse to write `target` when it is the bumpfile itself.
+pub fn guard_bumpfile(bumpfile: &Path, target: &Path) -> Result<(), BumpError> {
+    if canonical_path(bumpfile) == canonical_path(&resolve_path(&target.to_string_lossy())) {
+        return Err(BumpError::LogicError(format!(
+            "refusing to overwrite the bumpfile '{}'; choose a different output path",
+            target.display()
+        )));
+    }
+    Ok(())
+}
+
+/// A failed write to `path`, explained when the checkout is read-only (Nix builds,
+/// read-only mounts) instead of surfacing a bare EACCES/EROFS.
+pub fn write_error(path: &Path, err: io::Error) -> BumpError {
+    if !matches!(
+        err.kind(),
+        io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem
+    ) {
+        return BumpError::IoError(err);
+    }
+    BumpError::LogicError(format!(
+        "cannot write '{}' ({err}): the source tree looks read-only. \
+        Bumps need a writable bumpfile; 'bump gen --output-dir DIR' writes generated \
+        files to a writable directory instead",
+        path.display()
+    ))
+}
+
 pub fn ensure_directory_exists(path: &Path) -> Result<(), BumpError> {
     if let Some(parent) = path.parent()
         && !parent.exists()
     {
-        fs::create_dir_all(parent).map_err(BumpError::IoError)?;
+        fs::create_dir_all(parent).map_err(|err| write_error(parent, err))?;
     }
     Ok(())
 }
@@ -77,6 +140,9 @@ pub fn load_bumpfile(matches: &ArgMatches) -> Result<BumpFile, BumpError> {
     let version_file_path = matches
         .get_one::<String>("bumpfile")
         .expect("PATH not provided");
+    if version_file_path.starts_with("http://") || version_file_path.starts_with("https://") {
+        return BumpFile::fetch(version_file_path);
+    }
     BumpFile::load(resolve_path(version_file_path))
 }
 
@@ -101,7 +167,14 @@ pub fn get_bump_type(matches: &ArgMatches) -> Result<BumpType, BumpError> {
 pub fn initialize(matches: &ArgMatches) -> Result<(), BumpError> {
     let bumpfile_path = matches.get_one::<String>("bumpfile").unwrap();
     let filepath = resolve_path(bumpfile_path);
-    let bumpfile = BumpFile::create(&filepath)?;
+    // Automation and project templates can seed the starting version through the
+    // environment instead of passing --initial everywhere.
+    let initial = matches
+        .get_one::<String>("initial")
+        .cloned()
+        .or_else(|| std::env::var("BUMP_INITIAL_VERSION").ok())
+        .filter(|initial| !initial.is_empty());
+    let bumpfile = BumpFile::create(&filepath, initial.as_deref())?;
     println!(
         "Initialized new BUMPFILE at '{}'",
         bumpfile.path().display()
@@ -110,35 +183,186 @@ pub fn initialize(matches: &ArgMatches) -> Result<(), BumpError> {
 }
 
 pub fn apply(matches: &ArgMatches) -> Result<(), BumpError> {
-    let mut bumpfile = load_bumpfile(matches)?;
-    let mut version = bumpfile.version()?;
-    let has_meta = has_meta_flags(matches);
-    let has_formal = matches.contains_id("formal");
+    if matches.get_flag("push") {
+        return push::run(matches);
+    }
+    if workspace::is_selected(matches) {
+        let members = workspace::members(matches)?;
+        if let Some(root) = workspace::fixed_root(matches)? {
+            return apply_bumpfile(matches, root);
+        }
+        let names: Vec<String> = members.iter().map(|member| member.name.clone()).collect();
+        for member in members {
+            apply_bumpfile(matches, member.bumpfile)?;
+        }
+        // Phase and calendar bumps stay within the member; dependents only follow a
+        // major, minor, or patch release.
+        if ["major", "minor", "patch"]
+            .iter()
+            .any(|level| matches.get_flag(level))
+        {
+            return workspace::cascade(matches, &names);
+        }
+        return Ok(());
+    }
+    apply_bumpfile(matches, load_bumpfile(matches)?)
+}
+
+pub fn apply_bumpfile(matches: &ArgMatches, mut bumpfile: BumpFile) -> Result<(), BumpError> {
+    let version = with_meta_flags(matches, bumpfile.version()?)?;
+
+    if matches.contains_id("formal") {
+        bump_bumpfile(&mut bumpfile, version, &get_bump_type(matches)?)
+    } else if has_meta_flags(matches) {
+        bumpfile.save(&version)
+    } else {
+        Ok(())
+    }
+}
 
+/// `version` with the `--prefix` and `--suffix` given on the command line applied.
+pub fn with_meta_flags(matches: &ArgMatches, mut version: Version) -> Result<Version, BumpError> {
     if let Some(prefix) = matches.get_one::<String>("prefix") {
         version.prefix.clone_from(prefix);
+        version.validate().map_err(BumpError::LogicError)?;
     }
     if let Some(suffix) = matches.get_one::<String>("suffix") {
         version.suffix.mode = crate::version::SuffixMode::parse(suffix)?;
     }
+    Ok(version)
+}
+
+/// `bump convert --to MODE`: rewrite the bumpfile in the other versioning scheme.
+pub fn convert(matches: &ArgMatches) -> Result<(), BumpError> {
+    let mode = match matches.get_one::<String>("to").map(String::as_str) {
+        Some("calver") => VersionMode::Calver,
+        _ => VersionMode::Semver,
+    };
+    let mut bumpfile = load_bumpfile(matches)?;
+    let mut version = bumpfile.version()?;
+    let previous = print::to_string(&version, &PrintOptions::default())?;
+    version.convert(mode)?;
+    let converted = print::to_string(&version, &PrintOptions::default())?;
+    bumpfile.save(&version)?;
+    history::record(
+        &version,
+        bumpfile.path(),
+        "converted",
+        Some(&previous),
+        &converted,
+    )?;
+    println!(
+        "converted {} to {}: {previous} -> {converted}",
+        bumpfile.path().display(),
+        mode.as_str()
+    );
+    Ok(())
+}
 
-    if has_formal {
-        version.bump(&get_bump_type(matches)?)?;
-        println!(
-            "bumped {} to {}",
-            bumpfile.path().display(),
-            print::to_string(&version, &PrintOptions::with_timestamp())?
-        );
+/// Bump `version`, run its hooks and notifications, and save it to `bumpfile`.
+pub fn bump_bumpfile(
+    bumpfile: &mut BumpFile,
+    version: Version,
+    bump_type: &BumpType,
+) -> Result<(), BumpError> {
+    let version = save_bump(bumpfile, version, bump_type, true)?;
+    finish_bump(&version)
+}
+
+/// Bump `version` and save it, the files it updates and a history entry to `bumpfile`,
+/// running the `pre_bump` hooks first when `pre_hooks` is set.
+pub fn save_bump(
+    bumpfile: &mut BumpFile,
+    mut version: Version,
+    bump_type: &BumpType,
+    pre_hooks: bool,
+) -> Result<Version, BumpError> {
+    let before = version.clone();
+    let previous = print::to_string(&version, &PrintOptions::default())?;
+    version.bump(bump_type)?;
+    version_code::advance(&mut version)?;
+    let version_string = print::to_string(&version, &PrintOptions::default())?;
+    let files = update::configured_files(&before, &version, bumpfile.path())?;
+    if pre_hooks {
+        let hooks = version.hooks.clone().unwrap_or_default();
+        run_commands(&hooks.pre_bump, &version_string)?;
     }
+    println!(
+        "bumped {} to {}",
+        bumpfile.path().display(),
+        print::to_string(&version, &PrintOptions::with_timestamp())?
+    );
+    update::transaction(|| {
+        update::write_configured_files(&files)?;
+        bumpfile.save(&version)
+    })?;
+    history::record(
+        &version,
+        bumpfile.path(),
+        "bumped",
+        Some(&previous),
+        &version_string,
+    )?;
+    Ok(version)
+}
+
+/// Run the `post_bump` hooks and send the notification for a bump `save_bump` saved.
+pub fn finish_bump(version: &Version) -> Result<(), BumpError> {
+    let hooks = version.hooks.clone().unwrap_or_default();
+    let version_string = print::to_string(version, &PrintOptions::default())?;
+    run_commands(&hooks.post_bump, &version_string)?;
+    notify::send(version, "bumped")
+}
 
-    if has_meta || has_formal {
-        bumpfile.save(&version)?;
+/// Git answers memoized for the current invocation.
+///
+/// Cleared whenever bump runs something that may move HEAD or create tags
+/// (hook and publish commands, `bump tag`).
+struct GitCache {
+    is_repository: Option<bool>,
+    sha: Option<String>,
+    full_sha: Option<String>,
+    branch: Option<Option<String>>,
+    latest_tag: Option<Option<String>>,
+    tag_names: Option<Vec<String>>,
+    dirty: Option<Option<bool>>,
+}
+
+static GIT_CACHE: Mutex<GitCache> = Mutex::new(GitCache {
+    is_repository: None,
+    sha: None,
+    full_sha: None,
+    branch: None,
+    latest_tag: None,
+    tag_names: None,
+    dirty: None,
+});
+
+fn cached<T: Clone>(
+    field: fn(&mut GitCache) -> &mut Option<T>,
+    query: impl FnOnce() -> Result<T, BumpError>,
+) -> Result<T, BumpError> {
+    let mut cache = GIT_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(value) = field(&mut cache) {
+        return Ok(value.clone());
     }
+    let value = query()?;
+    *field(&mut cache) = Some(value.clone());
+    Ok(value)
+}
 
-    Ok(())
+pub fn clear_git_cache() {
+    let mut cache = GIT_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    cache.is_repository = None;
+    cache.sha = None;
+    cache.full_sha = None;
+    cache.branch = None;
+    cache.latest_tag = None;
+    cache.tag_names = None;
+    cache.dirty = None;
 }
 
-fn git_cmd() -> ProcessCommand {
+pub fn git_cmd() -> ProcessCommand {
     ProcessCommand::new("git")
 }
 
@@ -161,37 +385,382 @@ pub fn run_git(command: &str) -> Result<String, BumpError> {
     Ok(String::from_utf8_lossy(&output.stderr).trim().to_string())
 }
 
+static GIT_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Treat the working directory as outside any repository (`--no-git`).
+pub fn disable_git() {
+    GIT_DISABLED.store(true, Ordering::Relaxed);
+}
+
 pub fn is_git_repository() -> bool {
-    git_cmd()
-        .args(["rev-parse", "--git-dir"])
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+    if GIT_DISABLED.load(Ordering::Relaxed) {
+        return false;
+    }
+    cached(
+        |cache| &mut cache.is_repository,
+        || Ok(git_backend::is_repository()),
+    )
+    .unwrap_or(false)
 }
 
 pub fn get_git_commit_sha() -> Result<String, BumpError> {
-    run_git("rev-parse --short HEAD")
+    cached(|cache| &mut cache.sha, git_backend::short_sha)
 }
 
-pub fn get_git_branch() -> Result<String, BumpError> {
-    run_git("rev-parse --abbrev-ref HEAD")
+/// The latest release tag, or `None` when there is none: the nearest tag reachable
+/// from HEAD, or with `[tags] latest = "highest"` the highest of `version`'s tags.
+pub fn get_git_latest_tag(version: &Version) -> Result<Option<String>, BumpError> {
+    let latest = version.tags.as_ref().map(|tags| tags.latest);
+    if latest != Some(LatestTag::Highest) {
+        return cached(|cache| &mut cache.latest_tag, git_backend::latest_tag);
+    }
+    let names = cached(|cache| &mut cache.tag_names, git_backend::tag_names)?;
+    Ok(names
+        .iter()
+        .filter_map(|name| Some((name, version.parse_tag(name)?)))
+        .max_by(|(_, a), (_, b)| a.cmp_precedence(b))
+        .map(|(name, _)| name.clone()))
 }
 
-pub fn generate(matches: &ArgMatches, lang: Language) -> Result<(), BumpError> {
-    let bumpfile = load_bumpfile(matches)?;
-    let version = bumpfile.version()?;
-    let output_files: Vec<&String> = matches.get_many::<String>("output").unwrap().collect();
-    for output_file in output_files {
-        let output_path = Path::new(output_file);
+pub fn get_git_commit_full_sha() -> Result<String, BumpError> {
+    cached(|cache| &mut cache.full_sha, git_backend::full_sha)
+}
 
-        ensure_directory_exists(output_path)?;
-        lang::output_file(lang, &version, output_path)?;
+/// Whether the worktree has uncommitted changes to tracked files; `None` without the
+/// git binary.
+pub fn get_git_dirty() -> Result<Option<bool>, BumpError> {
+    cached(|cache| &mut cache.dirty, git_backend::is_dirty)
+}
+
+/// CI variables naming the branch being built, checked in order on a detached HEAD.
+const CI_BRANCH_VARS: &[&str] = &[
+    "GITHUB_HEAD_REF",
+    "GITHUB_REF_NAME",
+    "CI_COMMIT_REF_NAME",
+    "BUILDKITE_BRANCH",
+    "BRANCH_NAME",
+    "GIT_BRANCH",
+];
+
+/// The current branch, or `None` on a detached HEAD that no CI variable resolves.
+pub fn get_git_branch() -> Result<Option<String>, BumpError> {
+    cached(
+        |cache| &mut cache.branch,
+        || {
+            if let Some(branch) = git_backend::head_branch()? {
+                return Ok(Some(branch));
+            }
+            Ok(CI_BRANCH_VARS
+                .iter()
+                .filter_map(|var| std::env::var(var).ok())
+                .find(|value| !value.is_empty())
+                .map(|value| value.strip_prefix("origin/").unwrap_or(&value).to_string()))
+        },
+    )
+}
+
+/// GET `url` with the system curl, returning the HTTP status and body.
+pub fn http_get(url: &str) -> Result<(u16, String), BumpError> {
+    let output = ProcessCommand::new("curl")
+        .args(["-sS", "-L", "--max-time", "30", "-w", "\n%{http_code}", url])
+        .output()
+        .map_err(|e| BumpError::Http(format!("curl {url}: {e}")))?;
+    if !output.status.success() {
+        return Err(BumpError::Http(format!(
+            "curl {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let status = status
+        .trim()
+        .parse()
+        .map_err(|_| BumpError::Http(format!("curl {url}: unexpected status '{status}'")))?;
+    Ok((status, body.to_string()))
+}
+
+/// POST a JSON `body` to `url` with the system curl.
+pub fn http_post_json(url: &str, body: &str) -> Result<(), BumpError> {
+    let output = ProcessCommand::new("curl")
+        .args(["-sS", "-f", "--max-time", "30", "-X", "POST"])
+        .args([
+            "-H",
+            "Content-Type: application/json",
+            "--data-binary",
+            body,
+            url,
+        ])
+        .output()
+        .map_err(|e| BumpError::Http(format!("curl {url}: {e}")))?;
+    if !output.status.success() {
+        return Err(BumpError::Http(format!(
+            "POST {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
     }
+    Ok(())
+}
 
+/// Run `command` through the platform shell with `BUMP_VERSION` set.
+pub fn run_shell(command: &str, version: &str) -> Result<(), BumpError> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = ProcessCommand::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = ProcessCommand::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+    let status = cmd
+        .env("BUMP_VERSION", version)
+        .status()
+        .map_err(|e| BumpError::LogicError(format!("failed to run '{command}': {e}")))?;
+    clear_git_cache();
+    if !status.success() {
+        return Err(BumpError::LogicError(format!(
+            "'{command}' failed ({status})"
+        )));
+    }
+    Ok(())
+}
+
+fn run_commands(commands: &[String], version: &str) -> Result<(), BumpError> {
+    for command in commands {
+        println!("Running '{command}'");
+        run_shell(command, version)?;
+    }
     Ok(())
 }
 
-fn git_tag_exists(tag_name: &str) -> Result<bool, BumpError> {
+pub fn generate(matches: &ArgMatches, lang: Option<Language>) -> Result<(), BumpError> {
+    // Relative outputs land under --output-dir (per member name in a workspace) instead
+    // of the working or member directory, for read-only source trees.
+    let output_dir = matches.get_one::<String>("output-dir").map(PathBuf::from);
+    let out_dir = output_dir.clone().unwrap_or_default();
+    let stale = if workspace::is_selected(matches) {
+        let mut stale = 0;
+        for member in workspace::members(matches)? {
+            let outputs = gen_outputs(matches, lang, Some(&member.bumpfile))?;
+            let member_out_dir = output_dir
+                .as_ref()
+                .map_or_else(|| member.dir.clone(), |dir| dir.join(&member.name));
+            stale += gen_version(
+                matches,
+                &outputs,
+                &member.bumpfile.version()?,
+                Some(member.bumpfile.path()),
+                &member_out_dir,
+            )?;
+        }
+        stale
+    } else if matches.get_flag("version-from-stdin") {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(BumpError::IoError)?;
+        let version = version_from_input(&input)?;
+        let outputs = gen_outputs(matches, lang, None)?;
+        gen_version(matches, &outputs, &version, None, &out_dir)?
+    } else {
+        let bumpfile = load_bumpfile(matches)?;
+        let outputs = gen_outputs(matches, lang, Some(&bumpfile))?;
+        gen_version(
+            matches,
+            &outputs,
+            &bumpfile.version()?,
+            Some(bumpfile.path()),
+            &out_dir,
+        )?
+    };
+    if stale > 0 {
+        return Err(BumpError::LogicError(format!(
+            "{stale} generated file(s) out of date; run 'bump gen' to update them"
+        )));
+    }
+    Ok(())
+}
+
+/// A version handed over by another tool: a plain version string such as `1.2.0-rc.1`
+/// (read with the `bump init` defaults), or JSON holding a version string, a
+/// serialized version, or the plugin context (`{"version_string", "version"}`).
+fn version_from_input(input: &str) -> Result<Version, BumpError> {
+    let input = input.trim();
+    let invalid = |reason: String| BumpError::ParseError(format!("version on stdin: {reason}"));
+    let from_str = |text: &str| {
+        let mut version = bumpfile::template_version();
+        version.set_from_str(text)?;
+        Ok(version)
+    };
+    if !input.starts_with(['{', '"']) {
+        return from_str(input);
+    }
+    let json: serde_json::Value =
+        serde_json::from_str(input).map_err(|e| invalid(format!("invalid JSON: {e}")))?;
+    let version = match &json {
+        serde_json::Value::String(text) => return from_str(text),
+        serde_json::Value::Object(object) => match object.get("version") {
+            Some(version @ serde_json::Value::Object(_)) => version.clone(),
+            Some(serde_json::Value::String(text)) => return from_str(text),
+            _ if object.contains_key("base") => json.clone(),
+            _ => match object.get("version_string") {
+                Some(serde_json::Value::String(text)) => return from_str(text),
+                _ => {
+                    return Err(invalid(
+                        "expected a version string, a version object or 'version_string'"
+                            .to_string(),
+                    ));
+                }
+            },
+        },
+        _ => return Err(invalid("expected a string or an object".to_string())),
+    };
+    let version: Version = serde_json::from_value(version).map_err(|e| invalid(e.to_string()))?;
+    version.validate().map_err(invalid)?;
+    Ok(version)
+}
+
+/// An `--output` value: `FILE:LANG`, or `FILE` in `--lang`. The suffix only counts
+/// when it names a language, so `C:\out\version.h` stays a path.
+fn output_spec(spec: &str, lang: Option<Language>) -> Result<(Language, PathBuf), BumpError> {
+    if let Some((path, suffix)) = spec.rsplit_once(':')
+        && !path.is_empty()
+        && let Some(lang) = Language::from_str(suffix)
+    {
+        return Ok((lang, PathBuf::from(path)));
+    }
+    let lang = lang.ok_or_else(|| {
+        BumpError::LogicError(format!(
+            "no language for output '{spec}'; give --lang or write it as '{spec}:LANG'"
+        ))
+    })?;
+    Ok((lang, PathBuf::from(spec)))
+}
+
+/// The files to write: `--output` (with `--lang` or per-file languages) when given,
+/// otherwise the `[[gen]]` entries declared in `config`.
+fn gen_outputs(
+    matches: &ArgMatches,
+    lang: Option<Language>,
+    config: Option<&BumpFile>,
+) -> Result<Vec<(Language, PathBuf, OutputOptions)>, BumpError> {
+    if !matches.contains_id("output") {
+        let Some(config) = config else {
+            return Err(BumpError::LogicError(
+                "no --lang/--output given".to_string(),
+            ));
+        };
+        let outputs = lang::configured_outputs(config)?;
+        if outputs.is_empty() {
+            return Err(BumpError::LogicError(format!(
+                "no --lang/--output given and no [[gen]] outputs in {}",
+                config.path().display()
+            )));
+        }
+        return Ok(every_output(matches, outputs));
+    };
+    let options = OutputOptions {
+        symbol_prefix: matches
+            .get_one::<String>("symbol-prefix")
+            .cloned()
+            .unwrap_or_default(),
+        packed: matches
+            .get_one::<String>("packed")
+            .and_then(|packed| Packing::parse(packed)),
+        guard: matches.get_one::<String>("guard").cloned(),
+        max_string_length: matches.get_one::<usize>("max-string-length").copied(),
+        git_info: matches.get_flag("git-info"),
+        build_number_env: matches.get_one::<String>("build-number-env").cloned(),
+        build_number_in_full: matches.get_flag("build-number-in-full"),
+        module: matches
+            .get_one::<String>("module")
+            .and_then(|module| ModuleFormat::parse(module)),
+        package: matches.get_one::<String>("package").cloned(),
+        namespace: matches.get_one::<String>("namespace").cloned(),
+        class_name: matches.get_one::<String>("class-name").cloned(),
+        template: matches.get_one::<String>("template").map(PathBuf::from),
+        reproducible: false,
+        force: false,
+    };
+    let outputs = matches
+        .get_many::<String>("output")
+        .into_iter()
+        .flatten()
+        .map(|output| {
+            let (lang, path) = output_spec(output, lang)?;
+            Ok((lang, path, options.clone()))
+        })
+        .collect::<Result<_, BumpError>>()?;
+    Ok(every_output(matches, outputs))
+}
+
+/// `--force` and `--reproducible` apply to every output, on top of the same keys in
+/// `[[gen]]` entries.
+fn every_output(
+    matches: &ArgMatches,
+    mut outputs: Vec<(Language, PathBuf, OutputOptions)>,
+) -> Vec<(Language, PathBuf, OutputOptions)> {
+    for (_, _, options) in &mut outputs {
+        options.force |= matches.get_flag("force");
+        options.reproducible |= matches.get_flag("reproducible");
+    }
+    outputs
+}
+
+/// Write every output for `version`, refusing to overwrite `bumpfile` when there is
+/// one. With `--check`, print a diff for each output that is out of date instead and
+/// return how many are.
+fn gen_version(
+    matches: &ArgMatches,
+    outputs: &[(Language, PathBuf, OutputOptions)],
+    version: &Version,
+    bumpfile: Option<&Path>,
+    out_dir: &Path,
+) -> Result<usize, BumpError> {
+    let outputs: Vec<(Language, PathBuf, &OutputOptions)> = outputs
+        .iter()
+        .map(|(lang, output, options)| (*lang, out_dir.join(output), options))
+        .collect();
+    let mut seen = HashSet::new();
+    for (_, output_path, _) in &outputs {
+        if let Some(bumpfile) = bumpfile {
+            guard_bumpfile(bumpfile, output_path)?;
+        }
+        if !seen.insert(canonical_path(&resolve_path(
+            &output_path.to_string_lossy(),
+        ))) {
+            return Err(BumpError::LogicError(format!(
+                "output '{}' is given more than once",
+                output_path.display()
+            )));
+        }
+    }
+    if matches.get_flag("check") {
+        let mut stale = 0;
+        for (lang, output_path, options) in &outputs {
+            if let Some(diff) = lang::stale_diff(*lang, version, output_path, options)? {
+                print!("{diff}");
+                stale += 1;
+            }
+        }
+        return Ok(stale);
+    }
+    // Render everything before writing anything, so a failing output leaves the
+    // others as they were.
+    let contents = outputs
+        .iter()
+        .map(|(lang, output_path, options)| lang::render(*lang, version, output_path, options))
+        .collect::<Result<Vec<_>, _>>()?;
+    for ((lang, output_path, options), content) in outputs.iter().zip(contents) {
+        ensure_directory_exists(output_path)?;
+        lang::output_file(*lang, output_path, content, options)?;
+    }
+
+    Ok(0)
+}
+
+pub fn git_tag_exists(tag_name: &str) -> Result<bool, BumpError> {
     let output = git_cmd()
         .args([
             "rev-parse",
@@ -205,7 +774,11 @@ fn git_tag_exists(tag_name: &str) -> Result<bool, BumpError> {
     Ok(output.status.success())
 }
 
-pub fn create_git_tag(version: &Version, message: Option<&str>) -> Result<(), BumpError> {
+pub fn create_git_tag(
+    version: &Version,
+    message: Option<&str>,
+    with_changelog: bool,
+) -> Result<(), BumpError> {
     if !is_git_repository() {
         return Err(BumpError::LogicError("Not in a git repository".to_string()));
     }
@@ -216,15 +789,17 @@ pub fn create_git_tag(version: &Version, message: Option<&str>) -> Result<(), Bu
         return Err(BumpError::Git(format!("Tag '{tag_name}' already exists")));
     }
 
-    let mut cmd = git_cmd();
-    cmd.args(["tag", "-a", &tag_name]);
-
-    if let Some(msg) = message {
-        cmd.args(["-m", msg]);
-    } else {
-        let default_message = format!("chore(release): bump version to {tag_name}");
-        cmd.args(["-m", &default_message]);
+    let mut annotation = message.map_or_else(
+        || format!("chore(release): bump version to {tag_name}"),
+        str::to_string,
+    );
+    if with_changelog {
+        annotation.push_str(&changelog::section(version)?);
     }
+    annotation.push_str(&issues::section(version)?);
+
+    let mut cmd = git_cmd();
+    cmd.args(["tag", "-a", &tag_name, "-m", &annotation]);
 
     let output = cmd
         .output()
@@ -237,14 +812,69 @@ pub fn create_git_tag(version: &Version, message: Option<&str>) -> Result<(), Bu
         )));
     }
 
+    clear_git_cache();
     println!("Created git tag: {tag_name}");
-    Ok(())
+    notify::send(version, "tagged")
 }
 
 pub fn tag_version(matches: &ArgMatches) -> Result<(), BumpError> {
-    let bumpfile = load_bumpfile(matches)?;
+    if workspace::is_selected(matches) {
+        let members = workspace::members(matches)?;
+        if let Some(root) = workspace::fixed_root(matches)? {
+            return tag_bumpfile(matches, &root);
+        }
+        for member in members {
+            tag_bumpfile(matches, &member.bumpfile)?;
+        }
+        return Ok(());
+    }
+    tag_bumpfile(matches, &load_bumpfile(matches)?)
+}
+
+fn tag_bumpfile(matches: &ArgMatches, bumpfile: &BumpFile) -> Result<(), BumpError> {
     let version = bumpfile.version()?;
     let message = matches.get_one::<String>("message");
+    let tag_name = print::to_string(&version, &PrintOptions::default())?;
+    let hooks = version.hooks.clone().unwrap_or_default();
+    let publish = version
+        .publish
+        .as_ref()
+        .map(|publish| publish.commands.as_slice())
+        .unwrap_or_default();
+
+    if matches.get_flag("dry-run") {
+        for command in &hooks.pre_tag {
+            println!("dry run: would run '{command}'");
+        }
+        println!("dry run: would create git tag {tag_name}");
+        aliases::dry_run(&version, &tag_name, bumpfile.path());
+        if matches.get_flag("with-changelog") {
+            println!("dry run: changelog:{}", changelog::section(&version)?);
+        }
+        let issues = issues::section(&version)?;
+        if !issues.is_empty() {
+            println!("dry run: issues:{}", issues.trim_end());
+        }
+        if let Some(output) = matches.get_one::<String>("provenance") {
+            println!("dry run: would write provenance to {output}");
+        }
+        for command in hooks.post_tag.iter().chain(publish) {
+            println!("dry run: would run '{command}'");
+        }
+        return Ok(());
+    }
 
-    create_git_tag(&version, message.map(String::as_str))
+    run_commands(&hooks.pre_tag, &tag_name)?;
+    create_git_tag(
+        &version,
+        message.map(String::as_str),
+        matches.get_flag("with-changelog"),
+    )?;
+    history::record(&version, bumpfile.path(), "tagged", None, &tag_name)?;
+    aliases::update(&version, &tag_name, bumpfile.path())?;
+    if let Some(output) = matches.get_one::<String>("provenance") {
+        provenance::write(&tag_name, bumpfile.path(), Path::new(output))?;
+    }
+    run_commands(&hooks.post_tag, &tag_name)?;
+    run_commands(publish, &tag_name)
 }
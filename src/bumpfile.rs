@@ -1,16 +1,28 @@
-use crate::bump::{BumpError, ensure_directory_exists};
+use crate::bump::{BumpError, ensure_directory_exists, http_get, write_error};
+use crate::integrity;
 use crate::version::{Version, VersionMode};
 use std::{
     fmt, fs, io,
     path::{Path, PathBuf},
 };
-use toml_edit::{DocumentMut, Table, Value, value};
+use toml_edit::{DocumentMut, Item, TableLike, Value};
 
 const INIT_TEMPLATE_TIMESTAMP: &str = "1970-01-01 00:00:00 UTC";
 
+/// The version `bump init` writes, carrying the default separators and formats.
+pub fn template_version() -> Version {
+    let content =
+        include_str!("templates/bump.toml").replace("{timestamp}", INIT_TEMPLATE_TIMESTAMP);
+    toml::from_str(&content).expect("init template must deserialize")
+}
+
 pub struct BumpFile {
     path: PathBuf,
     doc: DocumentMut,
+    /// The merged contents of the files named by `include`, when there is one.
+    included: Option<DocumentMut>,
+    /// Fetched over HTTP(S); readable, but never written back.
+    remote: bool,
 }
 
 fn bumpfile_parse_error(path: &Path, message: impl fmt::Display) -> BumpError {
@@ -20,47 +32,248 @@ fn bumpfile_parse_error(path: &Path, message: impl fmt::Display) -> BumpError {
     ))
 }
 
-fn table<'a>(doc: &'a DocumentMut, section: &str, path: &Path) -> Result<&'a Table, BumpError> {
+/// Levenshtein distance, used to suggest fixes for misspelled keys and tables.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Top-level keys and tables bump reads; update this with every table added to a bumpfile.
+const KNOWN_TABLES: &[&str] = &[
+    "prefix",
+    "include",
+    "base",
+    "phase",
+    "suffix",
+    "timestamp",
+    "label",
+    "branch_prefix",
+    "notify",
+    "publish",
+    "hooks",
+    "issues",
+    "wasm",
+    "version_code",
+    "msi",
+    "rc",
+    "helm",
+    "docker",
+    "markdown",
+    "aliases",
+    "guard",
+    "integrity",
+    "history",
+    "tags",
+    "update",
+    "gen",
+    "workspace",
+];
+
+/// Keys within those tables; never suggested as a typo of one another (`major` vs `minor`).
+const KNOWN_KEYS: &[&str] = &[
+    "mode",
+    "delimiter",
+    "major",
+    "minor",
+    "patch",
+    "year",
+    "month",
+    "day",
+    "format",
+    "period_weeks",
+    "separator",
+    "name",
+    "distance",
+    "detached",
+    "sanitize",
+    "last",
+    "clock",
+    "position",
+    "url",
+    "kind",
+    "message",
+    "commands",
+    "pre_bump",
+    "post_bump",
+    "pre_tag",
+    "post_tag",
+    "keys",
+    "target",
+    "module",
+    "strategy",
+    "candidate",
+    "upgrade_code",
+    "product_code",
+    "file_build",
+    "product_build",
+    "fields",
+    "images",
+    "tag",
+    "patterns",
+    "tags",
+    "files",
+    "paths",
+    "checksum",
+    "key_env",
+    "file",
+    "path",
+    "search",
+    "replace",
+    "latest",
+    "lang",
+    "output",
+    "symbol_prefix",
+    "packed",
+    "guard",
+    "max_string_length",
+    "git_info",
+    "build_number_env",
+    "build_number_in_full",
+    "package",
+    "namespace",
+    "class_name",
+    "template",
+    "reproducible",
+    "force",
+    "members",
+    "depends",
+    "cascade",
+];
+
+fn is_typo_of(candidate: &str, expected: &str) -> bool {
+    !KNOWN_TABLES.contains(&candidate)
+        && !KNOWN_KEYS.contains(&candidate)
+        && candidate != expected
+        && edit_distance(candidate, expected) <= 2
+}
+
+/// Warn about a top-level table bump ignores because its name is a typo of one it reads.
+fn warn_unknown_tables(path: &Path, doc: &DocumentMut) {
+    for (key, _) in doc.iter() {
+        if let Some(known) = KNOWN_TABLES.iter().find(|known| is_typo_of(key, known)) {
+            eprintln!(
+                "bump warning: [{key}] in {} is not a bumpfile table and is ignored; did you mean [{known}]?",
+                path.display()
+            );
+        }
+    }
+}
+
+/// Explain why `section` is missing when the document has something close to it.
+fn table_hint(doc: &DocumentMut, section: &str) -> String {
+    if doc.contains_key("workspace") {
+        return " (this is a [workspace] root; pass --member NAME or --all)".to_string();
+    }
+    if doc.get(section).is_some_and(Item::is_array_of_tables) {
+        return format!(" ([[{section}]] is an array of tables, use [{section}])");
+    }
+    if let Some(old) = ["semver", "calver"]
+        .iter()
+        .find(|old| doc.contains_key(old))
+    {
+        return format!(
+            " (found bump v6 [{old}.*] tables; v7 uses [base], [phase], [suffix], [timestamp] and [label])"
+        );
+    }
+    doc.iter()
+        .map(|(key, _)| key)
+        .find(|key| is_typo_of(key, section))
+        .map(|key| format!(" (did you mean [{section}] instead of [{key}]?)"))
+        .unwrap_or_default()
+}
+
+/// Suggest a rename when serde reports ``missing field `name` `` and a close key exists.
+fn missing_field_hint(doc: &DocumentMut, message: &str) -> Option<String> {
+    let field = message.strip_prefix("missing field `")?.split('`').next()?;
+    let mut tables = vec![("(root)", doc.as_table() as &dyn TableLike)];
+    tables.extend(
+        doc.iter()
+            .filter_map(|(name, item)| Some((name, item.as_table_like()?))),
+    );
+    tables.into_iter().find_map(|(name, table)| {
+        table
+            .iter()
+            .map(|(key, _)| key)
+            .find(|key| is_typo_of(key, field))
+            .map(|key| format!("hint: did you mean `{field}` instead of `{key}` in [{name}]?"))
+    })
+}
+
+fn table<'a>(
+    doc: &'a DocumentMut,
+    section: &str,
+    path: &Path,
+) -> Result<&'a dyn TableLike, BumpError> {
     doc.get(section)
-        .and_then(|item| item.as_table())
-        .ok_or_else(|| bumpfile_parse_error(path, format!("'{section}' table not found")))
+        .and_then(Item::as_table_like)
+        .ok_or_else(|| {
+            bumpfile_parse_error(
+                path,
+                format!("'{section}' table not found{}", table_hint(doc, section)),
+            )
+        })
 }
 
 fn table_mut<'a>(
     doc: &'a mut DocumentMut,
     section: &str,
     path: &Path,
-) -> Result<&'a mut Table, BumpError> {
+) -> Result<&'a mut dyn TableLike, BumpError> {
     doc.get_mut(section)
-        .and_then(|item| item.as_table_mut())
+        .and_then(Item::as_table_like_mut)
         .ok_or_else(|| bumpfile_parse_error(path, format!("'{section}' table not found")))
 }
 
+/// Replace the value at `key`, keeping the whitespace and comments around it.
 fn set<V: Into<Value>>(
-    table: &mut Table,
+    table: &mut dyn TableLike,
     key: &str,
     val: V,
     section: &str,
     path: &Path,
 ) -> Result<(), BumpError> {
-    if !table.contains_key(key) {
+    let Some(item) = table.get_mut(key) else {
         return Err(bumpfile_parse_error(
             path,
             format!("Expected key '{key}' not found in [{section}]"),
         ));
+    };
+    let mut new_value = val.into();
+    if let Some(old_value) = item.as_value() {
+        *new_value.decor_mut() = old_value.decor().clone();
     }
-    table[key] = value(val);
+    *item = Item::Value(new_value);
     Ok(())
 }
 
 fn set_or_remove(
-    table: &mut Table,
+    table: &mut dyn TableLike,
     key: &str,
     val: Option<u32>,
     section: &str,
     path: &Path,
 ) -> Result<(), BumpError> {
     match val {
+        Some(n) if !table.contains_key(key) => {
+            // A component the file did not have yet, such as the quarter of a new
+            // `[base].format`.
+            table.insert(key, Item::Value(i64::from(n).into()));
+            Ok(())
+        }
         Some(n) => set(table, key, i64::from(n), section, path),
         None => {
             table.remove(key);
@@ -104,7 +317,7 @@ fn write_base(doc: &mut DocumentMut, version: &Version, path: &Path) -> Result<(
             ("major", "minor", "patch", "year", "month", "day")
         };
 
-    set(base, major_key, i64::from(version.base.major), "base", path)?;
+    set_or_remove(base, major_key, Some(version.base.major), "base", path)?;
     base.remove(old_major);
 
     set_or_remove(base, minor_key, version.base.minor, "base", path)?;
@@ -113,6 +326,12 @@ fn write_base(doc: &mut DocumentMut, version: &Version, path: &Path) -> Result<(
     set_or_remove(base, patch_key, version.base.patch, "base", path)?;
     base.remove(old_patch);
 
+    // Calendar periods mean nothing to SemVer (`bump convert --to semver`).
+    if version.base.mode == VersionMode::Semver {
+        base.remove("format");
+        base.remove("period_weeks");
+    }
+
     Ok(())
 }
 
@@ -121,13 +340,13 @@ fn write_version_into_doc(
     version: &Version,
     path: &Path,
 ) -> Result<(), BumpError> {
-    if !doc.contains_key("prefix") {
-        return Err(bumpfile_parse_error(
-            path,
-            "Expected key 'prefix' not found in [(root)]",
-        ));
-    }
-    doc["prefix"] = value(&version.prefix);
+    set(
+        doc.as_table_mut(),
+        "prefix",
+        &version.prefix,
+        "(root)",
+        path,
+    )?;
 
     let timestamp = table_mut(doc, "timestamp", path)?;
     set(
@@ -145,8 +364,33 @@ fn write_version_into_doc(
         path,
     )?;
 
+    if let Some(version_code) = &version.version_code {
+        let table = table_mut(doc, "version_code", path)?;
+        if table.contains_key("last") {
+            set(
+                table,
+                "last",
+                i64::from(version_code.last),
+                "version_code",
+                path,
+            )?;
+        } else {
+            table.insert("last", Item::Value(i64::from(version_code.last).into()));
+        }
+    }
+
     write_base(doc, version, path)?;
 
+    if let Some(integrity) = &version.integrity {
+        let checksum = integrity::checksum(version, integrity)?;
+        let table = table_mut(doc, "integrity", path)?;
+        if table.contains_key("checksum") {
+            set(table, "checksum", checksum, "integrity", path)?;
+        } else {
+            table.insert("checksum", Item::Value(checksum.into()));
+        }
+    }
+
     let phase = table_mut(doc, "phase", path)?;
     set(phase, "separator", &version.phase.separator, "phase", path)?;
     set(phase, "name", &version.phase.name, "phase", path)?;
@@ -181,6 +425,117 @@ fn write_version_into_doc(
     Ok(())
 }
 
+/// Copy `local` over `base`: tables are merged key by key, anything else (including
+/// arrays of tables such as `[[gen]]`) replaces the base entry.
+fn merge(base: &mut dyn TableLike, local: &dyn TableLike) {
+    for (key, item) in local.iter() {
+        match (
+            base.get_mut(key).and_then(Item::as_table_like_mut),
+            item.as_table_like(),
+        ) {
+            (Some(base_table), Some(local_table)) => merge(base_table, local_table),
+            _ => {
+                base.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+/// Read `path` with everything it includes: `include = "base.toml"` (relative to
+/// `path`) is read first, recursively, and `path`'s own keys win. The `include` key
+/// itself is dropped.
+fn read_included(path: &Path, chain: &mut Vec<PathBuf>) -> Result<DocumentMut, BumpError> {
+    let canonical = fs::canonicalize(path).map_err(|err| {
+        BumpError::LogicError(format!("include '{}' not found: {err}", path.display()))
+    })?;
+    if chain.contains(&canonical) {
+        return Err(BumpError::LogicError(format!(
+            "include cycle: {} includes itself",
+            path.display()
+        )));
+    }
+    chain.push(canonical);
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let mut doc = content.parse::<DocumentMut>().map_err(|e| {
+        BumpError::ParseError(format!(
+            "Failed to parse TOML document '{}': {}",
+            path.display(),
+            e.to_string().trim_end()
+        ))
+    })?;
+    match included_path(&doc, path)? {
+        Some(base_path) => {
+            let mut base = read_included(&base_path, chain)?;
+            doc.remove("include");
+            merge(base.as_table_mut(), doc.as_table());
+            Ok(base)
+        }
+        None => Ok(doc),
+    }
+}
+
+/// The file named by `doc`'s `include`, relative to the directory of `path`.
+fn included_path(doc: &DocumentMut, path: &Path) -> Result<Option<PathBuf>, BumpError> {
+    let Some(item) = doc.get("include") else {
+        return Ok(None);
+    };
+    let include = item
+        .as_str()
+        .ok_or_else(|| bumpfile_parse_error(path, "'include' must be a path string".to_string()))?;
+    Ok(Some(path.parent().unwrap_or(Path::new(".")).join(include)))
+}
+
+fn same_value(a: &Value, b: &Value) -> bool {
+    a.clone().decorated("", "").to_string() == b.clone().decorated("", "").to_string()
+}
+
+/// Write the values of `merged` (the written version over the included files) back
+/// into `local`, keeping only what `local` already sets or what no longer matches
+/// the included files, so shared settings stay in the shared file.
+fn overlay(local: &mut dyn TableLike, base: Option<&dyn TableLike>, merged: &dyn TableLike) {
+    for (key, item) in merged.iter() {
+        let base_item = base.and_then(|base| base.get(key));
+        if let Some(merged_table) = item.as_table_like() {
+            let base_table = base_item.and_then(Item::as_table_like);
+            match local.get_mut(key).and_then(Item::as_table_like_mut) {
+                Some(local_table) => overlay(local_table, base_table, merged_table),
+                None => {
+                    let mut table = toml_edit::Table::new();
+                    overlay(&mut table, base_table, merged_table);
+                    if !table.is_empty() {
+                        local.insert(key, Item::Table(table));
+                    }
+                }
+            }
+            continue;
+        }
+        let Some(value) = item.as_value() else {
+            continue;
+        };
+        if let Some(Item::Value(old)) = local.get_mut(key) {
+            let mut new_value = value.clone();
+            *new_value.decor_mut() = old.decor().clone();
+            *old = new_value;
+        } else if !base_item
+            .and_then(Item::as_value)
+            .is_some_and(|base_value| same_value(base_value, value))
+        {
+            let mut new_value = value.clone();
+            new_value.decor_mut().clear();
+            local.insert(key, Item::Value(new_value));
+        }
+    }
+    // Keys the writer removed (`minor`/`patch`, or keys renamed by a mode switch).
+    let stale: Vec<String> = local
+        .iter()
+        .filter(|(key, item)| item.is_value() && *key != "include" && !merged.contains_key(key))
+        .map(|(key, _)| key.to_string())
+        .collect();
+    for key in stale {
+        local.remove(&key);
+    }
+}
+
 impl BumpFile {
     pub fn load(path: impl AsRef<Path>) -> Result<Self, BumpError> {
         let path = path.as_ref();
@@ -195,40 +550,91 @@ impl BumpFile {
             }
         })?;
 
-        let doc = content
-            .parse::<DocumentMut>()
-            .map_err(|e| BumpError::ParseError(format!("Failed to parse TOML document: {e}")))?;
-
-        warn_mode_key_mismatch(path, &doc)?;
+        let doc = content.parse::<DocumentMut>().map_err(|e| {
+            BumpError::ParseError(format!(
+                "Failed to parse TOML document '{}': {}",
+                path.display(),
+                e.to_string().trim_end()
+            ))
+        })?;
 
-        Ok(Self {
+        let included = match included_path(&doc, path)? {
+            Some(base_path) => {
+                let mut chain = vec![fs::canonicalize(path).map_err(BumpError::IoError)?];
+                Some(read_included(&base_path, &mut chain)?)
+            }
+            None => None,
+        };
+        let bumpfile = Self {
             path: path.to_path_buf(),
             doc,
-        })
+            included,
+            remote: false,
+        };
+        warn_unknown_tables(path, &bumpfile.doc);
+        warn_mode_key_mismatch(path, &bumpfile.merged())?;
+        Ok(bumpfile)
     }
 
-    pub fn create(path: impl AsRef<Path>) -> Result<Self, BumpError> {
+    /// Read the bumpfile published at an `http://` or `https://` `url`. It can be
+    /// printed and generated from, but not saved, and may not use `include`.
+    pub fn fetch(url: &str) -> Result<Self, BumpError> {
+        let (status, content) = http_get(url)?;
+        if !(200..300).contains(&status) {
+            return Err(BumpError::Http(format!("GET {url}: HTTP {status}")));
+        }
+        let path = PathBuf::from(url);
+        let doc = content.parse::<DocumentMut>().map_err(|e| {
+            BumpError::ParseError(format!(
+                "Failed to parse TOML document '{url}': {}",
+                e.to_string().trim_end()
+            ))
+        })?;
+        if doc.contains_key("include") {
+            return Err(BumpError::LogicError(format!(
+                "'include' is not supported in a remote bumpfile ({url})"
+            )));
+        }
+        let bumpfile = Self {
+            path,
+            doc,
+            included: None,
+            remote: true,
+        };
+        warn_unknown_tables(&bumpfile.path, &bumpfile.doc);
+        warn_mode_key_mismatch(&bumpfile.path, &bumpfile.doc)?;
+        Ok(bumpfile)
+    }
+
+    /// Write a new bumpfile from the init template, starting at `initial` (a version
+    /// string such as `1.0.0-alpha.1`) instead of the template's 0.1.0 when given.
+    pub fn create(path: impl AsRef<Path>, initial: Option<&str>) -> Result<Self, BumpError> {
         let path = path.as_ref();
         ensure_directory_exists(path)?;
 
         let template = include_str!("templates/bump.toml");
-        let template_version: Version = {
-            let content = template.replace("{timestamp}", INIT_TEMPLATE_TIMESTAMP);
-            toml::from_str(&content).expect("init template must deserialize")
-        };
+        let template_version = template_version();
         let current_timestamp = chrono::Utc::now()
             .format(&template_version.timestamp.format)
             .to_string();
         let content = template.replace("{timestamp}", &current_timestamp);
-
-        fs::write(path, &content).map_err(BumpError::IoError)?;
-        let doc = content
+        let mut doc = content
             .parse::<DocumentMut>()
             .map_err(|e| BumpError::ParseError(format!("Failed to parse TOML document: {e}")))?;
 
+        if let Some(initial) = initial {
+            let mut version = template_version;
+            version.set_from_str(initial)?;
+            version.timestamp.last = current_timestamp;
+            write_version_into_doc(&mut doc, &version, path)?;
+        }
+
+        fs::write(path, doc.to_string()).map_err(|err| write_error(path, err))?;
         Ok(Self {
             path: path.to_path_buf(),
             doc,
+            included: None,
+            remote: false,
         })
     }
 
@@ -236,18 +642,56 @@ impl BumpFile {
         &self.path
     }
 
+    /// The bumpfile over the files it includes; the document versions and `[[gen]]`
+    /// outputs are read from.
+    pub fn merged(&self) -> DocumentMut {
+        let Some(included) = &self.included else {
+            return self.doc.clone();
+        };
+        let mut merged = included.clone();
+        merge(merged.as_table_mut(), self.doc.as_table());
+        merged.remove("include");
+        merged
+    }
+
     pub fn version(&self) -> Result<Version, BumpError> {
-        toml::from_str(&self.doc.to_string()).map_err(|err| {
+        let merged = self.merged();
+        let version: Version = toml::from_str(&merged.to_string()).map_err(|err| {
+            let hint = missing_field_hint(&merged, err.message())
+                .map(|hint| format!("{hint}\n"))
+                .unwrap_or_default();
             BumpError::ParseError(format!(
-                "Failed to parse version from '{}': {err}. \
+                "Failed to parse version from '{}': {}\n{hint}\
                 Recreate your bumpfile with 'bump init'.",
-                self.path.display()
+                self.path.display(),
+                err.to_string().trim_end()
             ))
-        })
+        })?;
+        version.validate().map_err(|message| {
+            BumpError::ParseError(format!("{message} in {}", self.path.display()))
+        })?;
+        Ok(version)
     }
 
     pub fn save(&mut self, version: &Version) -> Result<(), BumpError> {
-        write_version_into_doc(&mut self.doc, version, &self.path)?;
-        fs::write(&self.path, self.doc.to_string()).map_err(BumpError::IoError)
+        if self.remote {
+            return Err(BumpError::LogicError(format!(
+                "{} is a remote bumpfile and is read-only; run this against a local checkout",
+                self.path.display()
+            )));
+        }
+        match &self.included {
+            None => write_version_into_doc(&mut self.doc, version, &self.path)?,
+            Some(included) => {
+                let mut merged = self.merged();
+                write_version_into_doc(&mut merged, version, &self.path)?;
+                overlay(
+                    self.doc.as_table_mut(),
+                    Some(included.as_table()),
+                    merged.as_table(),
+                );
+            }
+        }
+        fs::write(&self.path, self.doc.to_string()).map_err(|err| write_error(&self.path, err))
     }
 }
@@ -0,0 +1,80 @@
+use crate::bump::{BumpError, get_git_latest_tag, run_git};
+use crate::issues;
+use crate::version::Version;
+
+/// Conventional commit types with their own changelog heading; anything else,
+/// including non-conventional subjects, goes under "Other" unchanged.
+const GROUPS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+];
+
+/// Commit subjects since the latest tag (or the whole history when untagged), oldest
+/// first and without merges.
+fn subjects_since_latest_tag(version: &Version) -> Result<Vec<String>, BumpError> {
+    let log = match get_git_latest_tag(version)? {
+        Some(tag) => run_git(&format!(
+            "log --reverse --no-merges --format=%s {tag}..HEAD"
+        ))?,
+        None => run_git("log --reverse --no-merges --format=%s")?,
+    };
+    Ok(log.lines().map(str::to_string).collect())
+}
+
+/// Split `type(scope)!: description` into its type and changelog entry.
+fn parse(subject: &str) -> (&str, String) {
+    let Some((head, description)) = subject.split_once(": ") else {
+        return ("", subject.to_string());
+    };
+    let (head, breaking) = head
+        .strip_suffix('!')
+        .map_or((head, false), |head| (head, true));
+    let (kind, scope) = match head.split_once('(') {
+        Some((kind, scope)) => (kind, scope.strip_suffix(')')),
+        None => (head, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphabetic()) {
+        return ("", subject.to_string());
+    }
+    let mut entry = String::new();
+    if breaking {
+        entry.push_str("BREAKING: ");
+    }
+    if let Some(scope) = scope {
+        entry.push_str(&format!("{scope}: "));
+    }
+    entry.push_str(description);
+    (kind, entry)
+}
+
+/// The changelog of this release appended to tag annotations by `tag --with-changelog`:
+/// commit subjects since the latest tag grouped by conventional commit type, or an
+/// empty string when there are none. Earlier release commits are left out, and
+/// `[issues]` references are linked.
+pub fn section(version: &Version) -> Result<String, BumpError> {
+    let mut groups: Vec<(&str, Vec<String>)> = GROUPS
+        .iter()
+        .map(|(_, heading)| (*heading, Vec::new()))
+        .chain(std::iter::once(("Other", Vec::new())))
+        .collect();
+    for subject in subjects_since_latest_tag(version)? {
+        if subject.starts_with("chore(release):") {
+            continue;
+        }
+        let (kind, entry) = parse(&subject);
+        match GROUPS.iter().position(|(group, _)| *group == kind) {
+            Some(index) => groups[index].1.push(entry),
+            None => groups[GROUPS.len()].1.push(subject),
+        }
+    }
+
+    let mut out = String::new();
+    for (heading, entries) in groups.iter().filter(|(_, entries)| !entries.is_empty()) {
+        out.push_str(&format!("\n\n{heading}:"));
+        for entry in entries {
+            out.push_str(&format!("\n- {}", issues::link(version, entry)?));
+        }
+    }
+    Ok(out)
+}
@@ -0,0 +1,225 @@
+use crate::bump::{BumpError, get_git_latest_tag, http_get, is_git_repository, load_bumpfile};
+use crate::bumpfile::BumpFile;
+use crate::lang;
+use crate::pep440::Pep440;
+use crate::print::{self, PrintOptions};
+use crate::version::Version;
+use clap::ArgMatches;
+use std::cmp::Ordering;
+use std::fs;
+
+/// One checked condition, as it appears in a text, TAP or JUnit report.
+pub struct Assertion {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+fn pypi_releases(index_url: &str, package: &str) -> Result<Vec<String>, BumpError> {
+    let url = format!("{}/{package}/json", index_url.trim_end_matches('/'));
+    let (status, body) = http_get(&url)?;
+    if status == 404 {
+        return Ok(Vec::new());
+    }
+    if !(200..300).contains(&status) {
+        return Err(BumpError::Http(format!("GET {url} returned {status}")));
+    }
+    let json: serde_json::Value = serde_json::from_str(&body)
+        .map_err(|e| BumpError::ParseError(format!("invalid JSON from {url}: {e}")))?;
+    Ok(json
+        .get("releases")
+        .and_then(serde_json::Value::as_object)
+        .map(|releases| releases.keys().cloned().collect())
+        .unwrap_or_default())
+}
+
+fn check_pypi(version: &Version, index_url: &str, package: &str) -> Result<Assertion, BumpError> {
+    let local_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let local = Pep440::parse(&local_str)?;
+    let latest = pypi_releases(index_url, package)?
+        .iter()
+        .filter_map(|release| Pep440::parse(release).ok().map(|v| (v, release.clone())))
+        .max_by(|a, b| a.0.cmp(&b.0));
+
+    let name = format!("pypi:{package}");
+    Ok(match latest {
+        None => Assertion {
+            name,
+            passed: true,
+            detail: format!("{local_str}, no published releases"),
+        },
+        Some((published, published_str)) => Assertion {
+            name,
+            passed: local > published,
+            detail: if local > published {
+                format!("{local_str} is newer than published {published_str}")
+            } else {
+                format!("{local_str} is not newer than published {published_str}")
+            },
+        },
+    })
+}
+
+/// The bumpfile version against the latest release tag: it may match the tag or be
+/// ahead of it, but never behind.
+fn check_tag(version: &Version) -> Result<Assertion, BumpError> {
+    let local = print::to_string(version, &PrintOptions::default())?;
+    let name = "tag".to_string();
+    let Some(tag) = get_git_latest_tag(version)? else {
+        return Ok(Assertion {
+            name,
+            passed: true,
+            detail: format!("{local}, no release tags"),
+        });
+    };
+    let Some(tagged) = version.parse_tag(&tag) else {
+        return Ok(Assertion {
+            name,
+            passed: true,
+            detail: format!("{local}, latest tag {tag} is not a release of this bumpfile"),
+        });
+    };
+    Ok(match version.cmp_precedence(&tagged) {
+        Ordering::Less => Assertion {
+            name,
+            passed: false,
+            detail: format!("{local} is behind the latest tag {tag}"),
+        },
+        Ordering::Equal => Assertion {
+            name,
+            passed: true,
+            detail: format!("{local} is tagged"),
+        },
+        Ordering::Greater => Assertion {
+            name,
+            passed: true,
+            detail: format!("{local} is ahead of the latest tag {tag}"),
+        },
+    })
+}
+
+/// One assertion per `[[gen]]` output: the file on disk is what `bump gen` would write.
+fn check_generated(bumpfile: &BumpFile, version: &Version) -> Result<Vec<Assertion>, BumpError> {
+    lang::configured_outputs(bumpfile)?
+        .into_iter()
+        .map(|(lang, path, options)| {
+            let stale = lang::stale_diff(lang, version, &path, &options)?;
+            Ok(Assertion {
+                name: format!("gen:{}", path.display()),
+                passed: stale.is_none(),
+                detail: if stale.is_none() {
+                    "up to date".to_string()
+                } else if path.exists() {
+                    "out of date, run bump gen".to_string()
+                } else {
+                    "missing, run bump gen".to_string()
+                },
+            })
+        })
+        .collect()
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn junit_report(command: &str, assertions: &[Assertion]) -> String {
+    let failures = assertions.iter().filter(|a| !a.passed).count();
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuite name=\"bump {command}\" tests=\"{}\" failures=\"{failures}\">\n",
+        assertions.len()
+    ));
+    for assertion in assertions {
+        let name = xml_escape(&assertion.name);
+        let detail = xml_escape(&assertion.detail);
+        if assertion.passed {
+            out.push_str(&format!(
+                "  <testcase classname=\"bump.{command}\" name=\"{name}\">\n    <system-out>{detail}</system-out>\n  </testcase>\n"
+            ));
+        } else {
+            out.push_str(&format!(
+                "  <testcase classname=\"bump.{command}\" name=\"{name}\">\n    <failure message=\"{detail}\"/>\n  </testcase>\n"
+            ));
+        }
+    }
+    out.push_str("</testsuite>\n");
+    out
+}
+
+fn tap_report(assertions: &[Assertion]) -> String {
+    let mut out = format!("TAP version 13\n1..{}\n", assertions.len());
+    for (i, assertion) in assertions.iter().enumerate() {
+        let status = if assertion.passed { "ok" } else { "not ok" };
+        out.push_str(&format!(
+            "{status} {} - {}: {}\n",
+            i + 1,
+            assertion.name,
+            assertion.detail
+        ));
+    }
+    out
+}
+
+fn text_report(assertions: &[Assertion]) -> String {
+    assertions
+        .iter()
+        .map(|assertion| {
+            let status = if assertion.passed { "ok" } else { "FAILED" };
+            format!("{} ... {status} ({})\n", assertion.name, assertion.detail)
+        })
+        .collect()
+}
+
+/// Gate a release: the bumpfile version is not behind the latest tag, every `[[gen]]`
+/// output is fresh, and with `--pypi` the version is newer than the published one.
+pub fn run(matches: &ArgMatches) -> Result<(), BumpError> {
+    let bumpfile = load_bumpfile(matches)?;
+    let version = bumpfile.version()?;
+    let index_url = matches
+        .get_one::<String>("index-url")
+        .expect("INDEX_URL has a default");
+
+    let mut assertions = Vec::new();
+    if is_git_repository() {
+        assertions.push(check_tag(&version)?);
+    }
+    assertions.extend(check_generated(&bumpfile, &version)?);
+    if let Some(package) = matches.get_one::<String>("pypi") {
+        assertions.push(check_pypi(&version, index_url, package)?);
+    }
+
+    report(matches, "check", &assertions)
+}
+
+/// Print `assertions` in the `--report` format (to `--report-file` if given, with the
+/// text summary still on stdout) and fail when any of them failed.
+pub fn report(
+    matches: &ArgMatches,
+    command: &str,
+    assertions: &[Assertion],
+) -> Result<(), BumpError> {
+    let report = match matches.get_one::<String>("report").map(String::as_str) {
+        Some("junit") => junit_report(command, assertions),
+        Some("tap") => tap_report(assertions),
+        _ => text_report(assertions),
+    };
+    if let Some(report_path) = matches.get_one::<String>("report-file") {
+        fs::write(report_path, &report).map_err(BumpError::IoError)?;
+        print!("{}", text_report(assertions));
+    } else {
+        print!("{report}");
+    }
+
+    let failed = assertions.iter().filter(|a| !a.passed).count();
+    if failed > 0 {
+        return Err(BumpError::LogicError(format!(
+            "{failed} of {} checks failed",
+            assertions.len()
+        )));
+    }
+    Ok(())
+}
@@ -30,7 +30,18 @@ pub fn cli() -> Command {
         .value_parser(clap::value_parser!(String))
         .default_value("bump.toml")
         .display_order(100)
-        .help("Path to the configuration file");
+        .help("Path to the configuration file, or an http(s) URL to read it from");
+    let member_arg = Arg::new("member")
+        .long("member")
+        .value_name("NAME")
+        .value_parser(clap::value_parser!(String))
+        .action(clap::ArgAction::Append)
+        .conflicts_with("all")
+        .help("Act on a [workspace] member of BUMPFILE (name or directory, repeatable)");
+    let all_arg = Arg::new("all")
+        .long("all")
+        .action(clap::ArgAction::SetTrue)
+        .help("Act on every [workspace] member of BUMPFILE");
 
     Command::new("bump")
         .styles(HELP_STYLES)
@@ -40,6 +51,13 @@ pub fn cli() -> Command {
         .subcommand(
             Command::new("init")
                 .about("Initialize a new version file with default values")
+                .arg(
+                    Arg::new("initial")
+                        .long("initial")
+                        .value_name("VERSION")
+                        .value_parser(clap::value_parser!(String))
+                        .help("Starting version instead of 0.1.0, i.e: '1.0.0-alpha.1' (defaults to $BUMP_INITIAL_VERSION)")
+                )
                 .arg(&bumpfile_arg)
         )
         .subcommand(
@@ -61,10 +79,10 @@ pub fn cli() -> Command {
                         .short('l')
                         .long("lang")
                         .value_name("LANG")
-                        .value_parser(clap::builder::PossibleValuesParser::new(["c", "java", "csharp", "go", "python"]))
+                        .value_parser(clap::builder::PossibleValuesParser::new(["c", "java", "kotlin", "swift", "csharp", "go", "python", "rust", "typescript", "javascript", "lua", "ruby", "php", "properties", "nix", "bazel", "sh", "dotenv", "powershell", "bat", "buildkite", "ld", "asm", "gas", "armasm", "mcuboot", "wix", "doxygen", "roff", "oci", "oci-args", "json", "yaml", "proto", "template", "wasm"]))
                         .num_args(1)
-                        .required(true)
-                        .help("Programming language for output files")
+                        .requires("output")
+                        .help("Language of --output files without a :LANG suffix (defaults to the [[gen]] outputs in BUMPFILE)")
                 )
                 .arg(
                     Arg::new("output")
@@ -73,8 +91,157 @@ pub fn cli() -> Command {
                         .value_name("OUTPUT")
                         .value_parser(clap::value_parser!(String))
                         .action(clap::ArgAction::Append)
+                        .help("Output file, optionally as FILE:LANG to override --lang (repeat for several files from one version)")
+                )
+                .arg(
+                    Arg::new("symbol-prefix")
+                        .long("symbol-prefix")
+                        .value_name("PREFIX")
+                        .value_parser(clap::value_parser!(String))
+                        .requires("output")
+                        .help("Prepend PREFIX to every generated symbol (i.e: 'CORE_')")
+                )
+                .arg(
+                    Arg::new("package")
+                        .long("package")
+                        .value_name("NAME")
+                        .value_parser(clap::value_parser!(String))
+                        .requires("output")
+                        .help("Package of Java, Kotlin, Go and proto outputs (i.e: 'com.acme.build')")
+                )
+                .arg(
+                    Arg::new("namespace")
+                        .long("namespace")
+                        .value_name("NAME")
+                        .value_parser(clap::value_parser!(String))
+                        .requires("output")
+                        .help("Namespace of C# outputs")
+                )
+                .arg(
+                    Arg::new("class-name")
+                        .long("class-name")
+                        .value_name("NAME")
+                        .value_parser(clap::value_parser!(String))
+                        .requires("output")
+                        .help("Name of the Java, Kotlin, C# or Swift type holding the constants, or of the proto message [default: Version]")
+                )
+                .arg(
+                    Arg::new("packed")
+                        .long("packed")
+                        .value_name("SCHEME")
+                        .value_parser(clap::builder::PossibleValuesParser::new(["bytes", "bcd"]))
+                        .requires("output")
+                        .help("Add VERSION_PACKED to C headers: major, minor, patch and phase distance as bytes or BCD")
+                )
+                .arg(
+                    Arg::new("guard")
+                        .long("guard")
+                        .value_name("MACRO")
+                        .value_parser(clap::value_parser!(String))
+                        .requires("output")
+                        .help("Include guard of C headers, or 'pragma-once' for #pragma once [default: named after the file]")
+                )
+                .arg(
+                    Arg::new("max-string-length")
+                        .long("max-string-length")
+                        .value_name("BYTES")
+                        .value_parser(clap::value_parser!(usize))
+                        .requires("output")
+                        .help("Fail unless VERSION_STRING, NUL included, fits BYTES, and add a _Static_assert for it to C headers")
+                )
+                .arg(
+                    Arg::new("git-info")
+                        .long("git-info")
+                        .action(clap::ArgAction::SetTrue)
+                        .requires("output")
+                        .help("Add GIT_SHA, GIT_BRANCH and GIT_DIRTY constants for the commit being built")
+                )
+                .arg(
+                    Arg::new("build-number-env")
+                        .long("build-number-env")
+                        .value_name("VAR")
+                        .value_parser(clap::value_parser!(String))
+                        .requires("output")
+                        .help("Add VERSION_BUILD from the CI build number in environment variable VAR (i.e: 'BUILD_NUMBER')")
+                )
+                .arg(
+                    Arg::new("build-number-in-full")
+                        .long("build-number-in-full")
+                        .action(clap::ArgAction::SetTrue)
+                        .requires("build-number-env")
+                        .help("Also append the build number to the fully qualified version (VERSION_FULL)")
+                )
+                .arg(
+                    Arg::new("module")
+                        .long("module")
+                        .value_name("FORMAT")
+                        .value_parser(clap::builder::PossibleValuesParser::new(["esm", "cjs"]))
+                        .requires("output")
+                        .help("Export style of TypeScript/JavaScript outputs [default: esm]")
+                )
+                .arg(
+                    Arg::new("template")
+                        .long("template")
+                        .value_name("FILE")
+                        .value_parser(clap::value_parser!(String))
+                        .requires("output")
+                        .help("Template for --lang template; {{ name }} expands to a version field (as in --lang json)")
+                )
+                .arg(
+                    Arg::new("version-from-stdin")
+                        .long("version-from-stdin")
+                        .action(clap::ArgAction::SetTrue)
+                        .requires("output")
+                        .conflicts_with_all(["member", "all"])
+                        .help("Read the version from stdin (a version string or JSON) instead of BUMPFILE")
+                )
+                .arg(
+                    Arg::new("check")
+                        .long("check")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Write nothing; print a diff and fail when any output is out of date")
+                )
+                .arg(
+                    Arg::new("output-dir")
+                        .long("output-dir")
+                        .value_name("DIR")
+                        .value_parser(clap::value_parser!(String))
+                        .help("Write relative outputs under DIR (DIR/<member> with --member/--all), e.g. outside a read-only source tree")
+                )
+                .arg(
+                    Arg::new("reproducible")
+                        .long("reproducible")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Leave out timestamps, or take them from SOURCE_DATE_EPOCH, so outputs are byte-identical across rebuilds")
+                )
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Rewrite outputs even when their content is unchanged (by default they are left untouched, keeping their modification time)")
+                )
+                .arg(
+                    // The default since unchanged outputs are always left alone.
+                    Arg::new("keep-mtime")
+                        .long("keep-mtime")
+                        .action(clap::ArgAction::SetTrue)
+                        .conflicts_with("force")
+                        .hide(true)
+                )
+                .arg(&member_arg)
+                .arg(&all_arg)
+                .arg(&bumpfile_arg)
+        )
+        .subcommand(
+            Command::new("convert")
+                .about("Rewrite the bumpfile from SemVer to CalVer or back")
+                .arg(
+                    Arg::new("to")
+                        .long("to")
+                        .value_name("MODE")
+                        .value_parser(clap::builder::PossibleValuesParser::new(["semver", "calver"]))
                         .required(true)
-                        .help("Output files for header generation (multiple files can be generated from a single bumpfile)")
+                        .help("Target scheme: calver starts from today's date; semver keeps the numbers, the year becoming major")
                 )
                 .arg(&bumpfile_arg)
         )
@@ -89,6 +256,27 @@ pub fn cli() -> Command {
                         .value_parser(clap::value_parser!(String))
                         .help("Custom tag message (defaults to conventional commit format)")
                 )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Show the tag and [publish] commands without running them")
+                )
+                .arg(
+                    Arg::new("with-changelog")
+                        .long("with-changelog")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Add the commits since the latest tag, grouped by conventional commit type, to the tag message")
+                )
+                .arg(
+                    Arg::new("provenance")
+                        .long("provenance")
+                        .value_name("PATH")
+                        .value_parser(clap::value_parser!(String))
+                        .help("Write provenance JSON (builder, commit, version, timestamp, config digest) to PATH")
+                )
+                .arg(&member_arg)
+                .arg(&all_arg)
                 .arg(&bumpfile_arg)
         )
         .subcommand(
@@ -98,9 +286,201 @@ pub fn cli() -> Command {
                     Arg::new("path")
                         .value_name("PATH")
                         .num_args(1)
-                        .value_parser(clap::builder::PossibleValuesParser::new(["Cargo.toml", "pyproject.toml"]))
+                        .value_parser(clap::value_parser!(String))
                         .required(true)
-                        .help("Certain file types bump is aware of, and know how to update")
+                        .help("File bump is aware of and knows how to update (Cargo.toml, pyproject.toml, setup.cfg, CMakeLists.txt, *.tf, ProjectSettings.asset, DefaultGame.ini, build.gradle, gradle.properties, pom.xml, package.json, Chart.yaml, Dockerfile, docker-compose.yml, pubspec.yaml, mix.exs, composer.json, *.gemspec, *.md, debian/changelog, *.spec, electron-builder.*, *.rc, *.bb, *.inc), or a directory to update every such file in")
+                )
+                .arg(
+                    Arg::new("also")
+                        .long("also")
+                        .value_name("PATH")
+                        .value_parser(clap::value_parser!(String))
+                        .action(clap::ArgAction::Append)
+                        .help("Another file or directory to update with PATH; if any fails, none is changed (repeatable)")
+                )
+                .arg(
+                    Arg::new("workspaces")
+                        .long("workspaces")
+                        .visible_alias("workspace")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("With a package.json or Cargo.toml, also update every npm/yarn workspace or Cargo workspace member it declares")
+                )
+                .arg(
+                    Arg::new("path-deps")
+                        .long("path-deps")
+                        .action(clap::ArgAction::SetTrue)
+                        .requires("workspaces")
+                        .help("With a Cargo workspace, also set the version requirement of path dependencies between its crates")
+                )
+                .arg(&bumpfile_arg)
+        )
+        .subcommand(
+            Command::new("auto")
+                .about("Bump only the [workspace] members changed since the last release")
+                .arg(
+                    Arg::new("since")
+                        .long("since")
+                        .value_name("TAG")
+                        .value_parser(clap::value_parser!(String))
+                        .num_args(1)
+                        .help("Compare against TAG instead of each member's release tag (or the latest tag reachable from HEAD)")
+                )
+                .arg(
+                    Arg::new("level")
+                        .long("level")
+                        .value_name("LEVEL")
+                        .value_parser(clap::builder::PossibleValuesParser::new(["major", "minor", "patch"]))
+                        .default_value("patch")
+                        .help("Component to bump in changed members")
+                )
+                .arg(&member_arg)
+                .arg(&all_arg)
+                .arg(&bumpfile_arg)
+        )
+        .subcommand(
+            Command::new("artifact-name")
+                .about("Print an artifact file name rendered from the version, without newline")
+                .arg(
+                    Arg::new("template")
+                        .long("template")
+                        .value_name("TEMPLATE")
+                        .value_parser(clap::value_parser!(String))
+                        .required(true)
+                        .help("File name with {version}, {tag}, {major}, {minor}, {patch}, {channel} or --var placeholders (i.e: 'myapp-{version}-{target}.tar.gz')")
+                )
+                .arg(
+                    Arg::new("var")
+                        .long("var")
+                        .value_name("NAME=VALUE")
+                        .value_parser(clap::value_parser!(String))
+                        .action(clap::ArgAction::Append)
+                        .help("Value for a {NAME} placeholder (repeatable)")
+                )
+                .arg(&bumpfile_arg)
+        )
+        .subcommand(
+            Command::new("check")
+                .about("Check the bumpfile version against the latest tag, [[gen]] outputs and package registries")
+                .arg(
+                    Arg::new("pypi")
+                        .long("pypi")
+                        .value_name("PACKAGE")
+                        .value_parser(clap::value_parser!(String))
+                        .num_args(1)
+                        .help("Fail unless the version is newer than the latest release of PACKAGE on PyPI")
+                )
+                .arg(
+                    Arg::new("index-url")
+                        .long("index-url")
+                        .value_name("URL")
+                        .value_parser(clap::value_parser!(String))
+                        .default_value("https://pypi.org/pypi")
+                        .help("PyPI JSON API base URL")
+                )
+                .arg(
+                    Arg::new("report")
+                        .long("report")
+                        .value_name("FORMAT")
+                        .value_parser(clap::builder::PossibleValuesParser::new(["text", "tap", "junit"]))
+                        .default_value("text")
+                        .help("Report format for check results")
+                )
+                .arg(
+                    Arg::new("report-file")
+                        .long("report-file")
+                        .value_name("PATH")
+                        .value_parser(clap::value_parser!(String))
+                        .help("Write the report to PATH instead of stdout")
+                )
+                .arg(&bumpfile_arg)
+        )
+        .subcommand(
+            Command::new("guard")
+                .about("Fail when files changed since a base ref but the version was not bumped")
+                .arg(
+                    Arg::new("base")
+                        .long("base")
+                        .value_name("REF")
+                        .value_parser(clap::value_parser!(String))
+                        .required(true)
+                        .help("Ref to compare against, from its merge base with HEAD (i.e: 'origin/main')")
+                )
+                .arg(&bumpfile_arg)
+        )
+        .subcommand(
+            Command::new("verify")
+                .about("Verify the bumpfile has not been edited outside bump")
+                .arg(
+                    Arg::new("integrity")
+                        .long("integrity")
+                        .action(clap::ArgAction::SetTrue)
+                        .required(true)
+                        .help("Fail when the prefix, [base] or [phase] no longer match [integrity].checksum")
+                )
+                .arg(
+                    Arg::new("seal")
+                        .long("seal")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Record the checksum of the current values instead of checking it")
+                )
+                .arg(
+                    Arg::new("report")
+                        .long("report")
+                        .value_name("FORMAT")
+                        .value_parser(clap::builder::PossibleValuesParser::new(["text", "tap", "junit"]))
+                        .default_value("text")
+                        .help("Report format for verify results")
+                )
+                .arg(
+                    Arg::new("report-file")
+                        .long("report-file")
+                        .value_name("PATH")
+                        .value_parser(clap::value_parser!(String))
+                        .help("Write the report to PATH instead of stdout")
+                )
+                .arg(&bumpfile_arg)
+        )
+        .subcommand(
+            Command::new("history")
+                .about("Show the [history] journal of bumps and tags")
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Print the entries as a JSON array")
+                )
+                .arg(&bumpfile_arg)
+        )
+        .subcommand(
+            Command::new("serve")
+                .about("Serve the current version over HTTP (/version, /version/full, /version.json)")
+                .arg(
+                    Arg::new("port")
+                        .long("port")
+                        .value_name("PORT")
+                        .value_parser(clap::value_parser!(u16))
+                        .default_value("7070")
+                        .help("Port to listen on (0 picks a free one)")
+                )
+                .arg(
+                    Arg::new("bind")
+                        .long("bind")
+                        .value_name("ADDR")
+                        .default_value("127.0.0.1")
+                        .help("Address to listen on")
+                )
+                .arg(&bumpfile_arg)
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Report release cadence computed from the version tags")
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .value_parser(clap::builder::PossibleValuesParser::new(["table", "json"]))
+                        .default_value("table")
+                        .help("Output format")
                 )
                 .arg(&bumpfile_arg)
         )
@@ -158,6 +538,22 @@ pub fn cli() -> Command {
                     .action(clap::ArgAction::SetTrue)
                     .help("Print full output; overrides all print flags except --with-label"),
             )
+            .arg(
+                Arg::new("docker-safe")
+                    .long("docker-safe")
+                    .action(clap::ArgAction::SetTrue)
+                    .group("print-safe")
+                    .conflicts_with_all(["full", "with-timestamp"])
+                    .help("Print [prefix][base][phase][suffix] as a legal Docker tag ('+' -> '-', '/' stripped)"),
+            )
+            .arg(
+                Arg::new("filename-safe")
+                    .long("filename-safe")
+                    .action(clap::ArgAction::SetTrue)
+                    .group("print-safe")
+                    .conflicts_with_all(["full", "with-timestamp"])
+                    .help("Print [prefix][base][phase][suffix] as a legal file name ('+' -> '-', '/' stripped)"),
+            )
             .arg(
                 Arg::new("with-label")
                     .long("with-label")
@@ -167,6 +563,8 @@ pub fn cli() -> Command {
                     .num_args(1)
                     .help("Inject LABEL at [label].position (not persisted)"),
             )
+            .arg(&member_arg)
+            .arg(&all_arg)
             .arg(&bumpfile_arg)
         )
         .arg(
@@ -225,5 +623,37 @@ pub fn cli() -> Command {
                 .num_args(1)
                 .help("Set suffix mode 'git_sha' or 'branch'")
         )
+        .arg(&member_arg)
+        .arg(&all_arg)
+        .arg(
+            Arg::new("push")
+                .long("push")
+                .action(clap::ArgAction::SetTrue)
+                .requires("formal")
+                .help("Commit the bump and push it to the current branch")
+        )
+        .arg(
+            Arg::new("remote")
+                .long("remote")
+                .value_name("REMOTE")
+                .value_parser(clap::value_parser!(String))
+                .requires("push")
+                .help("Remote for --push [default: origin]")
+        )
+        .arg(
+            Arg::new("retries")
+                .long("retries")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32))
+                .requires("push")
+                .help("When the push is rejected, bump again on top of the fetched branch, up to N times")
+        )
+        .arg(
+            Arg::new("no-git")
+                .long("no-git")
+                .action(clap::ArgAction::SetTrue)
+                .global(true)
+                .help("Never run git; act as if outside a repository")
+        )
         .arg(&bumpfile_arg)
 }
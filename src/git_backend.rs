@@ -0,0 +1,156 @@
+use crate::bump::BumpError;
+use crate::bump::git_cmd;
+#[cfg(not(feature = "gix"))]
+use crate::bump::run_git;
+
+// Read-only repository queries. The default build shells out to the git binary;
+// builds with `--features gix` read refs and objects from .git directly, so
+// tagged/untagged detection and suffixes work where git is not installed.
+// Writes (tags, logs for [issues]) and worktree status always go through the git
+// binary; without it the dirty flag is unknown.
+
+#[cfg(feature = "gix")]
+fn open() -> Result<gix::Repository, BumpError> {
+    gix::discover(".").map_err(|e| BumpError::Git(format!("failed to open repository: {e}")))
+}
+
+#[cfg(feature = "gix")]
+fn head_id(repo: &gix::Repository) -> Result<gix::Id<'_>, BumpError> {
+    repo.head_id()
+        .map_err(|e| BumpError::Git(format!("failed to resolve HEAD: {e}")))
+}
+
+#[cfg(feature = "gix")]
+pub fn is_repository() -> bool {
+    open().is_ok()
+}
+
+#[cfg(not(feature = "gix"))]
+pub fn is_repository() -> bool {
+    git_cmd()
+        .args(["rev-parse", "--git-dir"])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(feature = "gix")]
+pub fn short_sha() -> Result<String, BumpError> {
+    let repo = open()?;
+    Ok(head_id(&repo)?.shorten_or_id().to_string())
+}
+
+#[cfg(not(feature = "gix"))]
+pub fn short_sha() -> Result<String, BumpError> {
+    run_git("rev-parse --short HEAD")
+}
+
+#[cfg(feature = "gix")]
+pub fn full_sha() -> Result<String, BumpError> {
+    let repo = open()?;
+    Ok(head_id(&repo)?.to_string())
+}
+
+#[cfg(not(feature = "gix"))]
+pub fn full_sha() -> Result<String, BumpError> {
+    run_git("rev-parse HEAD")
+}
+
+/// The checked-out branch, or `None` on a detached HEAD.
+#[cfg(feature = "gix")]
+pub fn head_branch() -> Result<Option<String>, BumpError> {
+    let repo = open()?;
+    let head = repo
+        .head()
+        .map_err(|e| BumpError::Git(format!("failed to read HEAD: {e}")))?;
+    Ok(head.referent_name().map(|name| name.shorten().to_string()))
+}
+
+#[cfg(not(feature = "gix"))]
+pub fn head_branch() -> Result<Option<String>, BumpError> {
+    let branch = run_git("rev-parse --abbrev-ref HEAD")?;
+    Ok((branch != "HEAD").then_some(branch))
+}
+
+/// The nearest tag reachable from HEAD, or `None` when there are no tags.
+#[cfg(feature = "gix")]
+pub fn latest_tag() -> Result<Option<String>, BumpError> {
+    let repo = open()?;
+    let Ok(commit) = repo.head_commit() else {
+        return Ok(None);
+    };
+    let resolution = commit
+        .describe()
+        .names(gix::commit::describe::SelectRef::AllTags)
+        .try_resolve()
+        .map_err(|e| BumpError::Git(format!("failed to describe HEAD: {e}")))?;
+    Ok(resolution.and_then(|resolution| resolution.outcome.name.map(|name| name.to_string())))
+}
+
+#[cfg(not(feature = "gix"))]
+pub fn latest_tag() -> Result<Option<String>, BumpError> {
+    let output = git_cmd()
+        .args(["describe", "--tags", "--abbrev=0"])
+        .output()
+        .map_err(|e| BumpError::Git(format!("git describe: {e}")))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+/// The names of all tags in the repository.
+#[cfg(feature = "gix")]
+pub fn tag_names() -> Result<Vec<String>, BumpError> {
+    let repo = open()?;
+    let references = repo
+        .references()
+        .map_err(|e| BumpError::Git(format!("failed to read references: {e}")))?;
+    let tags = references
+        .tags()
+        .map_err(|e| BumpError::Git(format!("failed to read tags: {e}")))?;
+    Ok(tags
+        .filter_map(Result::ok)
+        .map(|tag| tag.name().shorten().to_string())
+        .collect())
+}
+
+#[cfg(not(feature = "gix"))]
+pub fn tag_names() -> Result<Vec<String>, BumpError> {
+    let output = git_cmd()
+        .args(["tag", "--list"])
+        .output()
+        .map_err(|e| BumpError::Git(format!("git tag --list: {e}")))?;
+    if !output.status.success() {
+        return Err(BumpError::Git(format!(
+            "git tag --list: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Whether tracked files differ from HEAD, staged or not. Untracked files do not count.
+/// `None` when the git binary is not installed, which gix builds otherwise work without.
+pub fn is_dirty() -> Result<Option<bool>, BumpError> {
+    let output = match git_cmd()
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+    {
+        Ok(output) => output,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(BumpError::Git(format!("git status: {err}"))),
+    };
+    if !output.status.success() {
+        return Err(BumpError::Git(format!(
+            "git status: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(Some(!output.stdout.is_empty()))
+}
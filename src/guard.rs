@@ -0,0 +1,102 @@
+use crate::bump::{BumpError, git_cmd, is_git_repository, load_bumpfile};
+use crate::print::{self, PrintOptions, glob_matches};
+use crate::version::Version;
+use clap::ArgMatches;
+use std::path::Path;
+
+/// Run git in `dir`, returning stdout, or `None` when `allow_failure` and git fails.
+fn git_in(dir: &Path, args: &[&str], allow_failure: bool) -> Result<Option<String>, BumpError> {
+    let output = git_cmd()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .map_err(|e| BumpError::Git(format!("git {}: {e}", args.join(" "))))?;
+    if !output.status.success() {
+        if allow_failure {
+            return Ok(None);
+        }
+        return Err(BumpError::Git(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
+}
+
+/// The version the bumpfile held at `base`, or `None` when it did not exist there.
+fn base_version(dir: &Path, file_name: &str, base: &str) -> Result<Option<Version>, BumpError> {
+    let Some(content) = git_in(dir, &["show", &format!("{base}:./{file_name}")], true)? else {
+        return Ok(None);
+    };
+    toml::from_str(&content).map(Some).map_err(|e| {
+        BumpError::ParseError(format!(
+            "Failed to parse version from {file_name} at {base}: {}",
+            e.to_string().trim_end()
+        ))
+    })
+}
+
+/// Fail when files under the bumpfile's directory changed since the merge base with
+/// `--base` but the version did not. `[guard].paths` narrows which changes count.
+pub fn run(matches: &ArgMatches) -> Result<(), BumpError> {
+    if !is_git_repository() {
+        return Err(BumpError::LogicError("Not in a git repository".to_string()));
+    }
+    let base = matches.get_one::<String>("base").expect("BASE is required");
+    let bumpfile = load_bumpfile(matches)?;
+    let version = bumpfile.version()?;
+    let dir = bumpfile
+        .path()
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = bumpfile
+        .path()
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let patterns = version
+        .guard
+        .as_ref()
+        .map(|guard| guard.paths.clone())
+        .unwrap_or_default();
+
+    let range = format!("{base}...HEAD");
+    let diff = git_in(
+        dir,
+        &["diff", "--name-only", "--relative", &range, "--", "."],
+        false,
+    )?
+    .unwrap_or_default();
+    let changed: Vec<&str> = diff
+        .lines()
+        .filter(|file| *file != file_name)
+        .filter(|file| patterns.is_empty() || patterns.iter().any(|p| glob_matches(p, file)))
+        .collect();
+
+    let current = print::to_string(&version, &PrintOptions::default())?;
+    if changed.is_empty() {
+        println!("no changes since {base} require a version bump ({current})");
+        return Ok(());
+    }
+    let previous = match base_version(dir, &file_name, base)? {
+        Some(previous) => print::to_string(&previous, &PrintOptions::default())?,
+        None => {
+            println!("{file_name} is new since {base} ({current})");
+            return Ok(());
+        }
+    };
+    if previous == current {
+        let mut message = format!(
+            "{} file(s) changed since {base} but the version is still {current}; bump it:",
+            changed.len()
+        );
+        for file in &changed {
+            message.push_str(&format!("\n  {file}"));
+        }
+        return Err(BumpError::LogicError(message));
+    }
+    println!("version bumped since {base}: {previous} -> {current}");
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use crate::bump::{BumpError, git_cmd, is_git_repository, load_bumpfile};
+use crate::version::Version;
+use clap::ArgMatches;
+use serde_json::{Value, json};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The journal file for `bumpfile`, relative to its directory, or `None` without `[history]`.
+pub fn journal(version: &Version, bumpfile: &Path) -> Option<PathBuf> {
+    let history = version.history.as_ref()?;
+    Some(
+        bumpfile
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(&history.file),
+    )
+}
+
+pub fn git_config(key: &str) -> Option<String> {
+    if !is_git_repository() {
+        return None;
+    }
+    let output = git_cmd().args(["config", key]).output().ok()?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+/// The CI job performing the change, from the variables each CI system sets.
+fn ci_job_url() -> Option<String> {
+    let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+    if let (Some(server), Some(repository), Some(run)) = (
+        var("GITHUB_SERVER_URL"),
+        var("GITHUB_REPOSITORY"),
+        var("GITHUB_RUN_ID"),
+    ) {
+        return Some(format!("{server}/{repository}/actions/runs/{run}"));
+    }
+    ["CI_JOB_URL", "BUILD_URL", "BUILDKITE_BUILD_URL"]
+        .iter()
+        .find_map(|name| var(name))
+}
+
+fn hostname() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|name| env::var(name).ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Append one entry to the `[history]` journal, if configured: the event, the
+/// version before and after, and who made the change from where.
+pub fn record(
+    version: &Version,
+    bumpfile: &Path,
+    event: &str,
+    from: Option<&str>,
+    to: &str,
+) -> Result<(), BumpError> {
+    let Some(path) = journal(version, bumpfile) else {
+        return Ok(());
+    };
+    let entry = json!({
+        "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        "event": event,
+        "from": from,
+        "to": to,
+        "actor": {
+            "name": git_config("user.name"),
+            "email": git_config("user.email"),
+        },
+        "ci_job_url": ci_job_url(),
+        "hostname": hostname(),
+    });
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(BumpError::IoError)?;
+    writeln!(file, "{entry}").map_err(BumpError::IoError)
+}
+
+fn read_entries(path: &Path) -> Result<Vec<Value>, BumpError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(BumpError::IoError(err)),
+    };
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                BumpError::ParseError(format!("{}:{}: {e}", path.display(), index + 1))
+            })
+        })
+        .collect()
+}
+
+/// Print the `[history]` journal, one line per entry or as a JSON array with `--json`.
+pub fn run(matches: &ArgMatches) -> Result<(), BumpError> {
+    let bumpfile = load_bumpfile(matches)?;
+    let version = bumpfile.version()?;
+    let path = journal(&version, bumpfile.path()).ok_or_else(|| {
+        BumpError::LogicError(format!(
+            "no [history] table in {}",
+            bumpfile.path().display()
+        ))
+    })?;
+    let entries = read_entries(&path)?;
+
+    if matches.get_flag("json") {
+        let json = serde_json::to_string_pretty(&entries)
+            .map_err(|e| BumpError::ParseError(format!("history: {e}")))?;
+        println!("{json}");
+        return Ok(());
+    }
+    let text = |value: &Value| value.as_str().unwrap_or("-").to_string();
+    for entry in &entries {
+        println!(
+            "{}  {:<7} {} -> {}  by {} <{}>",
+            text(&entry["timestamp"]),
+            text(&entry["event"]),
+            text(&entry["from"]),
+            text(&entry["to"]),
+            text(&entry["actor"]["name"]),
+            text(&entry["actor"]["email"]),
+        );
+    }
+    Ok(())
+}
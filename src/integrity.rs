@@ -0,0 +1,119 @@
+use crate::bump::{BumpError, load_bumpfile};
+use crate::check::{self, Assertion};
+use crate::provenance::sha256_hex;
+use crate::version::{Integrity, Version};
+use clap::ArgMatches;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::env;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The fields `[integrity].checksum` covers: the prefix, `[base]` and `[phase]` values.
+fn covered(version: &Version) -> String {
+    let component = |value: Option<u32>| value.map(|n| n.to_string()).unwrap_or_default();
+    format!(
+        "prefix={}\nmode={}\nmajor={}\nminor={}\npatch={}\nphase={}\ndistance={}\n",
+        version.prefix,
+        version.base.mode.as_str(),
+        version.base.major,
+        component(version.base.minor),
+        component(version.base.patch),
+        version.phase.name,
+        version.phase.distance
+    )
+}
+
+/// The HMAC-SHA256 of `covered` keyed by the variable named in `key_env`, which must be set.
+fn keyed_mac(key_env: &str, covered: &str) -> Result<HmacSha256, BumpError> {
+    let key = env::var(key_env)
+        .ok()
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| {
+            BumpError::LogicError(format!(
+                "[integrity].key_env names {key_env}, which is not set; cannot sign the version"
+            ))
+        })?;
+    let mut mac =
+        HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(covered.as_bytes());
+    Ok(mac)
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// The checksum of `version`: `sha256:<hex>`, or `hmac-sha256:<hex>` keyed by the
+/// variable named in `key_env`, which must then be set.
+pub fn checksum(version: &Version, integrity: &Integrity) -> Result<String, BumpError> {
+    let covered = covered(version);
+    let Some(key_env) = &integrity.key_env else {
+        return Ok(format!("sha256:{}", sha256_hex(covered.as_bytes())));
+    };
+    let tag: String = keyed_mac(key_env, &covered)?
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    Ok(format!("hmac-sha256:{tag}"))
+}
+
+/// Whether `recorded` is the checksum of `version`. Keyed checksums are verified in
+/// constant time, so a mismatch reveals nothing about the expected value.
+fn is_recorded(
+    version: &Version,
+    integrity: &Integrity,
+    recorded: &str,
+) -> Result<bool, BumpError> {
+    match (&integrity.key_env, recorded.strip_prefix("hmac-sha256:")) {
+        (Some(key_env), Some(tag)) => {
+            let mac = keyed_mac(key_env, &covered(version))?;
+            Ok(decode_hex(tag).is_some_and(|tag| mac.verify_slice(&tag).is_ok()))
+        }
+        _ => Ok(recorded == checksum(version, integrity)?),
+    }
+}
+
+/// `bump verify --integrity`: fail when the version fields no longer match the checksum
+/// bump recorded, i.e. they were edited by hand. `--seal` records the current values.
+pub fn run(matches: &ArgMatches) -> Result<(), BumpError> {
+    let mut bumpfile = load_bumpfile(matches)?;
+    let version = bumpfile.version()?;
+    let path = bumpfile.path().display().to_string();
+    let Some(integrity) = &version.integrity else {
+        return Err(BumpError::LogicError(format!(
+            "no [integrity] table in {path}"
+        )));
+    };
+
+    if matches.get_flag("seal") {
+        bumpfile.save(&version)?;
+        println!("{path}: recorded the integrity checksum");
+        return Ok(());
+    }
+    let Some(recorded) = &integrity.checksum else {
+        return Err(BumpError::LogicError(format!(
+            "no [integrity].checksum in {path}; record one with 'bump verify --integrity --seal'"
+        )));
+    };
+    let passed = is_recorded(&version, integrity, recorded)?;
+    let assertion = Assertion {
+        name: format!("integrity:{path}"),
+        passed,
+        detail: if passed {
+            "checksum matches".to_string()
+        } else {
+            "the prefix, [base] or [phase] were changed outside bump (checksum mismatch)"
+                .to_string()
+        },
+    };
+    check::report(matches, "verify", &[assertion])
+}
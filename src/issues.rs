@@ -0,0 +1,104 @@
+use crate::bump::{BumpError, get_git_latest_tag, run_git};
+use crate::version::{Issues, Version};
+use regex::Regex;
+
+/// The matchers for `issues`: each key as `KEY-<number>` not preceded by a letter or
+/// digit, then each pattern as written. A key's reference is capture group 1.
+fn matchers(issues: &Issues) -> Result<Vec<(Regex, usize)>, BumpError> {
+    let keys = issues.keys.iter().map(|key| {
+        let pattern = format!("(?:^|[^0-9A-Za-z])({}-[0-9]+)", regex::escape(key));
+        (pattern, 1)
+    });
+    let patterns = issues.patterns.iter().map(|pattern| (pattern.clone(), 0));
+    keys.chain(patterns)
+        .map(|(pattern, group)| {
+            Regex::new(&pattern)
+                .map(|regex| (regex, group))
+                .map_err(|e| {
+                    BumpError::ParseError(format!("invalid [issues] pattern '{pattern}': {e}"))
+                })
+        })
+        .collect()
+}
+
+/// Every reference in `text` with its byte range, in order of appearance.
+fn find(text: &str, matchers: &[(Regex, usize)]) -> Vec<(usize, usize, String)> {
+    let mut found: Vec<(usize, usize, String)> = matchers
+        .iter()
+        .flat_map(|(regex, group)| {
+            regex
+                .captures_iter(text)
+                .filter_map(|captures| captures.get(*group))
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end(), m.as_str().to_string()))
+        })
+        .collect();
+    found.sort_by_key(|(start, end, _)| (*start, std::cmp::Reverse(*end)));
+    // Patterns may overlap (`JIRA-\d+` and a key `JIRA`); keep the first, longest match.
+    let mut end_of_last = 0;
+    found.retain(|(start, end, _)| {
+        let keep = *start >= end_of_last;
+        if keep {
+            end_of_last = *end;
+        }
+        keep
+    });
+    found
+}
+
+/// The distinct references in `text`, in first-seen order.
+fn extract(text: &str, matchers: &[(Regex, usize)]) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for (_, _, id) in find(text, matchers) {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// `text` with every reference turned into a Markdown link when `[issues].url` is set.
+pub fn link(version: &Version, text: &str) -> Result<String, BumpError> {
+    let Some(issues) = &version.issues else {
+        return Ok(text.to_string());
+    };
+    let Some(url) = &issues.url else {
+        return Ok(text.to_string());
+    };
+    let mut out = String::new();
+    let mut copied = 0;
+    for (start, end, id) in find(text, &matchers(issues)?) {
+        out.push_str(&text[copied..start]);
+        out.push_str(&format!("[{id}]({})", url.replace("{id}", &id)));
+        copied = end;
+    }
+    out.push_str(&text[copied..]);
+    Ok(out)
+}
+
+/// Commit messages since the latest tag (or the whole history when untagged).
+fn messages_since_latest_tag(version: &Version) -> Result<String, BumpError> {
+    match get_git_latest_tag(version)? {
+        Some(tag) => run_git(&format!("log --format=%B {tag}..HEAD")),
+        None => run_git("log --format=%B"),
+    }
+}
+
+/// The `[issues]` section appended to tag annotations, or an empty string.
+pub fn section(version: &Version) -> Result<String, BumpError> {
+    let Some(issues) = &version.issues else {
+        return Ok(String::new());
+    };
+    let ids = extract(&messages_since_latest_tag(version)?, &matchers(issues)?);
+    if ids.is_empty() {
+        return Ok(String::new());
+    }
+    let mut out = String::from("\n\nIssues:\n");
+    for id in ids {
+        match &issues.url {
+            Some(url) => out.push_str(&format!("- {id}: {}\n", url.replace("{id}", &id))),
+            None => out.push_str(&format!("- {id}\n")),
+        }
+    }
+    Ok(out)
+}
@@ -1,16 +1,55 @@
-use crate::bump::BumpError;
+use crate::bump::{
+    BumpError, get_git_branch, get_git_commit_full_sha, get_git_dirty, is_git_repository,
+    write_error,
+};
+use crate::bumpfile::BumpFile;
 use crate::print::{self, PrintOptions};
 use crate::version::{Version, VersionMode};
+use crate::{msi, plugin, version_code, wasm};
+use serde::Deserialize;
+use serde_json::json;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::Command as ProcessCommand;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Language {
     C,
     Go,
     Java,
+    Kotlin,
+    Swift,
     CSharp,
     Python,
+    Rust,
+    TypeScript,
+    JavaScript,
+    Lua,
+    Ruby,
+    Php,
+    Properties,
+    Nix,
+    Bazel,
+    Sh,
+    Dotenv,
+    PowerShell,
+    Bat,
+    Buildkite,
+    Ld,
+    Asm,
+    Gas,
+    Armasm,
+    Mcuboot,
+    Wix,
+    Doxygen,
+    Roff,
+    Oci,
+    OciArgs,
+    Json,
+    Yaml,
+    Proto,
+    Template,
+    Wasm,
 }
 
 impl Language {
@@ -19,8 +58,39 @@ impl Language {
             "c" => Some(Self::C),
             "go" => Some(Self::Go),
             "java" => Some(Self::Java),
+            "kotlin" => Some(Self::Kotlin),
+            "swift" => Some(Self::Swift),
             "csharp" => Some(Self::CSharp),
             "python" => Some(Self::Python),
+            "rust" => Some(Self::Rust),
+            "typescript" => Some(Self::TypeScript),
+            "javascript" => Some(Self::JavaScript),
+            "lua" => Some(Self::Lua),
+            "ruby" => Some(Self::Ruby),
+            "php" => Some(Self::Php),
+            "properties" => Some(Self::Properties),
+            "nix" => Some(Self::Nix),
+            "bazel" => Some(Self::Bazel),
+            "sh" => Some(Self::Sh),
+            "dotenv" => Some(Self::Dotenv),
+            "powershell" => Some(Self::PowerShell),
+            "bat" => Some(Self::Bat),
+            "buildkite" => Some(Self::Buildkite),
+            "ld" => Some(Self::Ld),
+            "asm" => Some(Self::Asm),
+            "gas" => Some(Self::Gas),
+            "armasm" => Some(Self::Armasm),
+            "mcuboot" => Some(Self::Mcuboot),
+            "wix" => Some(Self::Wix),
+            "doxygen" => Some(Self::Doxygen),
+            "roff" => Some(Self::Roff),
+            "oci" => Some(Self::Oci),
+            "oci-args" => Some(Self::OciArgs),
+            "json" => Some(Self::Json),
+            "yaml" => Some(Self::Yaml),
+            "proto" => Some(Self::Proto),
+            "template" => Some(Self::Template),
+            "wasm" => Some(Self::Wasm),
             _ => None,
         }
     }
@@ -30,8 +100,39 @@ impl Language {
             Self::C => "C header file",
             Self::Go => "Go source file",
             Self::Java => "Java source file",
+            Self::Kotlin => "Kotlin source file",
+            Self::Swift => "Swift source file",
             Self::CSharp => "C# source file",
             Self::Python => "Python source file",
+            Self::Rust => "Rust source file",
+            Self::TypeScript => "TypeScript module",
+            Self::JavaScript => "JavaScript module",
+            Self::Lua => "Lua module",
+            Self::Ruby => "Ruby source file",
+            Self::Php => "PHP source file",
+            Self::Properties => "Java properties file",
+            Self::Nix => "Nix expression",
+            Self::Bazel => "Bazel Starlark file",
+            Self::Sh => "Shell environment file",
+            Self::Dotenv => "dotenv file",
+            Self::PowerShell => "PowerShell environment script",
+            Self::Bat => "Batch environment script",
+            Self::Buildkite => "Buildkite meta-data file",
+            Self::Ld => "Linker script",
+            Self::Asm => "Assembly source file",
+            Self::Gas => "GNU as include file",
+            Self::Armasm => "armasm include file",
+            Self::Mcuboot => "MCUboot image version file",
+            Self::Wix => "WiX include file",
+            Self::Doxygen => "Doxyfile fragment",
+            Self::Roff => "roff string definitions",
+            Self::Oci => "OCI label file",
+            Self::OciArgs => "OCI label arguments",
+            Self::Json => "JSON version file",
+            Self::Yaml => "YAML version file",
+            Self::Proto => "Protocol Buffers schema",
+            Self::Template => "Templated file",
+            Self::Wasm => "WASM plugin output",
         }
     }
 
@@ -43,15 +144,424 @@ impl Language {
             (Self::Go, VersionMode::Calver) => include_str!("templates/go/calver.go"),
             (Self::Java, VersionMode::Semver) => include_str!("templates/java/semver.java"),
             (Self::Java, VersionMode::Calver) => include_str!("templates/java/calver.java"),
+            (Self::Kotlin, VersionMode::Semver) => include_str!("templates/kotlin/semver.kt"),
+            (Self::Kotlin, VersionMode::Calver) => include_str!("templates/kotlin/calver.kt"),
+            (Self::Swift, VersionMode::Semver) => include_str!("templates/swift/semver.swift"),
+            (Self::Swift, VersionMode::Calver) => include_str!("templates/swift/calver.swift"),
             (Self::CSharp, VersionMode::Semver) => include_str!("templates/csharp/semver.cs"),
             (Self::CSharp, VersionMode::Calver) => include_str!("templates/csharp/calver.cs"),
             (Self::Python, VersionMode::Semver) => include_str!("templates/python/semver.py"),
             (Self::Python, VersionMode::Calver) => include_str!("templates/python/calver.py"),
+            (Self::Rust, VersionMode::Semver) => include_str!("templates/rust/semver.rs"),
+            (Self::Rust, VersionMode::Calver) => include_str!("templates/rust/calver.rs"),
+            (Self::TypeScript, VersionMode::Semver) => {
+                include_str!("templates/typescript/semver.ts")
+            }
+            (Self::TypeScript, VersionMode::Calver) => {
+                include_str!("templates/typescript/calver.ts")
+            }
+            (Self::JavaScript, VersionMode::Semver) => {
+                include_str!("templates/javascript/semver.js")
+            }
+            (Self::JavaScript, VersionMode::Calver) => {
+                include_str!("templates/javascript/calver.js")
+            }
+            (Self::Lua, VersionMode::Semver) => include_str!("templates/lua/semver.lua"),
+            (Self::Lua, VersionMode::Calver) => include_str!("templates/lua/calver.lua"),
+            (Self::Ruby, VersionMode::Semver) => include_str!("templates/ruby/semver.rb"),
+            (Self::Ruby, VersionMode::Calver) => include_str!("templates/ruby/calver.rb"),
+            (Self::Php, VersionMode::Semver) => include_str!("templates/php/semver.php"),
+            (Self::Php, VersionMode::Calver) => include_str!("templates/php/calver.php"),
+            (Self::Properties, VersionMode::Semver) => {
+                include_str!("templates/properties/semver.properties")
+            }
+            (Self::Properties, VersionMode::Calver) => {
+                include_str!("templates/properties/calver.properties")
+            }
+            (Self::Nix, VersionMode::Semver) => include_str!("templates/nix/semver.nix"),
+            (Self::Nix, VersionMode::Calver) => include_str!("templates/nix/calver.nix"),
+            (Self::Bazel, VersionMode::Semver) => include_str!("templates/bazel/semver.bzl"),
+            (Self::Bazel, VersionMode::Calver) => include_str!("templates/bazel/calver.bzl"),
+            (Self::Sh, VersionMode::Semver) => include_str!("templates/sh/semver.sh"),
+            (Self::Sh, VersionMode::Calver) => include_str!("templates/sh/calver.sh"),
+            (Self::Dotenv, VersionMode::Semver) => include_str!("templates/dotenv/semver.env"),
+            (Self::Dotenv, VersionMode::Calver) => include_str!("templates/dotenv/calver.env"),
+            (Self::PowerShell, VersionMode::Semver) => {
+                include_str!("templates/powershell/semver.ps1")
+            }
+            (Self::PowerShell, VersionMode::Calver) => {
+                include_str!("templates/powershell/calver.ps1")
+            }
+            (Self::Bat, VersionMode::Semver) => include_str!("templates/bat/semver.bat"),
+            (Self::Bat, VersionMode::Calver) => include_str!("templates/bat/calver.bat"),
+            (Self::Buildkite, VersionMode::Semver) => {
+                include_str!("templates/buildkite/semver.env")
+            }
+            (Self::Buildkite, VersionMode::Calver) => {
+                include_str!("templates/buildkite/calver.env")
+            }
+            (Self::Ld, VersionMode::Semver) => include_str!("templates/ld/semver.ld"),
+            (Self::Ld, VersionMode::Calver) => include_str!("templates/ld/calver.ld"),
+            (Self::Asm, _) => include_str!("templates/asm/version.s"),
+            (Self::Gas, VersionMode::Semver) => include_str!("templates/gas/semver.inc"),
+            (Self::Gas, VersionMode::Calver) => include_str!("templates/gas/calver.inc"),
+            (Self::Armasm, VersionMode::Semver) => include_str!("templates/armasm/semver.inc"),
+            (Self::Armasm, VersionMode::Calver) => include_str!("templates/armasm/calver.inc"),
+            (Self::Mcuboot, _) => include_str!("templates/mcuboot/version.conf"),
+            (Self::Wix, _) => include_str!("templates/wix/version.wxi"),
+            (Self::Doxygen, _) => include_str!("templates/doxygen/version.doxy"),
+            (Self::Roff, _) => include_str!("templates/roff/version.roff"),
+            (Self::Oci, _) => include_str!("templates/oci/labels.env"),
+            (Self::OciArgs, _) => include_str!("templates/oci/labels.args"),
+            (Self::Proto, _) => include_str!("templates/proto/version.proto"),
+            (Self::Json | Self::Yaml | Self::Template | Self::Wasm, _) => "",
         }
     }
 }
 
+/// How `VERSION_PACKED` fits major, minor, patch, and the phase distance into 32 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Packing {
+    /// One byte per component: `1.2.3-rc.4` is `0x01020304`.
+    Bytes,
+    /// Two BCD digits per component: `12.0.1` is `0x12000100`.
+    Bcd,
+}
+
+impl Packing {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "bytes" => Some(Self::Bytes),
+            "bcd" => Some(Self::Bcd),
+            _ => None,
+        }
+    }
+
+    /// The packed word, with the build number in the low byte.
+    fn pack(self, version: &Version) -> Result<u32, BumpError> {
+        if version.base.mode != VersionMode::Semver {
+            return Err(BumpError::LogicError(
+                "packed versions are only valid for version.type = 'semver'".to_string(),
+            ));
+        }
+        let components = [
+            ("major", version.base.major),
+            ("minor", version.base.minor.unwrap_or(0)),
+            ("patch", version.base.patch.unwrap_or(0)),
+            ("phase distance", version.build_number()),
+        ];
+        components.iter().try_fold(0, |word, &(name, value)| {
+            let byte = match self {
+                Self::Bytes if value <= 0xFF => value,
+                Self::Bcd if value <= 99 => ((value / 10) << 4) | (value % 10),
+                _ => {
+                    return Err(BumpError::LogicError(format!(
+                        "{name} {value} does not fit the {} packing",
+                        match self {
+                            Self::Bytes => "bytes (max 255)",
+                            Self::Bcd => "bcd (max 99)",
+                        }
+                    )));
+                }
+            };
+            Ok((word << 8) | byte)
+        })
+    }
+}
+
+/// How TypeScript and JavaScript outputs export the version object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleFormat {
+    /// `export const VERSION` plus a default export.
+    #[default]
+    Esm,
+    /// `module.exports = { VERSION }` (`export = { VERSION }` in TypeScript).
+    Cjs,
+}
+
+impl ModuleFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "esm" => Some(Self::Esm),
+            "cjs" => Some(Self::Cjs),
+            _ => None,
+        }
+    }
+}
+
+/// Fill the `{export}` and `{exports}` slots of a TypeScript or JavaScript template.
+fn module_template(lang: Language, tmpl: &str, options: &OutputOptions) -> String {
+    let name = format!("{}VERSION", options.symbol_prefix);
+    let (export, exports) = match (options.module.unwrap_or_default(), lang) {
+        (ModuleFormat::Esm, _) => ("export ", format!("export default {name};")),
+        (ModuleFormat::Cjs, Language::TypeScript) => ("", format!("export = {{ {name} }};")),
+        (ModuleFormat::Cjs, _) => ("", format!("module.exports = {{ {name} }};")),
+    };
+    tmpl.replace("{export}", export)
+        .replace("{exports}", &exports)
+}
+
+/// Fill the `{package}`, `{namespace}` and `{class_name}` slots, rejecting options the
+/// language has no place for.
+fn declarations(
+    lang: Language,
+    tmpl: &str,
+    options: &OutputOptions,
+    path: &Path,
+) -> Result<String, BumpError> {
+    let unsupported = |option: &str| {
+        BumpError::LogicError(format!(
+            "{option} does not apply to the {} {}",
+            lang.file_description(),
+            path.display()
+        ))
+    };
+    let package = match (lang, &options.package) {
+        (Language::Go, package) => format!("package {}", package.as_deref().unwrap_or("version")),
+        (Language::Java, Some(package)) => format!("package {package};\n\n"),
+        (Language::Kotlin, Some(package)) => format!("package {package}\n\n"),
+        (Language::Proto, Some(package)) => format!("package {package};\n\n"),
+        (_, Some(_)) => return Err(unsupported("package")),
+        (_, None) => String::new(),
+    };
+    let namespace = match (lang, &options.namespace) {
+        (Language::CSharp | Language::Php, Some(namespace)) => {
+            format!("namespace {namespace};\n\n")
+        }
+        (_, Some(_)) => return Err(unsupported("namespace")),
+        (_, None) => String::new(),
+    };
+    if options.class_name.is_some()
+        && !matches!(
+            lang,
+            Language::Java
+                | Language::Kotlin
+                | Language::CSharp
+                | Language::Swift
+                | Language::Ruby
+                | Language::Php
+                | Language::Proto
+        )
+    {
+        return Err(unsupported("class_name"));
+    }
+    Ok(tmpl
+        .replace("{package}", &package)
+        .replace("{namespace}", &namespace)
+        .replace(
+            "{class_name}",
+            options.class_name.as_deref().unwrap_or(match lang {
+                // As in `Rails::VERSION::MAJOR`.
+                Language::Ruby => "VERSION",
+                _ => "Version",
+            }),
+        ))
+}
+
+/// The `guard` value that makes C headers use `#pragma once` instead of a macro.
+const PRAGMA_ONCE: &str = "pragma-once";
+
+/// Fill the `{guard_open}` and `{guard_close}` slots of a C header: `#pragma once`, the
+/// configured macro, or one named after the file (`core_version.h` gives
+/// `CORE_VERSION_H`) behind the symbol prefix.
+fn include_guard(tmpl: &str, options: &OutputOptions, path: &Path) -> Result<String, BumpError> {
+    let guard = match options.guard.as_deref() {
+        Some(PRAGMA_ONCE) => {
+            return Ok(tmpl
+                .replace("{guard_open}", "#pragma once")
+                .replace("{guard_close}", ""));
+        }
+        Some(guard) => guard.to_string(),
+        None => {
+            let name: String = path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default()
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' | 'A'..='Z' | '0'..='9' => c.to_ascii_uppercase(),
+                    _ => '_',
+                })
+                .collect();
+            // A macro cannot start with a digit, so `2d.h` becomes `BUMP_2D_H`.
+            let lead = if name.starts_with(|c: char| c.is_ascii_digit()) {
+                "BUMP_"
+            } else {
+                ""
+            };
+            format!("{}{lead}{name}", options.symbol_prefix)
+        }
+    };
+    let valid = guard.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && guard.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_');
+    if !valid {
+        return Err(BumpError::LogicError(format!(
+            "include guard '{guard}' for {} is not a C identifier; set guard (or --guard) \
+             to a macro name or '{PRAGMA_ONCE}'",
+            path.display()
+        )));
+    }
+    Ok(tmpl
+        .replace("{guard_open}", &format!("#ifndef {guard}\n#define {guard}"))
+        .replace("{guard_close}", &format!("\n\n#endif /* {guard} */")))
+}
+
+/// Fill the `{string_assert}` slot of a C header with a compile-time check that
+/// `VERSION_STRING` fits `max_string_length` bytes, failing now if it already does not.
+fn string_length_assert(
+    tmpl: &str,
+    options: &OutputOptions,
+    version_string: &str,
+    path: &Path,
+) -> Result<String, BumpError> {
+    let Some(max) = options.max_string_length else {
+        return Ok(tmpl.replace("{string_assert}", ""));
+    };
+    let p = &options.symbol_prefix;
+    // sizeof counts the terminating NUL.
+    let size = version_string.len() + 1;
+    if size > max {
+        return Err(BumpError::LogicError(format!(
+            "{}: {p}VERSION_STRING \"{version_string}\" needs {size} bytes with its NUL, \
+             more than max_string_length = {max}",
+            path.display()
+        )));
+    }
+    let message = format!("{p}VERSION_STRING does not fit the {max}-byte version field");
+    Ok(tmpl.replace(
+        "{string_assert}",
+        &format!(
+            "\n\n#ifdef __cplusplus\nstatic_assert(sizeof({p}VERSION_STRING) <= {max}, \"{message}\");\n#else\n_Static_assert(sizeof({p}VERSION_STRING) <= {max}, \"{message}\");\n#endif"
+        ),
+    ))
+}
+
+/// `major.minor.revision+build_num` as `imgtool sign --version` takes it, checked
+/// against the u8/u8/u16/u32 fields of MCUboot's `struct image_version`.
+fn mcuboot_version(version: &Version) -> Result<String, BumpError> {
+    if version.base.mode != VersionMode::Semver {
+        return Err(BumpError::LogicError(
+            "MCUboot image versions are only valid for version.type = 'semver'".to_string(),
+        ));
+    }
+    let major = version.base.major;
+    let minor = version.base.minor.unwrap_or(0);
+    let revision = version.base.patch.unwrap_or(0);
+    for (name, value, max) in [
+        ("major", major, u32::from(u8::MAX)),
+        ("minor", minor, u32::from(u8::MAX)),
+        ("revision", revision, u32::from(u16::MAX)),
+    ] {
+        if value > max {
+            return Err(BumpError::LogicError(format!(
+                "MCUboot image {name} {value} exceeds {max}"
+            )));
+        }
+    }
+    Ok(format!(
+        "{major}.{minor}.{revision}+{}",
+        version.build_number()
+    ))
+}
+
+/// Per-output settings, from `gen` flags or a `[[gen]]` entry.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OutputOptions {
+    /// Prepended to every generated symbol (`CORE_` gives `CORE_VERSION_MAJOR`).
+    #[serde(default)]
+    pub symbol_prefix: String,
+    /// Adds `VERSION_PACKED` to C headers.
+    #[serde(default)]
+    pub packed: Option<Packing>,
+    /// Include guard macro of C headers, or `pragma-once` (named after the file by default).
+    #[serde(default)]
+    pub guard: Option<String>,
+    /// Size in bytes, NUL included, of the firmware field `VERSION_STRING` must fit; C
+    /// headers then assert it at compile time.
+    #[serde(default)]
+    pub max_string_length: Option<usize>,
+    /// Adds the commit SHA, branch and dirty flag to source and environment outputs.
+    #[serde(default)]
+    pub git_info: bool,
+    /// Environment variable holding the CI build number, added as `VERSION_BUILD`.
+    #[serde(default)]
+    pub build_number_env: Option<String>,
+    /// Appends the CI build number to the fully qualified version (`VERSION_FULL`).
+    #[serde(default)]
+    pub build_number_in_full: bool,
+    /// ESM or CommonJS exports for TypeScript and JavaScript files (ESM by default).
+    #[serde(default)]
+    pub module: Option<ModuleFormat>,
+    /// Package of Java, Kotlin, Go and proto outputs (Go defaults to `version`).
+    #[serde(default)]
+    pub package: Option<String>,
+    /// Namespace of C# outputs.
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Type holding the constants in Java, Kotlin, C# and Swift, or the proto message
+    /// (default `Version`).
+    #[serde(default)]
+    pub class_name: Option<String>,
+    /// The file `--lang template` renders, with `{{ name }}` placeholders.
+    #[serde(default)]
+    pub template: Option<PathBuf>,
+    /// Leaves out the timestamp, or takes it from `SOURCE_DATE_EPOCH`, for byte-identical
+    /// outputs across rebuilds.
+    #[serde(default)]
+    pub reproducible: bool,
+    /// Rewrites the file even when the content is unchanged, updating its modification time.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// A `[[gen]]` entry: a file `bump gen` writes when no `--lang`/`--output` is given.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenOutput {
+    pub lang: String,
+    pub output: String,
+    #[serde(flatten)]
+    pub options: OutputOptions,
+}
+
+#[derive(Deserialize)]
+struct GenConfig {
+    #[serde(default)]
+    r#gen: Vec<GenOutput>,
+}
+
+/// The `[[gen]]` outputs declared in `bumpfile`, including any from an included
+/// shared bumpfile.
+pub fn configured_outputs(
+    bumpfile: &BumpFile,
+) -> Result<Vec<(Language, PathBuf, OutputOptions)>, BumpError> {
+    let path = bumpfile.path();
+    let config: GenConfig = toml::from_str(&bumpfile.merged().to_string()).map_err(|e| {
+        BumpError::ParseError(format!("invalid [[gen]] in {}: {e}", path.display()))
+    })?;
+    config
+        .r#gen
+        .into_iter()
+        .map(|entry| {
+            let lang = Language::from_str(&entry.lang).ok_or_else(|| {
+                BumpError::ParseError(format!(
+                    "unknown [[gen]] lang '{}' in {}",
+                    entry.lang,
+                    path.display()
+                ))
+            })?;
+            Ok((lang, PathBuf::from(entry.output), entry.options))
+        })
+        .collect()
+}
+
 struct OutputFields {
+    symbol_prefix: String,
+    packed: String,
+    version_code: String,
+    build_number: String,
+    git_info: String,
     version_string: String,
     timestamp: String,
     prefix: String,
@@ -59,48 +569,769 @@ struct OutputFields {
     minor: u32,
     patch: u32,
     phase: String,
+    candidate: u32,
+}
+
+/// The `[version_code]` constant in `lang`'s syntax, for templates with a
+/// `{version_code}` slot.
+fn version_code_line(lang: Language, symbol_prefix: &str, code: u32) -> String {
+    match lang {
+        Language::C => format!("\n#define {symbol_prefix}VERSION_CODE {code}"),
+        Language::Java => format!("\n    public static final int {symbol_prefix}CODE = {code};"),
+        Language::Kotlin => format!("\n    const val {symbol_prefix}CODE = {code}"),
+        Language::Swift => format!("\n    public static let {symbol_prefix}code = {code}"),
+        Language::Python | Language::Bazel => format!("\n{symbol_prefix}VERSION_CODE = {code}"),
+        Language::Rust => format!("\npub const {symbol_prefix}VERSION_CODE: u32 = {code};"),
+        Language::TypeScript | Language::JavaScript => format!("\n  code: {code},"),
+        Language::Nix => format!("\n  code = {code};"),
+        Language::Lua => format!("\n  code = {code},"),
+        Language::Ruby => format!("\n  {symbol_prefix}CODE = {code}"),
+        Language::Php => format!("\n    public const {symbol_prefix}CODE = {code};"),
+        Language::Properties | Language::Dotenv => {
+            format!("\n{symbol_prefix}VERSION_CODE={code}")
+        }
+        Language::Sh => format!("\nexport {symbol_prefix}VERSION_CODE={code}"),
+        Language::PowerShell => format!("\n$env:{symbol_prefix}VERSION_CODE = '{code}'"),
+        Language::Bat => format!("\n@set \"{symbol_prefix}VERSION_CODE={code}\""),
+        Language::Gas => format!("\n    .equ {symbol_prefix}VERSION_CODE, {code}"),
+        Language::Armasm => format!("\n{symbol_prefix}VERSION_CODE EQU {code}"),
+        _ => String::new(),
+    }
+}
+
+/// The CI build number constant in `lang`'s syntax, for templates with a
+/// `{build_number}` slot.
+fn build_number_line(lang: Language, p: &str, build: u32) -> String {
+    match lang {
+        Language::C => format!("\n#define {p}VERSION_BUILD {build}"),
+        Language::Go => format!("\n\nconst {p}BUILD = {build}"),
+        Language::Java => format!("\n    public static final int {p}BUILD = {build};"),
+        Language::Kotlin => format!("\n    const val {p}BUILD = {build}"),
+        Language::Swift => format!("\n    public static let {p}build = {build}"),
+        Language::CSharp => format!("\n    public const int {p}BUILD = {build};"),
+        Language::Python | Language::Bazel => format!("\n{p}VERSION_BUILD = {build}"),
+        Language::Rust => format!("\npub const {p}VERSION_BUILD: u32 = {build};"),
+        Language::TypeScript | Language::JavaScript => format!("\n  build: {build},"),
+        Language::Nix => format!("\n  build = {build};"),
+        Language::Lua => format!("\n  build = {build},"),
+        Language::Ruby => format!("\n  {p}BUILD = {build}"),
+        Language::Php => format!("\n    public const {p}BUILD = {build};"),
+        Language::Properties | Language::Dotenv => format!("\n{p}VERSION_BUILD={build}"),
+        Language::Sh => format!("\nexport {p}VERSION_BUILD={build}"),
+        Language::PowerShell => format!("\n$env:{p}VERSION_BUILD = '{build}'"),
+        Language::Bat => format!("\n@set \"{p}VERSION_BUILD={build}\""),
+        Language::Gas => format!("\n    .equ {p}VERSION_BUILD, {build}"),
+        Language::Armasm => format!("\n{p}VERSION_BUILD EQU {build}"),
+        _ => String::new(),
+    }
+}
+
+/// The CI build number from the variable `build_number_env` names, or `None` without
+/// the option; 0 when the variable is unset or empty, as in local builds.
+fn ci_build_number(options: &OutputOptions) -> Result<Option<u32>, BumpError> {
+    let Some(var) = &options.build_number_env else {
+        return Ok(None);
+    };
+    let Some(value) = std::env::var(var).ok().filter(|value| !value.is_empty()) else {
+        return Ok(Some(0));
+    };
+    value.trim().parse().map(Some).map_err(|_| {
+        BumpError::LogicError(format!(
+            "build_number_env: {var}='{value}' is not a build number"
+        ))
+    })
 }
 
-fn output_fields(version: &Version) -> Result<OutputFields, BumpError> {
+/// The build number `build_number_in_full` appends to the fully qualified version;
+/// none when the variable is unset.
+fn full_build_number(options: &OutputOptions) -> Result<Option<u32>, BumpError> {
+    if !options.build_number_in_full {
+        return Ok(None);
+    }
+    Ok(ci_build_number(options)?.filter(|build| *build > 0))
+}
+
+/// The commit being built: full SHA, branch (empty on a detached HEAD no CI variable
+/// names) and whether tracked files have uncommitted changes. Empty outside git; not
+/// dirty when the git binary is missing and the flag is unknown.
+struct GitInfo {
+    sha: String,
+    branch: String,
+    dirty: bool,
+}
+
+impl GitInfo {
+    fn current() -> Result<Self, BumpError> {
+        if !is_git_repository() {
+            return Ok(Self {
+                sha: String::new(),
+                branch: String::new(),
+                dirty: false,
+            });
+        }
+        Ok(Self {
+            sha: get_git_commit_full_sha()?,
+            branch: get_git_branch()?.unwrap_or_default(),
+            dirty: get_git_dirty()?.unwrap_or(false),
+        })
+    }
+}
+
+/// `value` escaped for the string literal `lang`'s git info lines put it in. Branch
+/// names can come from CI variables a fork's pull request sets, so a quote must not end
+/// the literal; control characters are dropped.
+fn escape_literal(lang: Language, value: &str) -> String {
+    let value: String = value.chars().filter(|c| !c.is_control()).collect();
+    let backslashed = || value.replace('\\', "\\\\").replace('"', "\\\"");
+    match lang {
+        Language::Sh => value.replace('\'', "'\\''"),
+        Language::PowerShell => value.replace('\'', "''"),
+        Language::Php => value.replace('\\', "\\\\").replace('\'', "\\'"),
+        // `@set "NAME=value"` has no escape for `"`; `%` would expand a variable.
+        Language::Bat => value.replace('"', "").replace('%', "%%"),
+        Language::Properties => value.replace('\\', "\\\\"),
+        // Unquoted unless needed, since shells and dotenv loaders both read these.
+        Language::Dotenv => {
+            if value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "._-/+@:".contains(c))
+            {
+                value
+            } else {
+                format!("'{}'", value.replace('\'', ""))
+            }
+        }
+        Language::Kotlin | Language::Nix => backslashed().replace('$', "\\$"),
+        Language::Ruby => backslashed().replace('#', "\\#"),
+        _ => backslashed(),
+    }
+}
+
+/// `GIT_SHA`, `GIT_BRANCH` and `GIT_DIRTY` in `lang`'s syntax, for templates with a
+/// `{git_info}` slot.
+fn git_info_lines(lang: Language, p: &str, git: &GitInfo) -> String {
+    let (sha, branch, dirty) = (
+        &escape_literal(lang, &git.sha),
+        &escape_literal(lang, &git.branch),
+        &git.dirty,
+    );
+    match lang {
+        Language::C => format!(
+            "\n#define {p}GIT_SHA \"{sha}\"\n#define {p}GIT_BRANCH \"{branch}\"\n#define {p}GIT_DIRTY {}",
+            u8::from(*dirty)
+        ),
+        Language::Go => format!(
+            "\n\nconst (\n\t{p}GIT_SHA    = \"{sha}\"\n\t{p}GIT_BRANCH = \"{branch}\"\n\t{p}GIT_DIRTY  = {dirty}\n)"
+        ),
+        Language::Java => format!(
+            "\n    public static final String {p}GIT_SHA = \"{sha}\";\n    public static final String {p}GIT_BRANCH = \"{branch}\";\n    public static final boolean {p}GIT_DIRTY = {dirty};"
+        ),
+        Language::Kotlin => format!(
+            "\n    const val {p}GIT_SHA = \"{sha}\"\n    const val {p}GIT_BRANCH = \"{branch}\"\n    const val {p}GIT_DIRTY = {dirty}"
+        ),
+        Language::Swift => format!(
+            "\n    public static let {p}gitSha = \"{sha}\"\n    public static let {p}gitBranch = \"{branch}\"\n    public static let {p}gitDirty = {dirty}"
+        ),
+        Language::CSharp => format!(
+            "\n    public const string {p}GIT_SHA = \"{sha}\";\n    public const string {p}GIT_BRANCH = \"{branch}\";\n    public const bool {p}GIT_DIRTY = {dirty};"
+        ),
+        Language::Python | Language::Bazel => format!(
+            "\n{p}GIT_SHA = \"{sha}\"\n{p}GIT_BRANCH = \"{branch}\"\n{p}GIT_DIRTY = {}",
+            if *dirty { "True" } else { "False" }
+        ),
+        Language::Rust => format!(
+            "\npub const {p}GIT_SHA: &str = \"{sha}\";\npub const {p}GIT_BRANCH: &str = \"{branch}\";\npub const {p}GIT_DIRTY: bool = {dirty};"
+        ),
+        Language::TypeScript | Language::JavaScript => {
+            format!("\n  sha: \"{sha}\",\n  branch: \"{branch}\",\n  dirty: {dirty},")
+        }
+        Language::Nix => {
+            format!("\n  sha = \"{sha}\";\n  branch = \"{branch}\";\n  dirty = {dirty};")
+        }
+        Language::Lua => {
+            format!("\n  sha = \"{sha}\",\n  branch = \"{branch}\",\n  dirty = {dirty},")
+        }
+        Language::Ruby => format!(
+            "\n  {p}GIT_SHA = \"{sha}\"\n  {p}GIT_BRANCH = \"{branch}\"\n  {p}GIT_DIRTY = {dirty}"
+        ),
+        Language::Php => format!(
+            "\n    public const {p}GIT_SHA = '{sha}';\n    public const {p}GIT_BRANCH = '{branch}';\n    public const {p}GIT_DIRTY = {dirty};"
+        ),
+        Language::Properties | Language::Dotenv => {
+            format!("\n{p}GIT_SHA={sha}\n{p}GIT_BRANCH={branch}\n{p}GIT_DIRTY={dirty}")
+        }
+        Language::Sh => format!(
+            "\nexport {p}GIT_SHA='{sha}'\nexport {p}GIT_BRANCH='{branch}'\nexport {p}GIT_DIRTY={dirty}"
+        ),
+        Language::PowerShell => format!(
+            "\n$env:{p}GIT_SHA = '{sha}'\n$env:{p}GIT_BRANCH = '{branch}'\n$env:{p}GIT_DIRTY = '{dirty}'"
+        ),
+        Language::Bat => format!(
+            "\n@set \"{p}GIT_SHA={sha}\"\n@set \"{p}GIT_BRANCH={branch}\"\n@set \"{p}GIT_DIRTY={dirty}\""
+        ),
+        _ => String::new(),
+    }
+}
+
+/// `SOURCE_DATE_EPOCH` (reproducible-builds.org), when set.
+pub fn source_date_epoch() -> Result<Option<chrono::DateTime<chrono::Utc>>, BumpError> {
+    let Some(value) = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(None);
+    };
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+        .map(Some)
+        .ok_or_else(|| {
+            BumpError::LogicError(format!(
+                "SOURCE_DATE_EPOCH='{value}' is not a number of seconds since the epoch"
+            ))
+        })
+}
+
+/// The timestamp outputs carry: the bumpfile's, or with `reproducible` the
+/// `SOURCE_DATE_EPOCH` in the bumpfile's format, and none without it.
+fn output_timestamp(version: &Version, options: &OutputOptions) -> Result<String, BumpError> {
+    if !options.reproducible {
+        return Ok(version.timestamp.last.clone());
+    }
+    Ok(source_date_epoch()?
+        .map(|time| time.format(&version.timestamp.format).to_string())
+        .unwrap_or_default())
+}
+
+fn output_fields(
+    lang: Language,
+    version: &Version,
+    options: &OutputOptions,
+) -> Result<OutputFields, BumpError> {
+    let version_code = version_code::current(version)?
+        .map(|code| version_code_line(lang, &options.symbol_prefix, code))
+        .unwrap_or_default();
+    let build_number = ci_build_number(options)?
+        .map(|build| build_number_line(lang, &options.symbol_prefix, build))
+        .unwrap_or_default();
+    let git_info = if options.git_info {
+        git_info_lines(lang, &options.symbol_prefix, &GitInfo::current()?)
+    } else {
+        String::new()
+    };
+    let packed = match options.packed {
+        Some(packing) => format!(
+            "\n#define {}VERSION_PACKED 0x{:08X}",
+            options.symbol_prefix,
+            packing.pack(version)?
+        ),
+        None => String::new(),
+    };
     Ok(OutputFields {
+        symbol_prefix: options.symbol_prefix.clone(),
+        packed,
+        version_code,
+        build_number,
+        git_info,
         version_string: print::to_string(version, &PrintOptions::default())?,
-        timestamp: version.timestamp.last.clone(),
+        timestamp: output_timestamp(version, options)?,
         prefix: version.prefix.clone(),
         major: version.base.major,
         minor: version.base.minor.unwrap_or(0),
         patch: version.base.patch.unwrap_or(0),
         phase: version.phase.name.clone(),
+        candidate: version.build_number(),
     })
 }
 
 fn render_calver(tmpl: &str, f: &OutputFields) -> String {
-    tmpl.replace("{version_string}", &f.version_string)
+    tmpl.replace("{symbol_prefix}", &f.symbol_prefix)
+        .replace("{version_code}", &f.version_code)
+        .replace("{build_number}", &f.build_number)
+        .replace("{git_info}", &f.git_info)
+        .replace("{version_string}", &f.version_string)
         .replace("{timestamp}", &f.timestamp)
 }
 
 fn render_semver(tmpl: &str, f: &OutputFields) -> String {
-    tmpl.replace("{prefix}", &f.prefix)
+    tmpl.replace("{symbol_prefix}", &f.symbol_prefix)
+        .replace("{packed}", &f.packed)
+        .replace("{version_code}", &f.version_code)
+        .replace("{build_number}", &f.build_number)
+        .replace("{git_info}", &f.git_info)
+        .replace("{prefix}", &f.prefix)
         .replace("{major}", &f.major.to_string())
         .replace("{minor}", &f.minor.to_string())
         .replace("{patch}", &f.patch.to_string())
         .replace("{phase}", &f.phase)
+        .replace("{candidate}", &f.candidate.to_string())
         .replace("{version_string}", &f.version_string)
         .replace("{timestamp}", &f.timestamp)
 }
 
-fn write_output(lang: Language, path: &Path, content: String) -> Result<(), BumpError> {
-    fs::write(path, content).map_err(BumpError::IoError)?;
+fn render_oci(tmpl: &str, f: &OutputFields, reproducible: bool) -> Result<String, BumpError> {
+    let revision = if is_git_repository() {
+        get_git_commit_full_sha()?
+    } else {
+        String::new()
+    };
+    let created = match source_date_epoch()? {
+        Some(time) => Some(time),
+        None if reproducible => None,
+        None => Some(chrono::Utc::now()),
+    }
+    .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+    .unwrap_or_default();
+    Ok(tmpl
+        .replace("{version_string}", &f.version_string)
+        .replace("{revision}", &revision)
+        .replace("{created}", &created))
+}
+
+fn render_wix(tmpl: &str, version: &Version, f: &OutputFields) -> Result<String, BumpError> {
+    let mut codes = String::new();
+    if let Some(config) = &version.msi {
+        if let Some(upgrade_code) = &config.upgrade_code {
+            codes.push_str(&format!("\n  <?define UpgradeCode = \"{upgrade_code}\" ?>"));
+        }
+        if config.product_code {
+            codes.push_str(&format!(
+                "\n  <?define ProductCode = \"{}\" ?>",
+                msi::product_code(version, config)?
+            ));
+        }
+    }
+    Ok(tmpl
+        .replace("{msi_version}", &msi::product_version(version)?)
+        .replace("{version_string}", &f.version_string)
+        .replace("{msi_codes}", &codes))
+}
+
+/// Write `content` to `path`, leaving the file, and so its modification time, alone
+/// when it already holds exactly that unless `force`, so make-style builds keyed on
+/// modification times do not rebuild.
+fn write_output(
+    lang: Language,
+    path: &Path,
+    content: String,
+    force: bool,
+) -> Result<(), BumpError> {
+    if !force && fs::read(path).is_ok_and(|current| current == content.as_bytes()) {
+        println!(
+            "{} unchanged at {}",
+            lang.file_description(),
+            path.display()
+        );
+        return Ok(());
+    }
+    fs::write(path, content).map_err(|err| write_error(path, err))?;
     println!("{} written to {}", lang.file_description(), path.display());
     Ok(())
 }
 
-pub fn output_file(lang: Language, version: &Version, path: &Path) -> Result<(), BumpError> {
-    let fields = output_fields(version)?;
+/// Mirror each `key=value` line into the running build with `buildkite-agent meta-data set`.
+fn set_buildkite_metadata(content: &str) -> Result<(), BumpError> {
+    for (key, value) in content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+    {
+        let status = ProcessCommand::new("buildkite-agent")
+            .args(["meta-data", "set", key, value])
+            .status()
+            .map_err(|e| BumpError::LogicError(format!("failed to run buildkite-agent: {e}")))?;
+        if !status.success() {
+            return Err(BumpError::LogicError(format!(
+                "buildkite-agent meta-data set {key} failed ({status})"
+            )));
+        }
+    }
+    println!("Buildkite meta-data set");
+    Ok(())
+}
+
+fn render_wasm(version: &Version, path: &Path) -> Result<String, BumpError> {
+    let target = wasm::target_for(version, path).ok_or_else(|| {
+        BumpError::LogicError(format!("no [[wasm]] target matches '{}'", path.display()))
+    })?;
+    let context = plugin::version_context(version)?;
+    wasm::call(Path::new(&target.module), "generate", &context.to_string())
+}
+
+/// The version with its build suffix (`print --with-suffix`), or without one outside a
+/// git repository, then `build` as further build metadata when given.
+fn suffixed_string(version: &Version, build: Option<u32>) -> Result<String, BumpError> {
+    let with_suffix = PrintOptions {
+        with_suffix: is_git_repository(),
+        ..PrintOptions::default()
+    };
+    let full = print::to_string(version, &with_suffix)?;
+    Ok(match build {
+        Some(build) if full.contains('+') => format!("{full}.{build}"),
+        Some(build) => format!("{full}+{build}"),
+        None => full,
+    })
+}
+
+/// The version and git state for deployment tooling, in a fixed key order.
+fn data_fields(
+    version: &Version,
+    options: &OutputOptions,
+) -> Result<Vec<(&'static str, serde_json::Value)>, BumpError> {
+    let (sha, branch, dirty) = if is_git_repository() {
+        (
+            Some(get_git_commit_full_sha()?),
+            get_git_branch()?,
+            get_git_dirty()?,
+        )
+    } else {
+        (None, None, None)
+    };
+    let mut fields = vec![
+        ("mode", json!(version.base.mode.as_str())),
+        ("prefix", json!(version.prefix)),
+        ("major", json!(version.base.major)),
+        ("minor", json!(version.base.minor.unwrap_or(0))),
+        ("patch", json!(version.base.patch.unwrap_or(0))),
+        ("phase", json!(version.phase.name)),
+        ("candidate", json!(version.build_number())),
+        (
+            "string",
+            json!(print::to_string(version, &PrintOptions::default())?),
+        ),
+        (
+            "full",
+            json!(suffixed_string(version, full_build_number(options)?)?),
+        ),
+        ("timestamp", json!(output_timestamp(version, options)?)),
+        ("sha", json!(sha)),
+        ("branch", json!(branch)),
+        ("dirty", json!(dirty)),
+    ];
+    if let Some(code) = version_code::current(version)? {
+        fields.push(("code", json!(code)));
+    }
+    if let Some(build) = ci_build_number(options)? {
+        fields.push(("build", json!(build)));
+    }
+    Ok(fields)
+}
+
+/// `data_fields` as a JSON object, or as a YAML mapping of JSON scalars (valid YAML).
+fn render_data(
+    lang: Language,
+    version: &Version,
+    options: &OutputOptions,
+) -> Result<String, BumpError> {
+    let fields = data_fields(version, options)?;
+    Ok(match lang {
+        Language::Json => {
+            let lines: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("  \"{key}\": {value}"))
+                .collect();
+            format!("{{\n{}\n}}\n", lines.join(",\n"))
+        }
+        _ => {
+            let mut out = String::from("# This file is generated by bump\n");
+            for (key, value) in &fields {
+                out.push_str(&format!("{key}: {value}\n"));
+            }
+            out
+        }
+    })
+}
+
+/// Render a `.tera` template with the `data_fields` as its context, so conditionals,
+/// loops and filters work. Unknown variables are an error, as with the built-in syntax.
+#[cfg(feature = "tera")]
+fn render_tera(
+    template: &Path,
+    content: &str,
+    fields: &[(&'static str, serde_json::Value)],
+) -> Result<String, BumpError> {
+    let mut context = tera::Context::new();
+    for (key, value) in fields {
+        context.insert(*key, value);
+    }
+    tera::Tera::one_off(content, &context, false).map_err(|err| {
+        // Tera keeps the cause (the unknown variable, the syntax error) in the source chain.
+        let mut message = err.to_string();
+        let mut source = std::error::Error::source(&err);
+        while let Some(cause) = source {
+            message.push_str(&format!(": {cause}"));
+            source = cause.source();
+        }
+        BumpError::ParseError(
+            message.replace("'__tera_one_off'", &format!("'{}'", template.display())),
+        )
+    })
+}
+
+#[cfg(not(feature = "tera"))]
+fn render_tera(
+    template: &Path,
+    _content: &str,
+    _fields: &[(&'static str, serde_json::Value)],
+) -> Result<String, BumpError> {
+    Err(BumpError::LogicError(format!(
+        "'{}' is a Tera template; rebuild bump with '--features tera'",
+        template.display()
+    )))
+}
+
+/// Replace every `{{ name }}` in `template` with the field of the same name from
+/// `data_fields` (empty when null); unknown names are an error so a typo never ends
+/// up in a generated file. Single braces are copied as they are. A `.tera` template
+/// is rendered with Tera instead.
+fn render_template(
+    version: &Version,
+    template: &Path,
+    options: &OutputOptions,
+) -> Result<String, BumpError> {
+    let content = fs::read_to_string(template).map_err(|err| {
+        BumpError::LogicError(format!(
+            "cannot read template '{}': {err}",
+            template.display()
+        ))
+    })?;
+    let fields = data_fields(version, options)?;
+    if template.extension().is_some_and(|ext| ext == "tera") {
+        return render_tera(template, &content, &fields);
+    }
+    let mut output = String::new();
+    let mut rest = content.as_str();
+    while let Some(open) = rest.find("{{") {
+        // `{{{ string }}}` in LaTeX: the first brace is text.
+        if rest[open + 2..].starts_with('{') {
+            output.push_str(&rest[..=open]);
+            rest = &rest[open + 1..];
+            continue;
+        }
+        output.push_str(&rest[..open]);
+        let close = rest[open..].find("}}").ok_or_else(|| {
+            BumpError::ParseError(format!("unclosed '{{{{' in {}", template.display()))
+        })?;
+        let name = rest[open + 2..open + close].trim();
+        let value = fields
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| {
+                let known: Vec<&str> = fields.iter().map(|(key, _)| *key).collect();
+                BumpError::LogicError(format!(
+                    "unknown placeholder {{{{ {name} }}}} in {} (known: {})",
+                    template.display(),
+                    known.join(", ")
+                ))
+            })?;
+        match value {
+            serde_json::Value::String(text) => output.push_str(text),
+            serde_json::Value::Null => {}
+            other => output.push_str(&other.to_string()),
+        }
+        rest = &rest[open + close + 2..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// The content `gen` writes to `path`, without touching the file.
+pub fn render(
+    lang: Language,
+    version: &Version,
+    path: &Path,
+    options: &OutputOptions,
+) -> Result<String, BumpError> {
+    if options.packed.is_some() && !matches!(lang, Language::C) {
+        return Err(BumpError::LogicError(format!(
+            "packed versions are only generated for C headers, not {}",
+            path.display()
+        )));
+    }
+    if options.guard.is_some() && !matches!(lang, Language::C) {
+        return Err(BumpError::LogicError(format!(
+            "include guards only apply to C headers, not {}",
+            path.display()
+        )));
+    }
+    if options.max_string_length.is_some() && !matches!(lang, Language::C) {
+        return Err(BumpError::LogicError(format!(
+            "max_string_length only applies to C headers, not {}",
+            path.display()
+        )));
+    }
+    if matches!(lang, Language::C) && version.base.mode == VersionMode::Semver {
+        // VERSION_NUMBER gives minor and patch eight bits each.
+        for (name, value) in [("minor", version.base.minor), ("patch", version.base.patch)] {
+            let value = value.unwrap_or(0);
+            if value > 0xFF {
+                return Err(BumpError::LogicError(format!(
+                    "{}: {name} {value} does not fit VERSION_NUMBER (max 255)",
+                    path.display()
+                )));
+            }
+        }
+    }
+    if options.git_info && !lang.template(version.base.mode).contains("{git_info}") {
+        return Err(BumpError::LogicError(format!(
+            "git_info does not apply to the {} {}",
+            lang.file_description(),
+            path.display()
+        )));
+    }
+    if options.build_number_env.is_some()
+        && !matches!(lang, Language::Json | Language::Yaml | Language::Template)
+        && !lang.template(version.base.mode).contains("{build_number}")
+    {
+        return Err(BumpError::LogicError(format!(
+            "build_number_env does not apply to the {} {}",
+            lang.file_description(),
+            path.display()
+        )));
+    }
+    if options.build_number_in_full && options.build_number_env.is_none() {
+        return Err(BumpError::LogicError(format!(
+            "{}: build_number_in_full needs build_number_env",
+            path.display()
+        )));
+    }
+    if options.module.is_some() && !matches!(lang, Language::TypeScript | Language::JavaScript) {
+        return Err(BumpError::LogicError(format!(
+            "module formats only apply to TypeScript and JavaScript, not {}",
+            path.display()
+        )));
+    }
+    if options.template.is_some() != matches!(lang, Language::Template) {
+        return Err(BumpError::LogicError(format!(
+            "{}: --lang template and --template (template in [[gen]]) go together",
+            path.display()
+        )));
+    }
+    match (lang, &options.template) {
+        (Language::Wasm, _) => return render_wasm(version, path),
+        (Language::Json | Language::Yaml, _) => return render_data(lang, version, options),
+        (Language::Template, Some(template)) => {
+            return render_template(version, template, options);
+        }
+        _ => {}
+    }
+    let fields = output_fields(lang, version, options)?;
     let mode = version.base.mode;
-    let tmpl = lang.template(mode);
-    let content = match mode {
-        VersionMode::Calver => render_calver(tmpl, &fields),
-        VersionMode::Semver => render_semver(tmpl, &fields),
+    let tmpl = &declarations(lang, lang.template(mode), options, path)?;
+    let tmpl = match lang {
+        Language::TypeScript | Language::JavaScript => &module_template(lang, tmpl, options),
+        Language::C => &string_length_assert(
+            &include_guard(tmpl, options, path)?,
+            options,
+            &fields.version_string,
+            path,
+        )?,
+        // Nix derivations and Bazel modules take the version without its prefix.
+        Language::Nix | Language::Bazel => &tmpl.replace(
+            "{bare_string}",
+            &print::to_string(
+                version,
+                &PrintOptions {
+                    no_prefix: true,
+                    ..PrintOptions::default()
+                },
+            )?,
+        ),
+        Language::Sh | Language::Dotenv | Language::PowerShell | Language::Bat => &tmpl.replace(
+            "{full_string}",
+            &suffixed_string(version, full_build_number(options)?)?,
+        ),
+        _ => tmpl,
+    };
+    let content = match (lang, mode) {
+        (Language::Oci | Language::OciArgs, _) => render_oci(tmpl, &fields, options.reproducible)?,
+        // Numeric symbols only, so CalVer year/month/day fill the semver fields.
+        (Language::Ld | Language::Asm | Language::Gas | Language::Armasm, _) => {
+            render_semver(tmpl, &fields)
+        }
+        (Language::Wix, _) => render_wix(tmpl, version, &fields)?,
+        (Language::Mcuboot, _) => tmpl.replace("{mcuboot_version}", &mcuboot_version(version)?),
+        (_, VersionMode::Calver) => render_calver(tmpl, &fields),
+        (_, VersionMode::Semver) => render_semver(tmpl, &fields),
+    };
+    // cmd.exe mishandles labels and some parsing in LF-only batch files.
+    Ok(match lang {
+        Language::Bat => content.replace('\n', "\r\n"),
+        _ => content,
+    })
+}
+
+/// Write `content`, rendered for `lang`, to `path`.
+pub fn output_file(
+    lang: Language,
+    path: &Path,
+    content: String,
+    options: &OutputOptions,
+) -> Result<(), BumpError> {
+    if let Language::Buildkite = lang
+        && std::env::var("BUILDKITE").is_ok_and(|v| v == "true")
+    {
+        set_buildkite_metadata(&content)?;
+    }
+    write_output(lang, path, content, options.force)
+}
+
+/// `-`/`+` lines turning `old` into `new`, from their longest common subsequence.
+fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut out = String::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            out.push_str(&format!("-{}\n", old[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("+{}\n", new[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+/// A diff from the file at `path` to what `gen` would write there, or `None` when
+/// it is up to date.
+pub fn stale_diff(
+    lang: Language,
+    version: &Version,
+    path: &Path,
+    options: &OutputOptions,
+) -> Result<Option<String>, BumpError> {
+    let expected = render(lang, version, path, options)?;
+    let current = match fs::read_to_string(path) {
+        Ok(current) => current,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Some(format!("{} is missing\n", path.display())));
+        }
+        Err(err) => return Err(BumpError::IoError(err)),
     };
-    write_output(lang, path, content)
+    if current == expected {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "--- {path} (on disk)\n+++ {path} (generated)\n{}",
+        line_diff(&current, &expected),
+        path = path.display()
+    )))
 }
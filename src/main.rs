@@ -3,13 +3,33 @@ use crate::lang::Language;
 use clap_complete::aot::{Shell, generate};
 use std::process::ExitCode;
 
+mod aliases;
+mod artifact;
 mod bump;
 mod bumpfile;
+mod changelog;
+mod check;
 mod cli;
+mod git_backend;
+mod guard;
+mod history;
+mod integrity;
+mod issues;
 mod lang;
+mod msi;
+mod notify;
+mod pep440;
+mod plugin;
 mod print;
+mod provenance;
+mod push;
+mod serve;
+mod stats;
 mod update;
 mod version;
+mod version_code;
+mod wasm;
+mod workspace;
 
 fn egress(result: Result<(), BumpError>) -> ExitCode {
     if let Err(err) = result {
@@ -20,7 +40,18 @@ fn egress(result: Result<(), BumpError>) -> ExitCode {
 }
 
 fn main() -> ExitCode {
+    let args: Vec<std::ffi::OsString> = std::env::args_os().collect();
+    if let Some(plugin) = plugin::find(&args) {
+        return match plugin::run(&plugin, &args[2..]) {
+            Ok(code) => ExitCode::from(code),
+            Err(err) => egress(Err(err)),
+        };
+    }
+
     let matches = cli::cli().get_matches();
+    if matches.get_flag("no-git") {
+        bump::disable_git();
+    }
     match matches.subcommand() {
         Some(("completion", sub_matches)) => {
             let shell = sub_matches
@@ -33,19 +64,31 @@ fn main() -> ExitCode {
         }
         Some(("init", sub_matches)) => egress(bump::initialize(sub_matches)),
         Some(("gen", sub_matches)) => {
-            let lang_str = sub_matches
-                .get_one::<String>("lang")
-                .expect("LANG not provided");
-            let Some(lang) = Language::from_str(lang_str) else {
-                return egress(Err(BumpError::LogicError(format!(
-                    "Invalid language specified: {lang_str}"
-                ))));
+            let lang = match sub_matches.get_one::<String>("lang") {
+                Some(lang_str) => match Language::from_str(lang_str) {
+                    Some(lang) => Some(lang),
+                    None => {
+                        return egress(Err(BumpError::LogicError(format!(
+                            "Invalid language specified: {lang_str}"
+                        ))));
+                    }
+                },
+                None => None,
             };
             egress(bump::generate(sub_matches, lang))
         }
         Some(("tag", sub_matches)) => egress(bump::tag_version(sub_matches)),
+        Some(("convert", sub_matches)) => egress(bump::convert(sub_matches)),
         Some(("update", sub_matches)) => egress(update::modify_file(sub_matches)),
         Some(("print", sub_matches)) => egress(print::run(sub_matches)),
+        Some(("artifact-name", sub_matches)) => egress(artifact::run(sub_matches)),
+        Some(("check", sub_matches)) => egress(check::run(sub_matches)),
+        Some(("stats", sub_matches)) => egress(stats::run(sub_matches)),
+        Some(("guard", sub_matches)) => egress(guard::run(sub_matches)),
+        Some(("verify", sub_matches)) => egress(integrity::run(sub_matches)),
+        Some(("history", sub_matches)) => egress(history::run(sub_matches)),
+        Some(("serve", sub_matches)) => egress(serve::run(sub_matches)),
+        Some(("auto", sub_matches)) => egress(workspace::auto(sub_matches)),
         _ => {
             if bump::has_meta_flags(&matches) || matches.contains_id("formal") {
                 egress(bump::apply(&matches))
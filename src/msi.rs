@@ -0,0 +1,71 @@
+use crate::bump::BumpError;
+use crate::print::{self, PrintOptions};
+use crate::version::{Msi, MsiCandidate, Version, VersionMode};
+use sha2::{Digest, Sha256};
+
+// Windows Installer compares only major.minor.build, limited to 255.255.65535;
+// a fourth field is ignored, so it is never emitted.
+
+/// The three-part `ProductVersion` for `version` under `[msi].candidate`.
+pub fn product_version(version: &Version) -> Result<String, BumpError> {
+    if version.base.mode != VersionMode::Semver {
+        return Err(BumpError::LogicError(
+            "MSI ProductVersion is only valid for version.type = 'semver'".to_string(),
+        ));
+    }
+    let candidate = version
+        .msi
+        .as_ref()
+        .map_or(MsiCandidate::default(), |msi| msi.candidate);
+    let major = version.base.major;
+    let minor = version.base.minor.unwrap_or(0);
+    let patch = version.base.patch.unwrap_or(0);
+    let build = match candidate {
+        MsiCandidate::Drop => patch,
+        MsiCandidate::Build if version.phase.name.is_empty() => patch * 100 + 99,
+        MsiCandidate::Build => {
+            let distance = version.phase.distance;
+            if distance >= 99 {
+                return Err(BumpError::LogicError(format!(
+                    "MSI candidate 'build' needs a phase distance below 99, found {distance}"
+                )));
+            }
+            patch * 100 + distance
+        }
+    };
+    for (name, value, max) in [
+        ("major", major, 255),
+        ("minor", minor, 255),
+        ("build", build, 65535),
+    ] {
+        if value > max {
+            return Err(BumpError::LogicError(format!(
+                "MSI ProductVersion {name} {value} exceeds {max}"
+            )));
+        }
+    }
+    Ok(format!("{major}.{minor}.{build}"))
+}
+
+/// A ProductCode GUID that is stable for one version of one product: a name-based
+/// (version 5 layout, SHA-256) GUID over the UpgradeCode and the version string.
+pub fn product_code(version: &Version, msi: &Msi) -> Result<String, BumpError> {
+    let upgrade_code = msi.upgrade_code.as_deref().ok_or_else(|| {
+        BumpError::LogicError("[msi].product_code needs [msi].upgrade_code".to_string())
+    })?;
+    let version_string = print::to_string(version, &PrintOptions::default())?;
+    let digest = Sha256::digest(format!("{}|{version_string}", upgrade_code.to_uppercase()));
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    bytes[6] = (bytes[6] & 0x0F) | 0x50;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{b:02X}")).collect();
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
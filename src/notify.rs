@@ -0,0 +1,30 @@
+use crate::bump::{BumpError, http_post_json};
+use crate::print::{self, PrintOptions};
+use crate::version::{NotifyKind, Version};
+use serde_json::json;
+
+/// Fire the `[notify]` webhook, if configured. Delivery failures are reported
+/// as warnings: the bump or tag has already happened and must not be undone.
+pub fn send(version: &Version, event: &str) -> Result<(), BumpError> {
+    let Some(notify) = &version.notify else {
+        return Ok(());
+    };
+    let version_string = print::to_string(version, &PrintOptions::default())?;
+    let message = notify
+        .message
+        .replace("{event}", event)
+        .replace("{version}", &version_string);
+    let payload = match notify.kind {
+        NotifyKind::Slack => json!({ "text": message }),
+        NotifyKind::Discord => json!({ "content": message }),
+        NotifyKind::Generic => json!({
+            "event": event,
+            "version": version_string,
+            "message": message,
+        }),
+    };
+    if let Err(err) = http_post_json(&notify.url, &payload.to_string()) {
+        eprintln!("bump warning: notification not delivered >> {err}");
+    }
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use crate::bump::BumpError;
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PreKey {
+    DevOnly,
+    Pre(u8, u64),
+    Final,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum DevKey {
+    Dev(u64),
+    Final,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum LocalSegment {
+    Text(String),
+    Number(u64),
+}
+
+/// A PEP 440 public version, compared by the spec's precedence rules.
+/// https://packaging.python.org/en/latest/specifications/version-specifiers/
+#[derive(Debug, Clone)]
+pub struct Pep440 {
+    epoch: u64,
+    release: Vec<u64>,
+    pre: Option<(u8, u64)>,
+    post: Option<u64>,
+    dev: Option<u64>,
+    local: Vec<LocalSegment>,
+}
+
+fn invalid(input: &str) -> BumpError {
+    BumpError::ParseError(format!("'{input}' is not a valid PEP 440 version"))
+}
+
+fn strip_separator(s: &str) -> &str {
+    s.strip_prefix(['.', '-', '_']).unwrap_or(s)
+}
+
+fn take_number(s: &str) -> (Option<u64>, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if end == 0 {
+        return (None, s);
+    }
+    (s[..end].parse().ok(), &s[end..])
+}
+
+/// Matches one of `spellings` after an optional separator, followed by an optional number.
+fn take_tagged<'a>(s: &'a str, spellings: &[&'a str]) -> Option<(&'a str, u64, &'a str)> {
+    let body = strip_separator(s);
+    let spelling = spellings
+        .iter()
+        .filter(|spelling| body.starts_with(**spelling))
+        .max_by_key(|spelling| spelling.len())?;
+    let (n, rest) = take_number(strip_separator(&body[spelling.len()..]));
+    match n {
+        Some(n) => Some((spelling, n, rest)),
+        None => Some((spelling, 0, &body[spelling.len()..])),
+    }
+}
+
+impl Pep440 {
+    pub fn parse(input: &str) -> Result<Self, BumpError> {
+        let lowered = input.trim().to_ascii_lowercase();
+        let mut s = lowered.strip_prefix('v').unwrap_or(&lowered);
+
+        let (public, local) = match s.split_once('+') {
+            Some((public, local)) => (public, Some(local)),
+            None => (s, None),
+        };
+        s = public;
+
+        let mut epoch = 0;
+        if let Some((e, rest)) = s.split_once('!') {
+            epoch = e.parse().map_err(|_| invalid(input))?;
+            s = rest;
+        }
+
+        let mut release = Vec::new();
+        loop {
+            let (n, rest) = take_number(s);
+            release.push(n.ok_or_else(|| invalid(input))?);
+            match rest.strip_prefix('.') {
+                Some(next) if next.starts_with(|c: char| c.is_ascii_digit()) => s = next,
+                _ => {
+                    s = rest;
+                    break;
+                }
+            }
+        }
+
+        let mut pre = None;
+        if let Some((spelling, n, rest)) =
+            take_tagged(s, &["a", "alpha", "b", "beta", "c", "rc", "pre", "preview"])
+        {
+            let kind = match spelling {
+                "a" | "alpha" => 0,
+                "b" | "beta" => 1,
+                _ => 2,
+            };
+            pre = Some((kind, n));
+            s = rest;
+        }
+
+        let mut post = None;
+        if let Some((_, n, rest)) = take_tagged(s, &["post", "rev", "r"]) {
+            post = Some(n);
+            s = rest;
+        } else if let Some(implicit) = s.strip_prefix('-') {
+            let (n, rest) = take_number(implicit);
+            if let Some(n) = n {
+                post = Some(n);
+                s = rest;
+            }
+        }
+
+        let mut dev = None;
+        if let Some((_, n, rest)) = take_tagged(s, &["dev"]) {
+            dev = Some(n);
+            s = rest;
+        }
+
+        if !s.is_empty() {
+            return Err(invalid(input));
+        }
+
+        let local = local
+            .map(|local| {
+                local
+                    .split(['.', '-', '_'])
+                    .map(|segment| match segment.parse() {
+                        Ok(n) => Ok(LocalSegment::Number(n)),
+                        // Rejects the empty segments of `1.0+` and `1.0+abc..1`.
+                        Err(_)
+                            if !segment.is_empty()
+                                && segment.chars().all(|c| c.is_ascii_alphanumeric()) =>
+                        {
+                            Ok(LocalSegment::Text(segment.to_string()))
+                        }
+                        Err(_) => Err(invalid(input)),
+                    })
+                    .collect::<Result<_, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            epoch,
+            release,
+            pre,
+            post,
+            dev,
+            local,
+        })
+    }
+
+    fn key(&self) -> (u64, Vec<u64>, PreKey, Option<u64>, DevKey, &[LocalSegment]) {
+        let mut release = self.release.clone();
+        while release.len() > 1 && release.last() == Some(&0) {
+            release.pop();
+        }
+        let pre = match (self.pre, self.post, self.dev) {
+            (Some((kind, n)), _, _) => PreKey::Pre(kind, n),
+            (None, None, Some(_)) => PreKey::DevOnly,
+            _ => PreKey::Final,
+        };
+        let dev = self.dev.map_or(DevKey::Final, DevKey::Dev);
+        (self.epoch, release, pre, self.post, dev, &self.local)
+    }
+}
+
+/// The normalized form: `1.2.3rc1`, `1.2.3.post1.dev2`, `1!2.0+abc1234`.
+impl fmt::Display for Pep440 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+        let release: Vec<String> = self.release.iter().map(u64::to_string).collect();
+        f.write_str(&release.join("."))?;
+        if let Some((kind, n)) = self.pre {
+            write!(f, "{}{n}", ["a", "b", "rc"][usize::from(kind)])?;
+        }
+        if let Some(n) = self.post {
+            write!(f, ".post{n}")?;
+        }
+        if let Some(n) = self.dev {
+            write!(f, ".dev{n}")?;
+        }
+        if !self.local.is_empty() {
+            let local: Vec<String> = self
+                .local
+                .iter()
+                .map(|segment| match segment {
+                    LocalSegment::Text(text) => text.clone(),
+                    LocalSegment::Number(n) => n.to_string(),
+                })
+                .collect();
+            write!(f, "+{}", local.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Pep440 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pep440 {}
+
+impl PartialOrd for Pep440 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pep440 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
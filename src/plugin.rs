@@ -0,0 +1,73 @@
+use crate::bump::{BumpError, resolve_path};
+use crate::bumpfile::BumpFile;
+use crate::cli;
+use crate::print::{self, PrintOptions};
+use crate::version::Version;
+use serde_json::json;
+use std::ffi::OsString;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command as ProcessCommand, Stdio};
+
+fn is_builtin(verb: &str) -> bool {
+    verb == "help"
+        || cli::cli()
+            .get_subcommands()
+            .any(|cmd| cmd.get_name() == verb || cmd.get_all_aliases().any(|alias| alias == verb))
+}
+
+/// Cargo-style fallback: `bump <verb>` runs `bump-<verb>` from PATH when `<verb>`
+/// is neither a built-in subcommand nor an existing bumpfile path.
+pub fn find(args: &[OsString]) -> Option<PathBuf> {
+    let verb = args.get(1)?.to_str()?;
+    if verb.starts_with('-') || is_builtin(verb) || Path::new(verb).exists() {
+        return None;
+    }
+    let exe = format!("bump-{verb}{}", std::env::consts::EXE_SUFFIX);
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(&exe))
+        .find(|candidate| candidate.is_file())
+}
+
+/// The version context handed to external and WASM plugins.
+pub fn version_context(version: &Version) -> Result<serde_json::Value, BumpError> {
+    Ok(json!({
+        "version_string": print::to_string(version, &PrintOptions::default())?,
+        "version": version,
+    }))
+}
+
+/// The context of ./bump.toml, or null fields when it is missing or broken, so a
+/// plugin that repairs or migrates the bumpfile still runs.
+fn context() -> String {
+    let path = resolve_path("bump.toml");
+    let loaded = BumpFile::load(&path)
+        .and_then(|bumpfile| bumpfile.version())
+        .and_then(|version| version_context(&version));
+    match loaded {
+        Ok(mut context) => {
+            context["bumpfile"] = json!(path);
+            context
+        }
+        Err(_) => json!({ "bumpfile": null, "version_string": null, "version": null }),
+    }
+    .to_string()
+}
+
+/// Run the plugin with the remaining arguments, feeding the version context as JSON on stdin.
+pub fn run(plugin: &Path, args: &[OsString]) -> Result<u8, BumpError> {
+    let context = context();
+    let mut child = ProcessCommand::new(plugin)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| BumpError::LogicError(format!("failed to run {}: {e}", plugin.display())))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A plugin that never reads stdin closes the pipe early; that is not an error.
+        let _ = stdin.write_all(context.as_bytes());
+    }
+    let status = child.wait().map_err(BumpError::IoError)?;
+    Ok(status
+        .code()
+        .map_or(1, |code| u8::try_from(code).unwrap_or(1)))
+}
@@ -1,9 +1,47 @@
 use crate::bump::{
     BumpError, get_git_branch, get_git_commit_sha, is_git_repository, load_bumpfile,
 };
-use crate::version::{LabelPosition, SuffixMode, Version, VersionMode};
+use crate::version::{CalendarField, LabelPosition, SuffixMode, Version, VersionMode};
+use crate::workspace;
 use clap::ArgMatches;
 
+/// Characters a printed version has to be restricted to (`--docker-safe`,
+/// `--filename-safe`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeCharset {
+    /// Docker tags: `[A-Za-z0-9_.-]`, not starting with `.` or `-`, at most 128 long.
+    Docker,
+    /// File names on every common filesystem.
+    Filename,
+}
+
+impl SafeCharset {
+    /// `+` becomes `-`, `/` is dropped, and any other illegal character becomes `-`.
+    pub fn apply(self, text: &str) -> String {
+        let legal = |c: char| match self {
+            Self::Docker => c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'),
+            Self::Filename => {
+                !c.is_control()
+                    && !c.is_whitespace()
+                    && !matches!(c, '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+            }
+        };
+        let safe: String = text
+            .chars()
+            .filter(|&c| c != '/')
+            .map(|c| if c != '+' && legal(c) { c } else { '-' })
+            .collect();
+        match self {
+            Self::Docker => safe
+                .trim_start_matches(['.', '-'])
+                .chars()
+                .take(128)
+                .collect(),
+            Self::Filename => safe,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct PrintOptions {
     pub only_prefix: bool,
@@ -15,20 +53,30 @@ pub struct PrintOptions {
     pub with_timestamp: bool,
     pub with_label: Option<String>,
     pub full: bool,
+    pub safe: Option<SafeCharset>,
 }
 
 impl PrintOptions {
     pub fn parse(matches: &ArgMatches) -> Result<Self, BumpError> {
+        let safe = if matches.get_flag("docker-safe") {
+            Some(SafeCharset::Docker)
+        } else if matches.get_flag("filename-safe") {
+            Some(SafeCharset::Filename)
+        } else {
+            None
+        };
         let opts = Self {
             only_prefix: matches.get_flag("only-prefix"),
             only_phase: matches.get_flag("only-phase"),
             only_base: matches.get_flag("only-base"),
             no_prefix: matches.get_flag("no-prefix"),
             no_phase: matches.get_flag("no-phase"),
-            with_suffix: matches.get_flag("with-suffix"),
+            // The safe variants print the fully qualified version, suffix included.
+            with_suffix: matches.get_flag("with-suffix") || safe.is_some(),
             with_timestamp: matches.get_flag("with-timestamp"),
             with_label: matches.get_one::<String>("with-label").cloned(),
             full: matches.get_flag("full"),
+            safe,
         };
 
         let only = [opts.only_prefix, opts.only_phase, opts.only_base]
@@ -111,15 +159,10 @@ fn push_if_active(out: &mut String, field: &Field) {
 
 impl Components {
     pub fn from(version: &Version, opts: &PrintOptions) -> Result<Self, BumpError> {
-        let suffix_value = if is_git_repository() {
-            suffix(version)?
-        } else {
-            String::new()
-        };
         Ok(Self {
             prefix: Field {
                 active: true,
-                value: version.prefix.clone(),
+                value: prefix(version)?,
             },
             base: Field {
                 active: true,
@@ -129,9 +172,10 @@ impl Components {
                 active: true,
                 value: phase(version),
             },
+            // Filled in by `apply_opts` only when requested, so plain prints never touch git.
             suffix: Field {
                 active: false,
-                value: suffix_value,
+                value: String::new(),
             },
             timestamp: Field {
                 active: false,
@@ -219,9 +263,17 @@ impl Components {
 }
 
 pub fn run(matches: &ArgMatches) -> Result<(), BumpError> {
+    let opts = PrintOptions::parse(matches)?;
+    if workspace::is_selected(matches) {
+        // One `<member> <version>` line per member, so scripts can read them in a loop.
+        for member in workspace::members(matches)? {
+            let version = member.bumpfile.version()?;
+            println!("{} {}", member.name, to_string(&version, &opts)?);
+        }
+        return Ok(());
+    }
     let bumpfile = load_bumpfile(matches)?;
     let version = bumpfile.version()?;
-    let opts = PrintOptions::parse(matches)?;
     let mut components = Components::from(&version, &opts)?;
     print!("{}", assemble(&version, &opts, &mut components)?);
     Ok(())
@@ -237,14 +289,65 @@ pub fn assemble(
     opts: &PrintOptions,
     components: &mut Components,
 ) -> Result<String, BumpError> {
-    if let Some(segment) = components.apply_opts(version, opts)? {
-        return Ok(segment);
+    let output = match components.apply_opts(version, opts)? {
+        Some(segment) => segment,
+        None => components.collect(),
+    };
+    Ok(match opts.safe {
+        Some(safe) => safe.apply(&output),
+        None => output,
+    })
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters.
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
     }
-    Ok(components.collect())
+    rest.ends_with(last)
 }
 
-fn format_component(version: &Version, n: u32) -> String {
-    if version.base.mode == VersionMode::Calver {
+/// Exact branch names win over wildcard patterns; among wildcards the longest pattern wins.
+fn prefix(version: &Version) -> Result<String, BumpError> {
+    if version.branch_prefix.is_empty() || !is_git_repository() {
+        return Ok(version.prefix.clone());
+    }
+    let Some(branch) = get_git_branch()? else {
+        return Ok(version.prefix.clone());
+    };
+    if let Some(prefix) = version.branch_prefix.get(&branch) {
+        return Ok(prefix.clone());
+    }
+    Ok(version
+        .branch_prefix
+        .iter()
+        .filter(|(pattern, _)| pattern.contains('*') && glob_matches(pattern, &branch))
+        .max_by_key(|(pattern, _)| pattern.len())
+        .map_or_else(|| version.prefix.clone(), |(_, prefix)| prefix.clone()))
+}
+
+/// Component `index` of the base (1 for minor/month, 2 for patch/day); CalVer months
+/// and days are zero-padded.
+fn format_component(version: &Version, index: usize, n: u32) -> String {
+    let padded = version.base.mode == VersionMode::Calver
+        && version
+            .calendar_fields()
+            .ok()
+            .and_then(|fields| fields.get(index).copied())
+            .is_some_and(CalendarField::padded);
+    if padded {
         format!("{n:02}")
     } else {
         n.to_string()
@@ -257,21 +360,21 @@ fn base(version: &Version) -> String {
             "{}{}{}{}{}",
             version.base.major,
             version.base.delimiter,
-            format_component(version, minor),
+            format_component(version, 1, minor),
             version.base.delimiter,
-            format_component(version, patch),
+            format_component(version, 2, patch),
         ),
         (Some(minor), None) => format!(
             "{}{}{}",
             version.base.major,
             version.base.delimiter,
-            format_component(version, minor),
+            format_component(version, 1, minor),
         ),
         (None, Some(patch)) => format!(
             "{}{}{}",
             version.base.major,
             version.base.delimiter,
-            format_component(version, patch),
+            format_component(version, 2, patch),
         ),
         _ => format!("{}", version.base.major),
     }
@@ -295,6 +398,25 @@ fn phase(version: &Version) -> String {
     }
 }
 
+/// Map `text` onto SemVer build-metadata identifiers (`[0-9A-Za-z-]` joined by dots):
+/// `/` and `_` become `-`, other invalid characters are dropped, empty identifiers removed.
+fn sanitize_build_metadata(text: &str) -> String {
+    let mapped: String = text
+        .chars()
+        .filter_map(|c| match c {
+            '/' | '_' => Some('-'),
+            c if c.is_ascii_alphanumeric() || c == '-' || c == '.' => Some(c),
+            _ => None,
+        })
+        .collect();
+    mapped
+        .split('.')
+        .map(|identifier| identifier.trim_matches('-'))
+        .filter(|identifier| !identifier.is_empty())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 fn suffix(version: &Version) -> Result<String, BumpError> {
     if !is_git_repository() {
         return Err(BumpError::Git("Not a git repository".to_string()));
@@ -305,7 +427,16 @@ fn suffix(version: &Version) -> Result<String, BumpError> {
             Ok(format!("{}{}", version.suffix.separator, sha))
         }
         SuffixMode::Branch => {
-            let branch = get_git_branch()?;
+            let branch = match (get_git_branch()?, &version.suffix.detached) {
+                (Some(branch), _) => branch,
+                (None, Some(placeholder)) => placeholder.clone(),
+                (None, None) => get_git_commit_sha()?,
+            };
+            let branch = if version.suffix.sanitize {
+                sanitize_build_metadata(&branch)
+            } else {
+                branch
+            };
             Ok(format!("{}{}", version.suffix.separator, branch))
         }
     }
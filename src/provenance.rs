@@ -0,0 +1,54 @@
+use crate::bump::{BumpError, ensure_directory_exists, get_git_commit_full_sha};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{env, fs, path::Path};
+
+/// Identify the CI system (or local machine) that performed the release.
+fn builder_id() -> String {
+    let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+    if let (Some(server), Some(workflow_ref)) =
+        (var("GITHUB_SERVER_URL"), var("GITHUB_WORKFLOW_REF"))
+    {
+        return format!("{server}/{workflow_ref}");
+    }
+    ["CI_JOB_URL", "BUILD_URL", "BUILDKITE_BUILD_URL"]
+        .iter()
+        .find_map(|name| var(name))
+        .unwrap_or_else(|| {
+            format!(
+                "local:{}",
+                var("HOSTNAME").unwrap_or_else(|| "unknown".to_string())
+            )
+        })
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Write a provenance statement for `tag_name` to `output`.
+pub fn write(tag_name: &str, bumpfile: &Path, output: &Path) -> Result<(), BumpError> {
+    let config = fs::read(bumpfile).map_err(BumpError::IoError)?;
+    let statement = json!({
+        "builder": { "id": builder_id() },
+        "subject": {
+            "tag": tag_name,
+            "commit": get_git_commit_full_sha()?,
+        },
+        "version": tag_name,
+        "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        "config": {
+            "path": bumpfile,
+            "sha256": sha256_hex(&config),
+        },
+    });
+    let content = serde_json::to_string_pretty(&statement)
+        .map_err(|e| BumpError::LogicError(format!("failed to serialize provenance: {e}")))?;
+    ensure_directory_exists(output)?;
+    fs::write(output, content + "\n").map_err(BumpError::IoError)?;
+    println!("Provenance written to {}", output.display());
+    Ok(())
+}
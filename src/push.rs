@@ -0,0 +1,121 @@
+use crate::bump::{
+    BumpError, clear_git_cache, finish_bump, get_bump_type, git_cmd, is_git_repository,
+    load_bumpfile, run_git, save_bump, with_meta_flags,
+};
+use crate::history;
+use crate::print::{self, PrintOptions};
+use crate::workspace;
+use clap::ArgMatches;
+
+/// Run git with `args`, returning whether it succeeded and its stderr.
+fn git(args: &[&str]) -> Result<(bool, String), BumpError> {
+    let output = git_cmd()
+        .args(args)
+        .output()
+        .map_err(|e| BumpError::Git(format!("git {}: {e}", args.join(" "))))?;
+    Ok((
+        output.status.success(),
+        String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    ))
+}
+
+/// Whether a failed push was rejected only because the remote branch moved on, as opposed
+/// to failing authentication, a protected branch or an unreachable remote.
+fn lost_race(stderr: &str) -> bool {
+    stderr.contains("[rejected]")
+        && (stderr.contains("fetch first") || stderr.contains("non-fast-forward"))
+}
+
+/// Drop the unpushed bump commit and replay any other local commits onto `remote`'s
+/// `branch`, so the bump can be applied again on top of what won the race.
+fn rebase_onto_remote(remote: &str, branch: &str) -> Result<(), BumpError> {
+    run_git("reset --hard HEAD~1")?;
+    run_git(&format!("fetch {remote} {branch}"))?;
+    let (rebased, stderr) = git(&["rebase", "FETCH_HEAD"])?;
+    clear_git_cache();
+    if !rebased {
+        let _ = git(&["rebase", "--abort"]);
+        return Err(BumpError::Git(format!(
+            "could not rebase onto {remote}/{branch}: {stderr}"
+        )));
+    }
+    Ok(())
+}
+
+/// `bump --major|--minor|--patch|--phase|--calendar --push`: bump, commit the tracked
+/// changes and push the commit to the current branch on `--remote`. When the push is
+/// rejected because another pipeline pushed first, fetch, re-read the bumpfile, bump
+/// again on top of the new head and retry, up to `--retries` times. Hooks and the
+/// notification run once: `pre_bump` before the first attempt, the rest after the push.
+pub fn run(matches: &ArgMatches) -> Result<(), BumpError> {
+    if workspace::is_selected(matches) {
+        return Err(BumpError::LogicError(
+            "--push bumps a single bumpfile; drop --member and --all".to_string(),
+        ));
+    }
+    if !is_git_repository() {
+        return Err(BumpError::LogicError("Not in a git repository".to_string()));
+    }
+    let remote = matches
+        .get_one::<String>("remote")
+        .map_or("origin", String::as_str);
+    let retries = matches.get_one::<u32>("retries").copied().unwrap_or(0);
+    let branch = run_git("rev-parse --abbrev-ref HEAD")?;
+    if branch == "HEAD" {
+        return Err(BumpError::LogicError(
+            "--push needs a checked out branch, not a detached HEAD".to_string(),
+        ));
+    }
+    if !run_git("status --porcelain --untracked-files=no")?.is_empty() {
+        return Err(BumpError::LogicError(
+            "--push commits every tracked change; commit or stash local changes first".to_string(),
+        ));
+    }
+
+    let bump_type = get_bump_type(matches)?;
+    for attempt in 0..=retries {
+        let mut bumpfile = load_bumpfile(matches)?;
+        let version = with_meta_flags(matches, bumpfile.version()?)?;
+        let version = save_bump(&mut bumpfile, version, &bump_type, attempt == 0)?;
+        let version_string = print::to_string(&version, &PrintOptions::default())?;
+        run_git("add -u")?;
+        // A journal created by this bump is not tracked yet.
+        if let Some(journal) = history::journal(&version, bumpfile.path()) {
+            let journal = journal.to_string_lossy();
+            let (staged, stderr) = git(&["add", "--", &journal])?;
+            if !staged {
+                return Err(BumpError::Git(format!("git add {journal}: {stderr}")));
+            }
+        }
+        let message = format!("chore(release): bump version to {version_string}");
+        let (committed, stderr) = git(&["commit", "-q", "-m", &message])?;
+        if !committed {
+            return Err(BumpError::Git(format!("git commit: {stderr}")));
+        }
+        clear_git_cache();
+
+        let refspec = format!("HEAD:refs/heads/{branch}");
+        let (pushed, stderr) = git(&["push", "-q", remote, &refspec])?;
+        if pushed {
+            println!("pushed {version_string} to {remote}/{branch}");
+            return finish_bump(&version);
+        }
+        if !lost_race(&stderr) {
+            return Err(BumpError::Git(format!(
+                "push to {remote}/{branch} failed; the bump commit is kept locally: {stderr}"
+            )));
+        }
+        if attempt == retries {
+            return Err(BumpError::Git(format!(
+                "push to {remote}/{branch} rejected after {} attempt(s); the bump commit is kept locally: {stderr}",
+                attempt + 1
+            )));
+        }
+        eprintln!(
+            "push to {remote}/{branch} rejected, bumping again on top of it (retry {} of {retries})",
+            attempt + 1
+        );
+        rebase_onto_remote(remote, &branch)?;
+    }
+    Ok(())
+}
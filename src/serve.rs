@@ -0,0 +1,142 @@
+use crate::bump::{BumpError, clear_git_cache, load_bumpfile};
+use crate::print::{self, PrintOptions};
+use clap::ArgMatches;
+use serde_json::json;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+/// How long a client may take to send its request before the connection is dropped;
+/// requests are answered one at a time, so an idle client would stall everyone else.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn text(status: &'static str, body: String) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body,
+        }
+    }
+}
+
+/// Answer one request from the bumpfile and repository as they are now, so bumps and
+/// new commits show up without a restart.
+fn respond(matches: &ArgMatches, method: &str, target: &str) -> Result<Response, BumpError> {
+    clear_git_cache();
+    if method != "GET" {
+        return Ok(Response::text(
+            "405 Method Not Allowed",
+            "only GET is supported\n".to_string(),
+        ));
+    }
+    let path = target.split('?').next().unwrap_or_default();
+    if !matches!(path, "/" | "/version" | "/version/full" | "/version.json") {
+        return Ok(Response::text(
+            "404 Not Found",
+            "try /version, /version/full or /version.json\n".to_string(),
+        ));
+    }
+
+    let version = load_bumpfile(matches)?.version()?;
+    let short = print::to_string(&version, &PrintOptions::default())?;
+    // The suffix needs git; outside a repository only /version/full fails.
+    let full = || {
+        print::to_string(
+            &version,
+            &PrintOptions {
+                full: true,
+                ..PrintOptions::default()
+            },
+        )
+    };
+    Ok(match path {
+        "/version/full" => Response::text("200 OK", format!("{}\n", full()?)),
+        "/version.json" => {
+            let report = json!({
+                "version": short,
+                "full": full().ok(),
+                "prefix": version.prefix,
+                "base": {
+                    "mode": version.base.mode.as_str(),
+                    "major": version.base.major,
+                    "minor": version.base.minor,
+                    "patch": version.base.patch,
+                },
+                "phase": {
+                    "name": version.phase.name,
+                    "distance": version.phase.distance,
+                },
+            });
+            Response {
+                status: "200 OK",
+                content_type: "application/json",
+                body: format!("{report}\n"),
+            }
+        }
+        _ => Response::text("200 OK", format!("{short}\n")),
+    })
+}
+
+fn handle(matches: &ArgMatches, stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers; nothing in them changes the answer.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+    let response = respond(matches, method, target)
+        .unwrap_or_else(|err| Response::text("500 Internal Server Error", format!("{err}\n")));
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// `bump serve`: answer `GET /version` (plain text), `/version/full` (with the suffix
+/// and timestamp) and `/version.json` on a local port until interrupted.
+pub fn run(matches: &ArgMatches) -> Result<(), BumpError> {
+    // Fail on a missing or broken bumpfile before listening.
+    load_bumpfile(matches)?.version()?;
+    let bind = matches
+        .get_one::<String>("bind")
+        .map(String::as_str)
+        .unwrap_or("127.0.0.1");
+    let port = matches.get_one::<u16>("port").copied().unwrap_or(7070);
+    let listener = TcpListener::bind((bind, port)).map_err(BumpError::IoError)?;
+    let address = listener.local_addr().map_err(BumpError::IoError)?;
+    let bumpfile = matches
+        .get_one::<String>("bumpfile")
+        .expect("PATH not provided");
+    println!("serving {bumpfile} on http://{address}/version");
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(err) = handle(matches, stream) {
+                    eprintln!("bump serve: {err}");
+                }
+            }
+            Err(err) => eprintln!("bump serve: {err}"),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use crate::bump::{BumpError, git_cmd, is_git_repository, load_bumpfile};
+use crate::version::Version;
+use chrono::{DateTime, NaiveDate};
+use clap::ArgMatches;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+struct Release {
+    tag: String,
+    date: NaiveDate,
+    version: Version,
+}
+
+impl Release {
+    fn base(&self) -> (u32, Option<u32>, Option<u32>) {
+        let base = &self.version.base;
+        (base.major, base.minor, base.patch)
+    }
+
+    fn is_final(&self) -> bool {
+        self.version.phase.name.is_empty()
+    }
+}
+
+/// Version tags matching the bumpfile's prefix and component count, oldest first.
+/// Alias tags such as `v1` or `latest` are skipped.
+fn releases(template: &Version) -> Result<Vec<Release>, BumpError> {
+    let output = git_cmd()
+        .args([
+            "for-each-ref",
+            "--sort=creatordate",
+            "--format=%(refname:short)%09%(creatordate:unix)",
+            "refs/tags",
+        ])
+        .output()
+        .map_err(|e| BumpError::Git(format!("git for-each-ref: {e}")))?;
+    if !output.status.success() {
+        return Err(BumpError::Git(format!(
+            "git for-each-ref: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let mut releases = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((tag, timestamp)) = line.split_once('\t') else {
+            continue;
+        };
+        let Some(version) = template.parse_tag(tag) else {
+            continue;
+        };
+        let Some(date) = timestamp
+            .parse()
+            .ok()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        else {
+            continue;
+        };
+        releases.push(Release {
+            tag: tag.to_string(),
+            date: date.date_naive(),
+            version,
+        });
+    }
+    Ok(releases)
+}
+
+/// Days from each patch release to the release before it on the same major.minor line.
+fn patch_intervals(finals: &[&Release]) -> Vec<i64> {
+    finals
+        .windows(2)
+        .filter(|pair| {
+            let (previous, current) = (pair[0].base(), pair[1].base());
+            previous.0 == current.0 && previous.1 == current.1 && previous.2 != current.2
+        })
+        .map(|pair| (pair[1].date - pair[0].date).num_days())
+        .collect()
+}
+
+fn average(values: impl ExactSizeIterator<Item = f64>) -> Option<f64> {
+    let count = values.len();
+    (count > 0).then(|| values.sum::<f64>() / count as f64)
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn text_report(report: &serde_json::Value) -> String {
+    let number = |value: &serde_json::Value| {
+        value
+            .as_f64()
+            .map_or_else(|| "-".to_string(), |value| value.to_string())
+    };
+    let mut out = String::new();
+    for (label, value) in [
+        ("releases", report["releases"].to_string()),
+        ("pre-releases", report["pre_releases"].to_string()),
+        (
+            "average days between patches",
+            number(&report["average_days_between_patches"]),
+        ),
+        (
+            "average candidates per release",
+            number(&report["average_candidates_per_release"]),
+        ),
+    ] {
+        out.push_str(&format!("{label:<32}{value}\n"));
+    }
+    if let Some(months) = report["releases_per_month"].as_object() {
+        out.push_str(&format!("\n{:<10}releases\n", "month"));
+        for (month, count) in months {
+            out.push_str(&format!("{month:<10}{count}\n"));
+        }
+    }
+    if let Some(candidates) = report["candidates"].as_array() {
+        out.push_str(&format!("\n{:<20}candidates\n", "release"));
+        for entry in candidates {
+            let tag = entry["release"].as_str().unwrap_or_default();
+            out.push_str(&format!("{tag:<20}{}\n", entry["candidates"]));
+        }
+    }
+    out
+}
+
+/// Release cadence computed from the version tags: releases per month, days between
+/// patch releases and pre-releases per final release.
+pub fn run(matches: &ArgMatches) -> Result<(), BumpError> {
+    if !is_git_repository() {
+        return Err(BumpError::LogicError("Not in a git repository".to_string()));
+    }
+    let template = load_bumpfile(matches)?.version()?;
+    let releases = releases(&template)?;
+    let finals: Vec<&Release> = releases.iter().filter(|r| r.is_final()).collect();
+
+    let mut per_month: BTreeMap<String, usize> = BTreeMap::new();
+    for release in &finals {
+        *per_month
+            .entry(release.date.format("%Y-%m").to_string())
+            .or_default() += 1;
+    }
+    let candidates: Vec<(&str, usize)> = finals
+        .iter()
+        .map(|release| {
+            let count = releases
+                .iter()
+                .filter(|r| !r.is_final() && r.base() == release.base())
+                .count();
+            (release.tag.as_str(), count)
+        })
+        .collect();
+    let intervals = patch_intervals(&finals);
+
+    let report = json!({
+        "releases": finals.len(),
+        "pre_releases": releases.len() - finals.len(),
+        "releases_per_month": per_month,
+        "average_days_between_patches":
+            average(intervals.iter().map(|&days| days as f64)).map(round),
+        "candidates": candidates
+            .iter()
+            .map(|(tag, count)| json!({ "release": tag, "candidates": count }))
+            .collect::<Vec<_>>(),
+        "average_candidates_per_release":
+            average(candidates.iter().map(|&(_, count)| count as f64)).map(round),
+    });
+
+    match matches
+        .get_one::<String>("format")
+        .map(String::as_str)
+        .unwrap_or("table")
+    {
+        "json" => println!(
+            "{}",
+            serde_json::to_string_pretty(&report)
+                .map_err(|e| BumpError::ParseError(format!("stats: {e}")))?
+        ),
+        _ => print!("{}", text_report(&report)),
+    }
+    Ok(())
+}
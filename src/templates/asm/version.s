@@ -0,0 +1,19 @@
+/** This file is generated by:
+ *  ____  __  __  __  __  ____ 
+ * (  _ \(  )(  )(  \/  )(  _ \
+ *  ) _ < )(__)(  )    (  )___/
+ * (____/(______)(_/\/\_)(__)  
+ *
+ * https://github.com/launchfirestorm/bump
+ */
+
+/* Place .version at a fixed flash address from the linker script, e.g.
+ * .version 0x08000200 : { KEEP(*(.version)) } > FLASH */
+    .section .version, "a"
+    .balign 4
+    .global __{symbol_prefix}version
+__{symbol_prefix}version:
+    .4byte {major}
+    .4byte {minor}
+    .4byte {patch}
+    .asciz "{version_string}"
@@ -0,0 +1,5 @@
+@rem This file is generated by bump
+@rem https://github.com/launchfirestorm/bump
+@set "{symbol_prefix}VERSION_STRING={version_string}"
+@set "{symbol_prefix}VERSION_FULL={full_string}"
+@set "{symbol_prefix}VERSION_TIMESTAMP={timestamp}"{version_code}{build_number}{git_info}
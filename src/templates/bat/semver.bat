@@ -0,0 +1,11 @@
+@rem This file is generated by bump
+@rem https://github.com/launchfirestorm/bump
+@set "{symbol_prefix}VERSION_PREFIX={prefix}"
+@set "{symbol_prefix}VERSION_MAJOR={major}"
+@set "{symbol_prefix}VERSION_MINOR={minor}"
+@set "{symbol_prefix}VERSION_PATCH={patch}"
+@set "{symbol_prefix}VERSION_PHASE={phase}"
+@set "{symbol_prefix}VERSION_CANDIDATE={candidate}"
+@set "{symbol_prefix}VERSION_STRING={version_string}"
+@set "{symbol_prefix}VERSION_FULL={full_string}"
+@set "{symbol_prefix}VERSION_TIMESTAMP={timestamp}"{version_code}{build_number}{git_info}
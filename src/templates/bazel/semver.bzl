@@ -0,0 +1,17 @@
+# This file is generated by:
+#  ____  __  __  __  __  ____ 
+# (  _ \(  )(  )(  \/  )(  _ \
+#  ) _ < )(__)(  )    (  )___/
+# (____/(______)(_/\/\_)(__)  
+#
+# https://github.com/launchfirestorm/bump
+
+{symbol_prefix}VERSION = "{bare_string}"
+{symbol_prefix}VERSION_PREFIX = "{prefix}"
+{symbol_prefix}VERSION_MAJOR = {major}
+{symbol_prefix}VERSION_MINOR = {minor}
+{symbol_prefix}VERSION_PATCH = {patch}
+{symbol_prefix}VERSION_PHASE = "{phase}"
+{symbol_prefix}VERSION_CANDIDATE = {candidate}
+{symbol_prefix}VERSION_STRING = "{version_string}"
+{symbol_prefix}VERSION_TIMESTAMP = "{timestamp}"{version_code}{build_number}{git_info}
@@ -7,10 +7,7 @@
  * https://github.com/launchfirestorm/bump
  */
 
-#ifndef BUMP_VERSION_H
-#define BUMP_VERSION_H
+{guard_open}
 
-#define VERSION_STRING "{version_string}"
-#define VERSION_TIMESTAMP "{timestamp}"
-
-#endif /* BUMP_VERSION_H */
+#define {symbol_prefix}VERSION_STRING "{version_string}"
+#define {symbol_prefix}VERSION_TIMESTAMP "{timestamp}"{version_code}{build_number}{git_info}{string_assert}{guard_close}
@@ -7,15 +7,17 @@
  * https://github.com/launchfirestorm/bump
  */
 
-#ifndef BUMP_VERSION_H
-#define BUMP_VERSION_H
+{guard_open}
 
-#define VERSION_PREFIX "{prefix}"
-#define VERSION_MAJOR {major}
-#define VERSION_MINOR {minor}
-#define VERSION_PATCH {patch}
-#define VERSION_PHASE "{phase}"
-#define VERSION_STRING "{version_string}"
-#define VERSION_TIMESTAMP "{timestamp}"
+#define {symbol_prefix}VERSION_PREFIX "{prefix}"
+#define {symbol_prefix}VERSION_MAJOR {major}
+#define {symbol_prefix}VERSION_MINOR {minor}
+#define {symbol_prefix}VERSION_PATCH {patch}
+#define {symbol_prefix}VERSION_PHASE "{phase}"
+#define {symbol_prefix}VERSION_STRING "{version_string}"
+#define {symbol_prefix}VERSION_TIMESTAMP "{timestamp}"{packed}{version_code}{build_number}{git_info}{string_assert}
 
-#endif /* BUMP_VERSION_H */
+/* (major << 16) | (minor << 8) | patch, for compile-time checks; bump gen refuses minor or patch above 255 */
+#define {symbol_prefix}VERSION_NUMBER (({symbol_prefix}VERSION_MAJOR << 16) | ({symbol_prefix}VERSION_MINOR << 8) | {symbol_prefix}VERSION_PATCH)
+#define {symbol_prefix}VERSION_AT_LEAST(major, minor, patch) \
+    ({symbol_prefix}VERSION_NUMBER >= (((major) << 16) | ((minor) << 8) | (patch))){guard_close}
@@ -8,7 +8,7 @@
  * https://github.com/launchfirestorm/bump
  */
 
-public static class Version {
-    public const string STRING = "{version_string}";
-    public const string TIMESTAMP = "{timestamp}";
+{namespace}public static class {class_name} {
+    public const string {symbol_prefix}STRING = "{version_string}";
+    public const string {symbol_prefix}TIMESTAMP = "{timestamp}";{build_number}{git_info}
 }
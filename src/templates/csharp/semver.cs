@@ -8,12 +8,12 @@
  * https://github.com/launchfirestorm/bump
  */
 
-public static class Version {
-    public const string PREFIX = "{prefix}";
-    public const int MAJOR = {major};
-    public const int MINOR = {minor};
-    public const int PATCH = {patch};
-    public const string PHASE = "{phase}";
-    public const string STRING = "{version_string}";
-    public const string TIMESTAMP = "{timestamp}";
+{namespace}public static class {class_name} {
+    public const string {symbol_prefix}PREFIX = "{prefix}";
+    public const int {symbol_prefix}MAJOR = {major};
+    public const int {symbol_prefix}MINOR = {minor};
+    public const int {symbol_prefix}PATCH = {patch};
+    public const string {symbol_prefix}PHASE = "{phase}";
+    public const string {symbol_prefix}STRING = "{version_string}";
+    public const string {symbol_prefix}TIMESTAMP = "{timestamp}";{build_number}{git_info}
 }
@@ -6,9 +6,9 @@
 //
 // https://github.com/launchfirestorm/bump
 
-package version
+{package}
 
 const (
-	STRING = "{version_string}"
-    TIMESTAMP = "{timestamp}"
-)
+	{symbol_prefix}STRING = "{version_string}"
+    {symbol_prefix}TIMESTAMP = "{timestamp}"
+){build_number}{git_info}
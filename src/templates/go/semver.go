@@ -6,14 +6,14 @@
 //
 // https://github.com/launchfirestorm/bump
 
-package version
+{package}
 
 const (
-	PREFIX    = "{prefix}"
-	MAJOR     = {major}
-	MINOR     = {minor}
-	PATCH     = {patch}
-	PHASE     = "{phase}"
-	STRING    = "{version_string}"
-    TIMESTAMP = "{timestamp}"
-)
+	{symbol_prefix}PREFIX    = "{prefix}"
+	{symbol_prefix}MAJOR     = {major}
+	{symbol_prefix}MINOR     = {minor}
+	{symbol_prefix}PATCH     = {patch}
+	{symbol_prefix}PHASE     = "{phase}"
+	{symbol_prefix}STRING    = "{version_string}"
+    {symbol_prefix}TIMESTAMP = "{timestamp}"
+){build_number}{git_info}
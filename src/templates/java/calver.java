@@ -8,7 +8,7 @@
  * https://github.com/launchfirestorm/bump
  */
 
-public class Version {
-    public static final String STRING = "{version_string}";
-    public static final String TIMESTAMP = "{timestamp}";
+{package}public class {class_name} {
+    public static final String {symbol_prefix}STRING = "{version_string}";
+    public static final String {symbol_prefix}TIMESTAMP = "{timestamp}";{version_code}{build_number}{git_info}
 }
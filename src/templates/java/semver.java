@@ -8,12 +8,12 @@
  * https://github.com/launchfirestorm/bump
  */
 
-public class Version {
-    public static final String PREFIX = "{prefix}";
-    public static final int MAJOR = {major};
-    public static final int MINOR = {minor};
-    public static final int PATCH = {patch};
-    public static final String PHASE = "{phase}";
-    public static final String STRING = "{version_string}";
-    public static final String TIMESTAMP = "{timestamp}";
+{package}public class {class_name} {
+    public static final String {symbol_prefix}PREFIX = "{prefix}";
+    public static final int {symbol_prefix}MAJOR = {major};
+    public static final int {symbol_prefix}MINOR = {minor};
+    public static final int {symbol_prefix}PATCH = {patch};
+    public static final String {symbol_prefix}PHASE = "{phase}";
+    public static final String {symbol_prefix}STRING = "{version_string}";
+    public static final String {symbol_prefix}TIMESTAMP = "{timestamp}";{version_code}{build_number}{git_info}
 }
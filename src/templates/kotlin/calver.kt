@@ -0,0 +1,14 @@
+/**
+ * This file is generated by:
+ *  ____  __  __  __  __  ____ 
+ * (  _ \(  )(  )(  \/  )(  _ \
+ *  ) _ < )(__)(  )    (  )___/
+ * (____/(______)(_/\/\_)(__)  
+ *
+ * https://github.com/launchfirestorm/bump
+ */
+
+{package}object {class_name} {
+    const val {symbol_prefix}STRING = "{version_string}"
+    const val {symbol_prefix}TIMESTAMP = "{timestamp}"{version_code}{build_number}{git_info}
+}
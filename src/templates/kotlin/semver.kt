@@ -0,0 +1,19 @@
+/**
+ * This file is generated by:
+ *  ____  __  __  __  __  ____ 
+ * (  _ \(  )(  )(  \/  )(  _ \
+ *  ) _ < )(__)(  )    (  )___/
+ * (____/(______)(_/\/\_)(__)  
+ *
+ * https://github.com/launchfirestorm/bump
+ */
+
+{package}object {class_name} {
+    const val {symbol_prefix}PREFIX = "{prefix}"
+    const val {symbol_prefix}MAJOR = {major}
+    const val {symbol_prefix}MINOR = {minor}
+    const val {symbol_prefix}PATCH = {patch}
+    const val {symbol_prefix}PHASE = "{phase}"
+    const val {symbol_prefix}STRING = "{version_string}"
+    const val {symbol_prefix}TIMESTAMP = "{timestamp}"{version_code}{build_number}{git_info}
+}
@@ -0,0 +1,17 @@
+<?php
+
+/**
+ * This file is generated by:
+ *  ____  __  __  __  __  ____ 
+ * (  _ \(  )(  )(  \/  )(  _ \
+ *  ) _ < )(__)(  )    (  )___/
+ * (____/(______)(_/\/\_)(__)  
+ *
+ * https://github.com/launchfirestorm/bump
+ */
+
+{namespace}final class {class_name}
+{
+    public const {symbol_prefix}STRING = '{version_string}';
+    public const {symbol_prefix}TIMESTAMP = '{timestamp}';{version_code}{build_number}{git_info}
+}
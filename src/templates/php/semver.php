@@ -0,0 +1,22 @@
+<?php
+
+/**
+ * This file is generated by:
+ *  ____  __  __  __  __  ____ 
+ * (  _ \(  )(  )(  \/  )(  _ \
+ *  ) _ < )(__)(  )    (  )___/
+ * (____/(______)(_/\/\_)(__)  
+ *
+ * https://github.com/launchfirestorm/bump
+ */
+
+{namespace}final class {class_name}
+{
+    public const {symbol_prefix}PREFIX = '{prefix}';
+    public const {symbol_prefix}MAJOR = {major};
+    public const {symbol_prefix}MINOR = {minor};
+    public const {symbol_prefix}PATCH = {patch};
+    public const {symbol_prefix}PHASE = '{phase}';
+    public const {symbol_prefix}STRING = '{version_string}';
+    public const {symbol_prefix}TIMESTAMP = '{timestamp}';{version_code}{build_number}{git_info}
+}
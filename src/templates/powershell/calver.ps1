@@ -0,0 +1,5 @@
+# This file is generated by bump
+# https://github.com/launchfirestorm/bump
+$env:{symbol_prefix}VERSION_STRING = '{version_string}'
+$env:{symbol_prefix}VERSION_FULL = '{full_string}'
+$env:{symbol_prefix}VERSION_TIMESTAMP = '{timestamp}'{version_code}{build_number}{git_info}
@@ -0,0 +1,11 @@
+# This file is generated by bump
+# https://github.com/launchfirestorm/bump
+$env:{symbol_prefix}VERSION_PREFIX = '{prefix}'
+$env:{symbol_prefix}VERSION_MAJOR = '{major}'
+$env:{symbol_prefix}VERSION_MINOR = '{minor}'
+$env:{symbol_prefix}VERSION_PATCH = '{patch}'
+$env:{symbol_prefix}VERSION_PHASE = '{phase}'
+$env:{symbol_prefix}VERSION_CANDIDATE = '{candidate}'
+$env:{symbol_prefix}VERSION_STRING = '{version_string}'
+$env:{symbol_prefix}VERSION_FULL = '{full_string}'
+$env:{symbol_prefix}VERSION_TIMESTAMP = '{timestamp}'{version_code}{build_number}{git_info}
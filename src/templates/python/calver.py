@@ -7,5 +7,5 @@
  # https://github.com/launchfirestorm/bump
  #/
 
-VERSION_STRING = "{version_string}"
-VERSION_TIMESTAMP = "{timestamp}"
+{symbol_prefix}VERSION_STRING = "{version_string}"
+{symbol_prefix}VERSION_TIMESTAMP = "{timestamp}"{version_code}{build_number}{git_info}
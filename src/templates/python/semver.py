@@ -7,10 +7,10 @@
  # https://github.com/launchfirestorm/bump
  #/
 
-VERSION_PREFIX = "{prefix}"
-VERSION_MAJOR = {major}
-VERSION_MINOR = {minor}
-VERSION_PATCH = {patch}
-VERSION_PHASE = "{phase}"
-VERSION_STRING = "{version_string}"
-VERSION_TIMESTAMP = "{timestamp}"
+{symbol_prefix}VERSION_PREFIX = "{prefix}"
+{symbol_prefix}VERSION_MAJOR = {major}
+{symbol_prefix}VERSION_MINOR = {minor}
+{symbol_prefix}VERSION_PATCH = {patch}
+{symbol_prefix}VERSION_PHASE = "{phase}"
+{symbol_prefix}VERSION_STRING = "{version_string}"
+{symbol_prefix}VERSION_TIMESTAMP = "{timestamp}"{version_code}{build_number}{git_info}
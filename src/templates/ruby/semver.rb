@@ -0,0 +1,19 @@
+# frozen_string_literal: true
+
+# This file is generated by:
+#  ____  __  __  __  __  ____ 
+# (  _ \(  )(  )(  \/  )(  _ \
+#  ) _ < )(__)(  )    (  )___/
+# (____/(______)(_/\/\_)(__)  
+#
+# https://github.com/launchfirestorm/bump
+
+module {class_name}
+  {symbol_prefix}PREFIX = "{prefix}"
+  {symbol_prefix}MAJOR = {major}
+  {symbol_prefix}MINOR = {minor}
+  {symbol_prefix}PATCH = {patch}
+  {symbol_prefix}PHASE = "{phase}"
+  {symbol_prefix}STRING = "{version_string}"
+  {symbol_prefix}TIMESTAMP = "{timestamp}"{version_code}{build_number}{git_info}
+end
@@ -0,0 +1,10 @@
+// This file is generated by:
+//  ____  __  __  __  __  ____ 
+// (  _ \(  )(  )(  \/  )(  _ \
+//  ) _ < )(__)(  )    (  )___/
+// (____/(______)(_/\/\_)(__)  
+//
+// https://github.com/launchfirestorm/bump
+
+pub const {symbol_prefix}VERSION_STRING: &str = "{version_string}";
+pub const {symbol_prefix}VERSION_TIMESTAMP: &str = "{timestamp}";{version_code}{build_number}{git_info}
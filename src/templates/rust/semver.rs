@@ -0,0 +1,16 @@
+// This file is generated by:
+//  ____  __  __  __  __  ____ 
+// (  _ \(  )(  )(  \/  )(  _ \
+//  ) _ < )(__)(  )    (  )___/
+// (____/(______)(_/\/\_)(__)  
+//
+// https://github.com/launchfirestorm/bump
+
+pub const {symbol_prefix}VERSION_PREFIX: &str = "{prefix}";
+pub const {symbol_prefix}VERSION_MAJOR: u32 = {major};
+pub const {symbol_prefix}VERSION_MINOR: u32 = {minor};
+pub const {symbol_prefix}VERSION_PATCH: u32 = {patch};
+pub const {symbol_prefix}VERSION_PHASE: &str = "{phase}";
+pub const {symbol_prefix}VERSION_CANDIDATE: u32 = {candidate};
+pub const {symbol_prefix}VERSION_STRING: &str = "{version_string}";
+pub const {symbol_prefix}VERSION_TIMESTAMP: &str = "{timestamp}";{version_code}{build_number}{git_info}
@@ -0,0 +1,11 @@
+# This file is generated by bump
+# https://github.com/launchfirestorm/bump
+export {symbol_prefix}VERSION_PREFIX='{prefix}'
+export {symbol_prefix}VERSION_MAJOR={major}
+export {symbol_prefix}VERSION_MINOR={minor}
+export {symbol_prefix}VERSION_PATCH={patch}
+export {symbol_prefix}VERSION_PHASE='{phase}'
+export {symbol_prefix}VERSION_CANDIDATE={candidate}
+export {symbol_prefix}VERSION_STRING='{version_string}'
+export {symbol_prefix}VERSION_FULL='{full_string}'
+export {symbol_prefix}VERSION_TIMESTAMP='{timestamp}'{version_code}{build_number}{git_info}
@@ -0,0 +1,17 @@
+// This file is generated by:
+//  ____  __  __  __  __  ____ 
+// (  _ \(  )(  )(  \/  )(  _ \
+//  ) _ < )(__)(  )    (  )___/
+// (____/(______)(_/\/\_)(__)  
+//
+// https://github.com/launchfirestorm/bump
+
+public enum {class_name} {
+    public static let {symbol_prefix}prefix = "{prefix}"
+    public static let {symbol_prefix}major = {major}
+    public static let {symbol_prefix}minor = {minor}
+    public static let {symbol_prefix}patch = {patch}
+    public static let {symbol_prefix}phase = "{phase}"
+    public static let {symbol_prefix}string = "{version_string}"
+    public static let {symbol_prefix}timestamp = "{timestamp}"{version_code}{build_number}{git_info}
+}
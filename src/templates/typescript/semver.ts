@@ -0,0 +1,20 @@
+// This file is generated by:
+//  ____  __  __  __  __  ____ 
+// (  _ \(  )(  )(  \/  )(  _ \
+//  ) _ < )(__)(  )    (  )___/
+// (____/(______)(_/\/\_)(__)  
+//
+// https://github.com/launchfirestorm/bump
+
+{export}const {symbol_prefix}VERSION = Object.freeze({
+  prefix: "{prefix}",
+  major: {major},
+  minor: {minor},
+  patch: {patch},
+  phase: "{phase}",
+  candidate: {candidate},
+  string: "{version_string}",
+  timestamp: "{timestamp}",{version_code}{build_number}{git_info}
+} as const);
+
+{exports}
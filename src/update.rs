@@ -1,12 +1,20 @@
 use crate::{
-    bump::{BumpError, load_bumpfile, resolve_path},
-    print::{self, PrintOptions},
-    version::Version,
+    artifact,
+    bump::{BumpError, guard_bumpfile, load_bumpfile, resolve_path, write_error},
+    history, lang,
+    pep440::Pep440,
+    plugin,
+    print::{self, PrintOptions, SafeCharset, glob_matches},
+    version::{HelmField, RcBuild, Version, VersionMode},
+    version_code, wasm,
 };
 use clap::ArgMatches;
 use std::fs;
-use std::path::Path;
-use toml_edit::{DocumentMut, value};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+use toml_edit::{DocumentMut, TableLike, value};
 
 fn load_toml(path: &Path) -> Result<DocumentMut, BumpError> {
     let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
@@ -16,7 +24,104 @@ fn load_toml(path: &Path) -> Result<DocumentMut, BumpError> {
 }
 
 fn save_toml(path: &Path, doc: &DocumentMut) -> Result<(), BumpError> {
-    fs::write(path, doc.to_string()).map_err(BumpError::IoError)
+    write_file(path, doc.to_string())
+}
+
+/// Files written inside [`transaction`], each with its content before the first write
+/// (`None` when it did not exist yet); `None` outside a transaction.
+type Journal = Option<Vec<(PathBuf, Option<Vec<u8>>)>>;
+
+static JOURNAL: Mutex<Journal> = Mutex::new(None);
+
+fn journal() -> MutexGuard<'static, Journal> {
+    JOURNAL.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Stage `contents` in a temporary file next to `target`, then rename it into place so
+/// the target is never left half-written. Symlinks are followed and permissions kept.
+fn replace_file(target: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let staged = target.with_file_name(format!(".{name}.bump-staged"));
+    let result = fs::write(&staged, contents)
+        .and_then(|()| match fs::metadata(target) {
+            Ok(metadata) => fs::set_permissions(&staged, metadata.permissions()),
+            Err(_) => Ok(()),
+        })
+        .and_then(|()| fs::rename(&staged, target));
+    if result.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+    result
+}
+
+/// Write an updated file, recording its previous content when inside a transaction.
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), BumpError> {
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if let Some(written) = journal().as_mut()
+        && !written.iter().any(|(file, _)| *file == target)
+    {
+        written.push((target.clone(), fs::read(&target).ok()));
+    }
+    replace_file(&target, contents.as_ref()).map_err(|err| write_error(path, err))
+}
+
+/// Rename an updated file, refusing to replace an existing one. Inside a transaction
+/// the rename is journaled as a write of the old path and a creation of the new one.
+fn rename_file(from: &Path, to: &Path) -> Result<(), BumpError> {
+    if to.exists() {
+        return Err(BumpError::LogicError(format!(
+            "cannot rename {} to {}: the target already exists",
+            from.display(),
+            to.display()
+        )));
+    }
+    let source = fs::canonicalize(from).map_err(BumpError::IoError)?;
+    let target = match to.file_name() {
+        Some(name) => source.with_file_name(name),
+        None => to.to_path_buf(),
+    };
+    if let Some(written) = journal().as_mut() {
+        if !written.iter().any(|(file, _)| *file == source) {
+            written.push((
+                source.clone(),
+                Some(fs::read(&source).map_err(BumpError::IoError)?),
+            ));
+        }
+        written.push((target.clone(), None));
+    }
+    fs::rename(&source, &target).map_err(|err| write_error(to, err))
+}
+
+/// Run `update`, and when it fails restore every file it wrote, so updating several
+/// files never leaves the tree half-bumped.
+pub fn transaction(update: impl FnOnce() -> Result<(), BumpError>) -> Result<(), BumpError> {
+    *journal() = Some(Vec::new());
+    let result = update();
+    let written = journal().take().unwrap_or_default();
+    if result.is_err() && !written.is_empty() {
+        for (path, original) in &written {
+            let restored = match original {
+                Some(content) => replace_file(path, content),
+                None => fs::remove_file(path),
+            };
+            if let Err(err) = restored {
+                eprintln!("could not restore {}: {err}", path.display());
+            }
+        }
+        eprintln!("rolled back {} updated file(s)", written.len());
+    }
+    result
+}
+
+/// Replace the quoted value of a `key = "value"` line, keeping everything else byte-for-byte.
+fn replace_quoted_value(line: &str, value_str: &str) -> Option<String> {
+    let (_, rhs) = line.split_once('=')?;
+    let open = line.len() - rhs.len() + rhs.find('"')?;
+    let close = open + 1 + line[open + 1..].find('"')?;
+    Some(format!("{}{value_str}{}", &line[..=open], &line[close..]))
 }
 
 fn set_toml_field(
@@ -34,7 +139,251 @@ fn set_toml_field(
     Ok(())
 }
 
-/// Update a file with the version from the bumpfile
+/// Directories never descended into when `bump update` is given a directory.
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+
+/// Whether `update_file` knows how to update `path`.
+fn is_supported(version: &Version, path: &Path) -> bool {
+    if wasm::target_for(version, path).is_some() {
+        return true;
+    }
+    match path.file_name().and_then(|name| name.to_str()) {
+        // Virtual workspace manifests have no [package] version to set; unparsable
+        // manifests are kept so the failure is reported.
+        Some("Cargo.toml") => load_toml(path).map_or(true, |doc| doc.contains_key("package")),
+        // Only setuptools configs declaring the version; setup.cfg also holds tool settings.
+        Some("setup.cfg") => fs::read_to_string(path).is_ok_and(|content| {
+            let mut in_metadata = false;
+            content.lines().any(|line| {
+                if line.trim().starts_with('[') {
+                    in_metadata = line.trim() == "[metadata]";
+                }
+                in_metadata && setup_cfg_version(line).is_some()
+            })
+        }),
+        // Tool-only configs (black, ruff) have no package version to set.
+        Some("pyproject.toml") => load_toml(path).map_or(true, |doc| {
+            doc.contains_key("project")
+                || doc
+                    .get("tool")
+                    .is_some_and(|tool| tool.get("poetry").is_some())
+        }),
+        Some(
+            "ProjectSettings.asset"
+            | "DefaultGame.ini"
+            | "package.json"
+            | "Chart.yaml"
+            | "electron-builder.json"
+            | "electron-builder.yml"
+            | "electron-builder.yaml",
+        ) => true,
+        // Only module build files declare versionName; settings and root builds do not.
+        // Subdirectory CMakeLists.txt files usually have no versioned project() call.
+        Some("CMakeLists.txt") => {
+            fs::read_to_string(path).is_ok_and(|content| cmake_project_version(&content).is_some())
+        }
+        Some("build.gradle" | "build.gradle.kts") => {
+            fs::read_to_string(path).is_ok_and(|content| {
+                content.contains("versionName")
+                    || content
+                        .lines()
+                        .any(|line| gradle_project_version(line).is_some())
+            })
+        }
+        Some("gradle.properties") => fs::read_to_string(path).is_ok_and(|content| {
+            content
+                .lines()
+                .any(|line| gradle_properties_version(line).is_some())
+        }),
+        // Dockerfiles without a literal version label, such as build stages that take it
+        // from a build arg, and Compose files without an image to retag are not ours.
+        Some(name) if is_dockerfile(name) => fs::read_to_string(path).is_ok_and(|content| {
+            content
+                .lines()
+                .any(|line| !oci_version_labels(line).is_empty())
+        }),
+        Some(name) if is_compose_file(name) => {
+            let images = version
+                .docker
+                .as_ref()
+                .map(|docker| docker.images.as_slice())
+                .unwrap_or_default();
+            fs::read_to_string(path).is_ok_and(|content| {
+                content.lines().any(|line| {
+                    compose_image(line).is_some_and(|(repository, _)| {
+                        let name = &line[repository];
+                        images.iter().any(|pattern| glob_matches(pattern, name))
+                    })
+                })
+            })
+        }
+        // Modules inheriting the parent's version have nothing of their own to set.
+        Some("pom.xml") => {
+            fs::read_to_string(path).is_ok_and(|content| pom_project_version(&content).is_some())
+        }
+        // Include files are shared by many recipes and `name_git.bb` recipes usually take
+        // PV from one; only files setting PV or named after their version are ours.
+        Some(name) if name.ends_with(".bb") || name.ends_with(".inc") => {
+            versioned_recipe_name(path).is_some()
+                || fs::read_to_string(path)
+                    .is_ok_and(|content| content.lines().any(|line| bitbake_pv(line).is_some()))
+        }
+        // Libraries usually leave the version to their git tags.
+        Some("composer.json") => fs::read_to_string(path).is_ok_and(|content| {
+            json_string_values(&content)
+                .iter()
+                .any(|(key, _)| key == &["version"])
+        }),
+        Some("pubspec.yaml") => true,
+        // Projects reading the version from a VERSION file have nothing literal to set.
+        Some("mix.exs") => fs::read_to_string(path).is_ok_and(|content| {
+            content.lines().any(|line| {
+                quoted_after(line, "@version").is_some() || quoted_after(line, "version:").is_some()
+            })
+        }),
+        Some(name) if name.ends_with(".gemspec") => fs::read_to_string(path)
+            .is_ok_and(|content| content.lines().any(|line| gemspec_version(line).is_some())),
+        // Docs are only rewritten with [markdown]; changelogs keep their old versions.
+        Some(name) if name.ends_with(".md") => {
+            version.markdown.is_some()
+                && !name.to_ascii_uppercase().starts_with("CHANGELOG")
+                && fs::read_to_string(path).is_ok_and(|content| {
+                    markdown_versions(version, &content).is_ok_and(|found| !found.is_empty())
+                })
+        }
+        Some("changelog") => is_debian_changelog(path),
+        // Specs taking Version: from a macro are left to whatever defines it.
+        Some(name) if name.ends_with(".spec") => fs::read_to_string(path).is_ok_and(|content| {
+            content
+                .lines()
+                .any(|line| spec_tag(line, "Version").is_some())
+        }),
+        // Most .tf files configure infrastructure and declare no version of their own.
+        Some(name) if name.ends_with(".tf") => {
+            fs::read_to_string(path).is_ok_and(|content| rewrite_terraform(&content, "").1 > 0)
+        }
+        // Resource scripts holding only dialogs, menus or icons have nothing to update.
+        Some(name) if name.ends_with(".rc") => fs::read_to_string(path)
+            .is_ok_and(|content| content.split_whitespace().any(|word| word == "VERSIONINFO")),
+        _ => false,
+    }
+}
+
+fn update_file(version: &Version, path_str: &str, file_path: &Path) -> Result<(), BumpError> {
+    let file_name = file_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+
+    if let Some(target) = wasm::target_for(version, Path::new(path_str)) {
+        return wasm_update(version, file_path, &target.module);
+    }
+
+    match file_name {
+        "Cargo.toml" => cargo_toml(version, file_path),
+        "pyproject.toml" => pyproject_toml(version, file_path),
+        "setup.cfg" => setup_cfg(version, file_path),
+        "ProjectSettings.asset" => unity_project_settings(version, file_path),
+        "DefaultGame.ini" => unreal_default_game(version, file_path),
+        "build.gradle" | "build.gradle.kts" => gradle(version, file_path),
+        "gradle.properties" => gradle_properties(version, file_path),
+        "pom.xml" => pom_xml(version, file_path),
+        "CMakeLists.txt" => cmake_lists(version, file_path),
+        "package.json" => package_json(version, file_path),
+        "Chart.yaml" => helm_chart(version, file_path),
+        "pubspec.yaml" => pubspec_yaml(version, file_path),
+        "mix.exs" => mix_exs(version, file_path),
+        "composer.json" => composer_json(version, file_path),
+        name if name.ends_with(".gemspec") => gemspec(version, file_path),
+        "electron-builder.json" | "electron-builder.yml" | "electron-builder.yaml" => {
+            electron_builder(version, file_path)
+        }
+        name if is_dockerfile(name) => dockerfile(version, file_path),
+        name if is_compose_file(name) => compose_file(version, file_path),
+        name if name.ends_with(".md") => markdown(version, file_path),
+        "changelog" if is_debian_changelog(file_path) => debian_changelog(version, file_path),
+        name if name.ends_with(".spec") => rpm_spec(version, file_path),
+        name if name.ends_with(".tf") => terraform(version, file_path),
+        name if name.ends_with(".rc") => windows_rc(version, file_path),
+        name if name.ends_with(".bb") || name.ends_with(".inc") => bitbake(version, file_path),
+        _ => Err(BumpError::LogicError(format!(
+            "Unsupported file type: {path_str}"
+        ))),
+    }
+}
+
+/// Collect every supported file under `dir`, skipping hidden and build directories.
+fn collect_files(version: &Version, dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), BumpError> {
+    let mut entries = fs::read_dir(dir)
+        .map_err(BumpError::IoError)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(BumpError::IoError)?;
+    entries.sort_by_key(fs::DirEntry::file_name);
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let file_type = entry.file_type().map_err(BumpError::IoError)?;
+        if file_type.is_dir() {
+            if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                collect_files(version, &path, files)?;
+            }
+        } else if is_supported(version, &path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Update every supported file under `dir` in parallel, then print a summary.
+fn update_tree(version: &Version, bumpfile: &Path, dir: &Path) -> Result<(), BumpError> {
+    let mut files = Vec::new();
+    collect_files(version, dir, &mut files)?;
+    files.retain(|file| guard_bumpfile(bumpfile, file).is_ok());
+
+    let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_size = files.len().div_ceil(workers).max(1);
+    let failures: Vec<(PathBuf, BumpError)> = thread::scope(|scope| {
+        let handles: Vec<_> = files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter_map(|file| {
+                            update_file(version, &file.to_string_lossy(), file)
+                                .err()
+                                .map(|err| (file.clone(), err))
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("update worker panicked"))
+            .collect()
+    });
+
+    // A failure rolls back every file the transaction wrote, so only a clean run has
+    // anything to report as updated.
+    if !failures.is_empty() {
+        for (file, err) in &failures {
+            eprintln!("{}: {err}", file.display());
+        }
+        return Err(BumpError::LogicError(format!(
+            "{} of {} files under {} failed, no files changed",
+            failures.len(),
+            files.len(),
+            dir.display()
+        )));
+    }
+    println!("updated {} files under {}", files.len(), dir.display());
+    Ok(())
+}
+
+/// Update a file with the version from the bumpfile, or every known file under a directory.
+/// With `--also`, every target is updated or, on any failure, none is.
 pub fn modify_file(matches: &ArgMatches) -> Result<(), BumpError> {
     let bumpfile = load_bumpfile(matches)?;
     let version = bumpfile.version()?;
@@ -44,15 +393,103 @@ pub fn modify_file(matches: &ArgMatches) -> Result<(), BumpError> {
             "path not provided",
         ))
     })?;
+    let targets: Vec<&String> = std::iter::once(path_str)
+        .chain(matches.get_many::<String>("also").into_iter().flatten())
+        .collect();
+    transaction(|| {
+        targets
+            .iter()
+            .try_for_each(|target| update_target(matches, &version, bumpfile.path(), target))
+    })
+}
+
+fn update_target(
+    matches: &ArgMatches,
+    version: &Version,
+    bumpfile: &Path,
+    path_str: &str,
+) -> Result<(), BumpError> {
     let file_path = resolve_path(path_str);
+    if file_path.is_dir() {
+        return update_tree(version, bumpfile, Path::new(path_str));
+    }
+    guard_bumpfile(bumpfile, &file_path)?;
+    if matches.get_flag("workspaces") {
+        return match file_path.file_name().and_then(|name| name.to_str()) {
+            Some("package.json") => {
+                package_json(version, &file_path)?;
+                package_json_workspaces(version, &file_path)
+            }
+            Some("Cargo.toml") => {
+                cargo_workspace(version, &file_path, matches.get_flag("path-deps"))
+            }
+            _ => Err(BumpError::LogicError(format!(
+                "--workspace applies to a package.json or Cargo.toml, not {path_str}"
+            ))),
+        };
+    }
+    update_file(version, path_str, &file_path)
+}
 
-    match path_str.as_str() {
-        "Cargo.toml" => cargo_toml(&version, &file_path),
-        "pyproject.toml" => pyproject_toml(&version, &file_path),
-        _ => Err(BumpError::LogicError(format!(
-            "Unsupported file type: {path_str}"
-        ))),
+/// The `[[update.file]]` rewrites for a bump from `previous` to `version`: the new
+/// content of each file, computed before anything is written so a rule whose `search`
+/// no longer matches fails the bump. Paths are relative to the bumpfile.
+pub fn configured_files(
+    previous: &Version,
+    version: &Version,
+    bumpfile: &Path,
+) -> Result<Vec<(PathBuf, String)>, BumpError> {
+    let Some(update) = &version.update else {
+        return Ok(Vec::new());
+    };
+    let dir = bumpfile.parent().unwrap_or(Path::new("."));
+    let before = artifact::builtins(previous)?;
+    let after = artifact::builtins(version)?;
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+    for rule in &update.file {
+        let path = dir.join(&rule.path);
+        guard_bumpfile(bumpfile, &path)?;
+        let search = artifact::render(&rule.search, &before)?;
+        let replace = artifact::render(&rule.replace, &after)?;
+        // Later rules for the same file apply to the content earlier ones produced.
+        let index = files.iter().position(|(file, _)| *file == path);
+        let content = match index {
+            Some(index) => files[index].1.clone(),
+            None => fs::read_to_string(&path).map_err(BumpError::IoError)?,
+        };
+        if search.is_empty() || !content.contains(&search) {
+            return Err(BumpError::LogicError(format!(
+                "[[update.file]] {}: '{search}' not found; was the file edited by hand?",
+                path.display()
+            )));
+        }
+        let content = content.replace(&search, &replace);
+        match index {
+            Some(index) => files[index].1 = content,
+            None => files.push((path, content)),
+        }
+    }
+    Ok(files)
+}
+
+/// Write the rewrites from [`configured_files`]; run inside a [`transaction`] with the
+/// bumpfile save so a failed write restores the files already written.
+pub fn write_configured_files(files: &[(PathBuf, String)]) -> Result<(), BumpError> {
+    for (path, content) in files {
+        write_file(path, content)?;
+        println!("{} updated", path.display());
     }
+    Ok(())
+}
+
+fn wasm_update(version: &Version, path: &Path, module: &str) -> Result<(), BumpError> {
+    let mut context = plugin::version_context(version)?;
+    context["file"] = serde_json::json!(path);
+    context["content"] = serde_json::json!(fs::read_to_string(path).map_err(BumpError::IoError)?);
+    let content = wasm::call(Path::new(module), "update", &context.to_string())?;
+    write_file(path, content)?;
+    println!("{} updated by wasm plugin {module}", path.display());
+    Ok(())
 }
 
 pub fn cargo_toml(version: &Version, path: &Path) -> Result<(), BumpError> {
@@ -68,31 +505,1958 @@ pub fn cargo_toml(version: &Version, path: &Path) -> Result<(), BumpError> {
     Ok(())
 }
 
+/// The member manifests of the Cargo workspace rooted at `path`: `[workspace] members`
+/// patterns, minus `exclude`.
+fn cargo_members(path: &Path, doc: &DocumentMut) -> Result<Vec<PathBuf>, BumpError> {
+    let strings = |key: &str| -> Vec<String> {
+        doc.get("workspace")
+            .and_then(|workspace| workspace.get(key))
+            .and_then(|item| item.as_array())
+            .map(|array| {
+                array
+                    .iter()
+                    .filter_map(|value| value.as_str())
+                    .map(|value| {
+                        value
+                            .trim_start_matches("./")
+                            .trim_end_matches('/')
+                            .to_string()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let base = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let excluded = strings("exclude");
+    let mut manifests = Vec::new();
+    for pattern in strings("members") {
+        for dir in expand_workspace_pattern(base, &pattern)? {
+            let relative = dir
+                .strip_prefix(base)
+                .unwrap_or(&dir)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let manifest = dir.join("Cargo.toml");
+            if !excluded
+                .iter()
+                .any(|exclude| glob_matches(exclude, &relative))
+                && manifest.is_file()
+                && !manifests.contains(&manifest)
+            {
+                manifests.push(manifest);
+            }
+        }
+    }
+    manifests.sort();
+    Ok(manifests)
+}
+
+/// Whether `[package] version` is a literal, not `version.workspace = true`.
+fn has_own_version(doc: &DocumentMut) -> bool {
+    doc.get("package")
+        .and_then(|package| package.get("version"))
+        .is_some_and(|version| version.is_str())
+}
+
+/// The dependency tables of a manifest: `[dependencies]`, `[dev-dependencies]`,
+/// `[build-dependencies]`, their `[target.*]` variants and `[workspace.dependencies]`.
+fn dependency_tables(doc: &mut DocumentMut) -> Vec<&mut dyn TableLike> {
+    const KINDS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+    let mut tables: Vec<&mut dyn TableLike> = Vec::new();
+    for (key, item) in doc.as_table_mut().iter_mut() {
+        match key.get() {
+            kind if KINDS.contains(&kind) => tables.extend(item.as_table_like_mut()),
+            "workspace" => tables.extend(
+                item.get_mut("dependencies")
+                    .and_then(|item| item.as_table_like_mut()),
+            ),
+            "target" => {
+                if let Some(targets) = item.as_table_like_mut() {
+                    for (_, target) in targets.iter_mut() {
+                        if let Some(target) = target.as_table_like_mut() {
+                            for (kind, table) in target.iter_mut() {
+                                if KINDS.contains(&kind.get()) {
+                                    tables.extend(table.as_table_like_mut());
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    tables
+}
+
+/// Set the version requirement of path dependencies on workspace crates (`names`),
+/// keeping an `=`, `^` or `~` operator. Returns how many were set.
+fn set_path_dependency_versions(doc: &mut DocumentMut, names: &[String], v_str: &str) -> usize {
+    let mut updated = 0;
+    for table in dependency_tables(doc) {
+        for (key, dependency) in table.iter_mut() {
+            let Some(dependency) = dependency.as_table_like_mut() else {
+                continue;
+            };
+            let name = dependency
+                .get("package")
+                .and_then(|item| item.as_str())
+                .unwrap_or(key.get())
+                .to_string();
+            if !dependency.contains_key("path") || !names.contains(&name) {
+                continue;
+            }
+            let Some(current) = dependency.get("version").and_then(|item| item.as_str()) else {
+                continue;
+            };
+            let operator: String = current
+                .chars()
+                .take_while(|c| "=^~ ".contains(*c))
+                .collect();
+            set_version(dependency, &format!("{operator}{v_str}"));
+            updated += 1;
+        }
+    }
+    updated
+}
+
+/// `bump update Cargo.toml --workspace`: set `[workspace.package] version`, the root
+/// package's version, and that of every member that does not inherit it; with
+/// `path_deps`, also the requirements of path dependencies between workspace crates.
+pub fn cargo_workspace(version: &Version, path: &Path, path_deps: bool) -> Result<(), BumpError> {
+    let mut root = load_toml(path)?;
+    if !root.contains_key("workspace") {
+        return Err(BumpError::ParseError(format!(
+            "no [workspace] table in {}",
+            path.display()
+        )));
+    }
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let members = cargo_members(path, &root)?;
+
+    let mut manifests = vec![(path.to_path_buf(), root.clone())];
+    for member in &members {
+        manifests.push((member.clone(), load_toml(member)?));
+    }
+    let names: Vec<String> = manifests
+        .iter()
+        .filter_map(|(_, doc)| {
+            doc.get("package")?
+                .get("name")?
+                .as_str()
+                .map(str::to_string)
+        })
+        .collect();
+
+    if let Some(package) = root
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("package"))
+        .and_then(|item| item.as_table_like_mut())
+        .filter(|package| package.contains_key("version"))
+    {
+        set_version(package, &v_str);
+        println!(
+            "{} [workspace.package] updated to version {v_str}",
+            path.display()
+        );
+    }
+    manifests[0].1 = root;
+
+    for (manifest, doc) in &mut manifests {
+        let own = has_own_version(doc);
+        if own {
+            set_toml_field(doc, "package", "version", &v_str)?;
+        }
+        let dependencies = if path_deps {
+            set_path_dependency_versions(doc, &names, &v_str)
+        } else {
+            0
+        };
+        if manifest.as_path() == path || own || dependencies > 0 {
+            save_toml(manifest, doc)?;
+        }
+        match (own, dependencies) {
+            (true, 0) => println!("{} updated to version {v_str}", manifest.display()),
+            (true, n) => println!(
+                "{} updated to version {v_str} ({n} path dependencies)",
+                manifest.display()
+            ),
+            (false, 0) => {}
+            (false, n) => println!("{} updated {n} path dependencies", manifest.display()),
+        }
+    }
+    Ok(())
+}
+
+/// The version in PEP 440's normalized form, without the prefix: `1.2.3-rc.1` becomes
+/// `1.2.3rc1` and `1.2.3-dev.4` becomes `1.2.3.dev4`.
+fn pep440_version(version: &Version) -> Result<String, BumpError> {
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let normalized = Pep440::parse(&v_str)
+        .map_err(|_| {
+            BumpError::LogicError(format!(
+                "'{v_str}' has no PEP 440 form; Python packages take alpha, beta, rc, \
+                 post and dev phases"
+            ))
+        })?
+        .to_string();
+    if normalized != v_str {
+        println!("normalized {v_str} to {normalized} for PEP 440");
+    }
+    Ok(normalized)
+}
+
+/// Set `version` in `table`, keeping the whitespace and comment around an existing value.
+fn set_version(table: &mut dyn TableLike, v_str: &str) {
+    match table
+        .get_mut("version")
+        .and_then(|item| item.as_value_mut())
+    {
+        Some(existing) => {
+            let decor = existing.decor().clone();
+            *existing = v_str.into();
+            *existing.decor_mut() = decor;
+        }
+        None => {
+            table.insert("version", value(v_str));
+        }
+    }
+}
+
+/// Set `[project] version` (PEP 621, unless listed in `dynamic`) and
+/// `[tool.poetry] version`, whichever the file has.
 pub fn pyproject_toml(version: &Version, path: &Path) -> Result<(), BumpError> {
     let mut doc = load_toml(path)?;
+    let v_str = pep440_version(version)?;
 
-    // https://packaging.python.org/en/latest/version.html#public-version-identifiers
-    let yellow = "\x1b[33m";
-    let cyan = "\x1b[36m";
-    let purple = "\x1b[35m";
-    let reset = "\x1b[0m";
+    let mut updated = Vec::new();
+    if let Some(project) = doc
+        .get_mut("project")
+        .and_then(|item| item.as_table_like_mut())
+    {
+        let dynamic = project
+            .get("dynamic")
+            .and_then(|item| item.as_array())
+            .is_some_and(|dynamic| {
+                dynamic
+                    .iter()
+                    .any(|field| field.as_str() == Some("version"))
+            });
+        if !dynamic {
+            set_version(project, &v_str);
+            updated.push("[project]");
+        }
+    }
+    if let Some(poetry) = doc
+        .get_mut("tool")
+        .and_then(|tool| tool.get_mut("poetry"))
+        .and_then(|item| item.as_table_like_mut())
+    {
+        set_version(poetry, &v_str);
+        updated.push("[tool.poetry]");
+    }
+    if updated.is_empty() {
+        return Err(BumpError::ParseError(format!(
+            "no static [project] or [tool.poetry] version to set in {}",
+            path.display()
+        )));
+    }
+    save_toml(path, &doc)?;
     println!(
-        "{yellow}Warning: pyproject.toml version string must comply with the following scheme:{reset}"
+        "{} {} updated to version {v_str}",
+        path.display(),
+        updated.join(" and ")
     );
-    println!("{purple} [N!]N(.N)*[{{a|b|rc}}N][.postN][.devN]{reset}");
-    println!("{cyan}  N, N!, and N.N are numeric components.{reset}");
-    println!("{cyan}  {{a|b|rc}} is the alpha, beta, or release candidate suffix.{reset}");
-    println!("{cyan}  postN is the post-release version.{reset}");
-    println!("{cyan}  devN is the development version.{reset}");
+    Ok(())
+}
+
+/// The `version` value of a setup.cfg `[metadata]` line, as `(before, value, after)`.
+fn setup_cfg_version(line: &str) -> Option<(&str, &str, &str)> {
+    let (key, rest) = line.split_once(['=', ':'])?;
+    if key.trim() != "version" {
+        return None;
+    }
+    let value = rest.trim();
+    let start = line.len() - rest.len() + rest.find(value).unwrap_or(0);
+    let end = start + value.len();
+    Some((&line[..start], &line[start..end], &line[end..]))
+}
+
+/// Set `version` in the `[metadata]` section of a setuptools setup.cfg, keeping the
+/// rest of the file as it is.
+pub fn setup_cfg(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = pep440_version(version)?;
+
+    let mut in_metadata = false;
+    let mut replaced = 0;
+    let mut output = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_metadata = trimmed == "[metadata]";
+        } else if in_metadata
+            && !line.starts_with(char::is_whitespace)
+            && let Some((before, current, after)) = setup_cfg_version(line)
+        {
+            // `attr:` and `file:` read the version from the package at build time.
+            if current.starts_with("attr:") || current.starts_with("file:") {
+                return Err(BumpError::LogicError(format!(
+                    "{}: [metadata] version is '{current}'; update that source instead",
+                    path.display()
+                )));
+            }
+            output.push_str(&format!("{before}{v_str}{after}"));
+            replaced += 1;
+            continue;
+        }
+        output.push_str(line);
+    }
+    if replaced == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no [metadata] version found in {}",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!("{} updated to version {v_str}", path.display());
+    Ok(())
+}
+
+fn is_exact_version(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_digit())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+}
+
+/// Whether `key` in the innermost of `blocks` holds the module's own version:
+/// `app_version` or `version` in `locals`, or the `default` of `variable "app_version"`
+/// or `variable "version"`. Provider, module and chart versions elsewhere are not ours.
+fn is_terraform_version(blocks: &[String], key: &str) -> bool {
+    let Some(block) = blocks.last() else {
+        return false;
+    };
+    match key {
+        "app_version" | "version" => block == "locals",
+        "default" => matches!(
+            block.as_str(),
+            "variable \"app_version\"" | "variable \"version\""
+        ),
+        _ => false,
+    }
+}
+
+/// `content` with every exact version [`is_terraform_version`] accepts set to `v_str`,
+/// and how many were set.
+fn rewrite_terraform(content: &str, v_str: &str) -> (String, usize) {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut updated = 0;
+    let mut output = String::new();
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        let mut out = line.to_string();
+        if let Some((key, value)) = trimmed.split_once('=')
+            && is_terraform_version(&blocks, key.trim())
+            && value.split('"').nth(1).is_some_and(is_exact_version)
+            && let Some(replaced) = replace_quoted_value(line, v_str)
+        {
+            out = replaced;
+            updated += 1;
+        }
+        let opens = trimmed.matches('{').count();
+        let closes = trimmed.matches('}').count();
+        if opens > closes {
+            let header = trimmed.split(['=', '{']).next().unwrap_or_default();
+            blocks.push(header.split_whitespace().collect::<Vec<_>>().join(" "));
+        } else if closes > opens {
+            blocks.pop();
+        }
+        output.push_str(&out);
+    }
+    (output, updated)
+}
+
+/// Rewrite the module's own version: `app_version` or `version` in `locals`, or the
+/// default of a `version`/`app_version` variable.
+pub fn terraform(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let (output, updated) = rewrite_terraform(&content, &v_str);
+    if updated == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no exact app_version or version in locals, or default of variable \"version\", \
+             found in {}",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!("{} updated to version {v_str}", path.display());
+    Ok(())
+}
+
+/// Replace the value of a `key: value` YAML line, keeping indentation and line ending.
+fn replace_yaml_value(line: &str, value_str: &str) -> String {
+    let (key, rest) = line.split_once(':').unwrap_or((line, ""));
+    let ending = &rest[rest.trim_end_matches(['\r', '\n']).len()..];
+    format!("{key}: {value_str}{ending}")
+}
+
+/// Byte range of the scalar a top-level `key:` line holds, inside the quotes when it is
+/// quoted, so trailing comments and quoting survive.
+fn yaml_scalar(line: &str, key: &str) -> Option<std::ops::Range<usize>> {
+    let rest = line.strip_prefix(key)?.strip_prefix(':')?;
+    let value = rest.trim_start_matches([' ', '\t']);
+    let start = line.len() - value.len();
+    match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let end = start + 1 + value[1..].find(quote)?;
+            Some(start + 1..end)
+        }
+        _ => {
+            let len = value[..value.find(" #").unwrap_or(value.len())]
+                .trim_end()
+                .len();
+            Some(start..start + len)
+        }
+    }
+}
+
+const OCI_VERSION_LABEL: &str = "org.opencontainers.image.version=";
+
+/// Whether `name` is a Dockerfile: `Dockerfile`, `Containerfile`, `Dockerfile.*` or `*.Dockerfile`.
+fn is_dockerfile(name: &str) -> bool {
+    matches!(name, "Dockerfile" | "Containerfile")
+        || name.starts_with("Dockerfile.")
+        || name.ends_with(".Dockerfile")
+}
+
+/// Whether `name` is a Compose file: `compose.yml`, `docker-compose.yml` or a
+/// `docker-compose.*.yml` override, with either extension.
+fn is_compose_file(name: &str) -> bool {
+    (name.starts_with("compose.") || name.starts_with("docker-compose."))
+        && (name.ends_with(".yml") || name.ends_with(".yaml"))
+}
+
+/// The byte range of each literal `org.opencontainers.image.version` label value in a
+/// Dockerfile line, without quotes. Values from build args (`$VERSION`) are skipped.
+fn oci_version_labels(line: &str) -> Vec<std::ops::Range<usize>> {
+    if line.trim_start().starts_with('#') {
+        return Vec::new();
+    }
+    line.match_indices(OCI_VERSION_LABEL)
+        .filter(|(index, _)| *index == 0 || line[..*index].ends_with([' ', '\t']))
+        .filter_map(|(index, key)| {
+            let start = index + key.len();
+            let value = &line[start..];
+            match value.chars().next() {
+                Some('"') => value[1..].find('"').map(|end| start + 1..start + 1 + end),
+                Some('$') | None => None,
+                Some(_) => {
+                    let end = value
+                        .find([' ', '\t', '\\', '\r', '\n'])
+                        .unwrap_or(value.len());
+                    Some(start..start + end)
+                }
+            }
+        })
+        .collect()
+}
+
+/// Set the `org.opencontainers.image.version` labels of a Dockerfile to the version, as
+/// `bump gen --lang oci` prints it.
+pub fn dockerfile(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::default())?;
+    let mut updated = 0;
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let mut line = line.to_string();
+            for range in oci_version_labels(&line).into_iter().rev() {
+                line.replace_range(range, &v_str);
+                updated += 1;
+            }
+            line
+        })
+        .collect();
+    if updated == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no LABEL {OCI_VERSION_LABEL}<version> with a literal value found in {}",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!("{} updated image version label to {v_str}", path.display());
+    Ok(())
+}
+
+/// The repository and tag ranges of a Compose `image:` line; the tag range is empty
+/// when the image has no tag. Digests and variables are left alone.
+fn compose_image(line: &str) -> Option<(std::ops::Range<usize>, std::ops::Range<usize>)> {
+    let indent = line.len() - line.trim_start_matches([' ', '\t', '-']).len();
+    let range = yaml_scalar(&line[indent..], "image")?;
+    let range = indent + range.start..indent + range.end;
+    let image = &line[range.clone()];
+    if image.is_empty() || image.contains(['@', '$']) {
+        return None;
+    }
+    let name_start = image.rfind('/').map_or(0, |slash| slash + 1);
+    Some(match image[name_start..].find(':') {
+        Some(colon) => {
+            let colon = range.start + name_start + colon;
+            (range.start..colon, colon + 1..range.end)
+        }
+        None => (range.clone(), range.end..range.end),
+    })
+}
+
+/// Set the tag of every Compose image whose repository matches `[docker].images` to the
+/// `[docker].tag` template, restricted to the characters docker allows in tags.
+pub fn compose_file(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let Some(docker) = version
+        .docker
+        .as_ref()
+        .filter(|docker| !docker.images.is_empty())
+    else {
+        return Err(BumpError::LogicError(format!(
+            "{}: list the images to retag in [docker].images",
+            path.display()
+        )));
+    };
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let tag = SafeCharset::Docker.apply(&artifact::render(
+        &docker.tag,
+        &artifact::builtins(version)?,
+    )?);
+    let mut updated = Vec::new();
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let Some((repository, current)) = compose_image(line) else {
+                return line.to_string();
+            };
+            let name = &line[repository.clone()];
+            if !docker
+                .images
+                .iter()
+                .any(|pattern| glob_matches(pattern, name))
+            {
+                return line.to_string();
+            }
+            updated.push(name.to_string());
+            if current.is_empty() {
+                format!("{}:{tag}{}", &line[..current.start], &line[current.end..])
+            } else {
+                format!("{}{tag}{}", &line[..current.start], &line[current.end..])
+            }
+        })
+        .collect();
+    if updated.is_empty() {
+        return Err(BumpError::ParseError(format!(
+            "no image matching [docker].images ({}) found in {}",
+            docker.images.join(", "),
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
     println!(
-        "{yellow}  Public version identifiers MUST NOT include leading or trailing whitespace.{reset}"
+        "{} updated {} to tag {tag}",
+        path.display(),
+        updated.join(", ")
     );
+    Ok(())
+}
 
-    let v_str = print::to_string(version, &PrintOptions::default())?;
-    if doc.get_mut("project").is_some() {
-        set_toml_field(&mut doc, "project", "version", &v_str)?;
-        save_toml(path, &doc)?;
-        println!("pyproject.toml updated to version {v_str}");
+/// Set `version` and `appVersion` in a Helm Chart.yaml, or the fields `[helm]` picks.
+/// An unquoted `appVersion` is quoted, as YAML would read `1.10` as a number.
+pub fn helm_chart(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let fields = version.helm.clone().unwrap_or_default().fields;
+    if fields.is_empty() {
+        return Err(BumpError::LogicError(
+            "[helm].fields is empty; list version, appVersion or both".to_string(),
+        ));
+    }
+
+    let mut updated = Vec::new();
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            for field in &fields {
+                let Some(range) = yaml_scalar(line, field.key()) else {
+                    continue;
+                };
+                updated.push(field.key());
+                let quoted = range.start > 0 && line[..range.start].ends_with(['"', '\'']);
+                let new_value = match field {
+                    HelmField::AppVersion if !quoted => format!("\"{v_str}\""),
+                    _ => v_str.clone(),
+                };
+                return format!("{}{new_value}{}", &line[..range.start], &line[range.end..]);
+            }
+            line.to_string()
+        })
+        .collect();
+    if updated.is_empty() {
+        let keys: Vec<&str> = fields.iter().map(|field| field.key()).collect();
+        return Err(BumpError::ParseError(format!(
+            "no top-level {} found in {}",
+            keys.join(" or "),
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!(
+        "{} updated {} to {v_str}",
+        path.display(),
+        updated.join(" and ")
+    );
+    Ok(())
+}
+
+/// Set `bundleVersion` in Unity's ProjectSettings.asset, and the Android
+/// `AndroidBundleVersionCode` and iOS `buildNumber` to the next build number (see
+/// [`version_code::for_file`]), since the stores reject uploads that reuse one.
+pub fn unity_project_settings(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+
+    let field = |key: &str| {
+        content.lines().find_map(|line| {
+            line.trim_start()
+                .strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix(':'))
+                .map(str::trim)
+        })
+    };
+    let Some(current) = field("bundleVersion") else {
+        return Err(BumpError::ParseError(format!(
+            "no bundleVersion found in {}",
+            path.display()
+        )));
+    };
+    let file_code = field("AndroidBundleVersionCode")
+        .map(|code| {
+            code.parse::<u32>().map_err(|e| {
+                BumpError::ParseError(format!(
+                    "invalid AndroidBundleVersionCode '{code}' in {}: {e}",
+                    path.display()
+                ))
+            })
+        })
+        .transpose()?;
+    let code = version_code::for_file(version, current != v_str, file_code)?;
+
+    let mut in_build_number = None;
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let indent = line.len() - trimmed.len();
+            if in_build_number.is_some_and(|block_indent| indent <= block_indent) {
+                in_build_number = None;
+            }
+            let key = trimmed.split(':').next().unwrap_or_default();
+            match key {
+                "bundleVersion" => replace_yaml_value(line, &v_str),
+                "AndroidBundleVersionCode" => replace_yaml_value(line, &code.to_string()),
+                "iPhone" if in_build_number.is_some() => {
+                    replace_yaml_value(line, &code.to_string())
+                }
+                "buildNumber" => {
+                    in_build_number = Some(indent);
+                    line.to_string()
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect();
+
+    write_file(path, output)?;
+    println!(
+        "{} updated to version {v_str} (build number {code})",
+        path.display()
+    );
+    Ok(())
+}
+
+const UNREAL_PROJECT_SECTION: &str = "[/Script/EngineSettings.GeneralProjectSettings]";
+
+/// Set `ProjectVersion=` in the project settings section of Unreal's
+/// Config/DefaultGame.ini, adding the key under the section header when it is missing.
+pub fn unreal_default_game(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+
+    let mut section = None;
+    let mut entries = Vec::new();
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            section = Some(trimmed);
+        }
+        let is_version = section == Some(UNREAL_PROJECT_SECTION)
+            && trimmed
+                .split_once('=')
+                .is_some_and(|(key, _)| key.trim() == "ProjectVersion");
+        entries.push((line, trimmed == UNREAL_PROJECT_SECTION, is_version));
+    }
+    if !entries.iter().any(|&(_, is_header, _)| is_header) {
+        return Err(BumpError::ParseError(format!(
+            "no {UNREAL_PROJECT_SECTION} section found in {}",
+            path.display()
+        )));
+    }
+    let has_version = entries.iter().any(|&(_, _, is_version)| is_version);
+
+    let mut output = String::new();
+    for (line, is_header, is_version) in entries {
+        let ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+        if is_version {
+            output.push_str(&format!("ProjectVersion={v_str}{ending}"));
+            continue;
+        }
+        output.push_str(line);
+        if is_header && !has_version {
+            let ending = if ending.is_empty() { "\n" } else { ending };
+            if !line.ends_with('\n') {
+                output.push_str(ending);
+            }
+            output.push_str(&format!("ProjectVersion={v_str}{ending}"));
+        }
+    }
+
+    write_file(path, output)?;
+    println!("{} updated to version {v_str}", path.display());
+    Ok(())
+}
+
+/// Split a Gradle `key "value"`, `key = "value"`, or `key = 12` line into the text
+/// before the value, the value, and the text after it.
+fn gradle_assignment<'a>(line: &'a str, key: &str) -> Option<(&'a str, &'a str, &'a str)> {
+    let rest = line.trim_start().strip_prefix(key)?;
+    let rest_trimmed = rest.trim_start_matches([' ', '\t', '=']);
+    if rest_trimmed.len() == rest.len() {
+        return None;
+    }
+    let start = line.len() - rest_trimmed.len();
+    let (value, after) = if let Some(quoted) = rest_trimmed.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        (&line[start + 1..start + 1 + end], &line[start + 1 + end..])
+    } else {
+        let end = rest_trimmed
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest_trimmed.len());
+        if end == 0 {
+            return None;
+        }
+        (&line[start..start + end], &line[start + end..])
+    };
+    let before = &line[..line.len() - value.len() - after.len()];
+    Some((before, value, after))
+}
+
+/// A top-level `version = "..."` (or `'...'` in Groovy) project assignment, split
+/// around the value.
+fn gradle_project_version(line: &str) -> Option<(&str, &str, &str)> {
+    let rest = line.strip_prefix("version")?;
+    let value = rest.trim_start_matches([' ', '\t']).strip_prefix('=')?;
+    let value = value.trim_start_matches([' ', '\t']);
+    let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let start = line.len() - value.len() + 1;
+    let end = start + line[start..].find(quote)?;
+    Some((&line[..start], &line[start..end], &line[end..]))
+}
+
+/// Set the top-level project `version` of a Gradle build script.
+fn gradle_project(version: &Version, path: &Path, content: &str) -> Result<(), BumpError> {
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let mut updated = 0;
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| match gradle_project_version(line) {
+            Some((before, _, after)) => {
+                updated += 1;
+                format!("{before}{v_str}{after}")
+            }
+            None => line.to_string(),
+        })
+        .collect();
+    if updated == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no versionName or top-level version = \"...\" found in {}",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!("{} updated to version {v_str}", path.display());
+    Ok(())
+}
+
+/// The value of a `version=` (or `version:`, `version = `) line in gradle.properties,
+/// split around it.
+fn gradle_properties_version(line: &str) -> Option<(&str, &str, &str)> {
+    let rest = line.trim_start().strip_prefix("version")?;
+    let value = rest
+        .trim_start_matches([' ', '\t'])
+        .strip_prefix(['=', ':'])?
+        .trim_start_matches([' ', '\t']);
+    let start = line.len() - value.len();
+    let end = start + value.trim_end().len();
+    Some((&line[..start], &line[start..end], &line[end..]))
+}
+
+/// Set `version` in gradle.properties, where builds that read `project.version` from
+/// properties keep it.
+pub fn gradle_properties(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let mut updated = 0;
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| match gradle_properties_version(line) {
+            Some((before, _, after)) => {
+                updated += 1;
+                format!("{before}{v_str}{after}")
+            }
+            None => line.to_string(),
+        })
+        .collect();
+    if updated == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no version property found in {}",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!("{} updated to version {v_str}", path.display());
+    Ok(())
+}
+
+/// Byte range of the text of the `<version>` directly under `<project>`, skipping the
+/// ones in `<parent>`, dependencies, plugins and profiles, and comments.
+fn pom_project_version(content: &str) -> Option<std::ops::Range<usize>> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut i = 0;
+    while let Some(found) = content[i..].find('<') {
+        let start = i + found;
+        let rest = &content[start..];
+        if rest.starts_with("<!--") {
+            i = start + rest.find("-->")? + 3;
+            continue;
+        }
+        if rest.starts_with("<![CDATA[") {
+            i = start + rest.find("]]>")? + 3;
+            continue;
+        }
+        let end = start + rest.find('>')?;
+        let tag = &content[start + 1..end];
+        i = end + 1;
+        if tag.starts_with(['?', '!']) || tag.ends_with('/') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            if stack.last() == Some(&name.trim()) {
+                stack.pop();
+            }
+            continue;
+        }
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        if name == "version" && stack == ["project"] {
+            let close = i + content[i..].find('<')?;
+            return Some(i..close);
+        }
+        stack.push(name);
+    }
+    None
+}
+
+/// Set the project's own `<version>` in a Maven pom.xml, leaving the parent and
+/// dependency versions alone.
+pub fn pom_xml(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let Some(range) = pom_project_version(&content) else {
+        return Err(BumpError::ParseError(format!(
+            "no <version> directly under <project> in {}; modules inheriting it from \
+             <parent> take the parent pom's version",
+            path.display()
+        )));
+    };
+    let current = content[range.clone()].trim();
+    if current.contains("${") {
+        return Err(BumpError::LogicError(format!(
+            "{}: <version> is {current}; update the property it names instead",
+            path.display()
+        )));
+    }
+    let mut output = content.clone();
+    output.replace_range(range, &v_str);
+    write_file(path, output)?;
+    println!("{} updated to version {v_str}", path.display());
+    Ok(())
+}
+
+/// Set `versionName` and `versionCode` in an Android module's build.gradle(.kts), or
+/// the top-level project `version` of other build scripts.
+pub fn gradle(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    if !content.contains("versionName") {
+        return gradle_project(version, path, &content);
+    }
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+
+    let find = |key: &str| {
+        content
+            .lines()
+            .find_map(|line| gradle_assignment(line, key).map(|(_, value, _)| value))
+    };
+    let Some(current) = find("versionName") else {
+        return Err(BumpError::ParseError(format!(
+            "no versionName found in {}",
+            path.display()
+        )));
+    };
+    let file_code = find("versionCode")
+        .map(|code| {
+            code.parse::<u32>().map_err(|e| {
+                BumpError::ParseError(format!(
+                    "invalid versionCode '{code}' in {}: {e}",
+                    path.display()
+                ))
+            })
+        })
+        .transpose()?;
+    let code = version_code::for_file(version, current != v_str, file_code)?;
+
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            if let Some((before, _, after)) = gradle_assignment(line, "versionName") {
+                format!("{before}{v_str}{after}")
+            } else if let Some((before, _, after)) = gradle_assignment(line, "versionCode") {
+                format!("{before}{code}{after}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    write_file(path, output)?;
+    println!(
+        "{} updated to version {v_str} (versionCode {code})",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Byte ranges of the string values in a JSON document (inside the quotes), each with
+/// the path of object keys leading to it. Formatting is never touched.
+fn json_string_values(content: &str) -> Vec<(Vec<String>, std::ops::Range<usize>)> {
+    let mut values = Vec::new();
+    let mut path: Vec<Option<String>> = Vec::new();
+    let mut key: Option<String> = None;
+    let mut after_colon = false;
+    let mut chars = content.char_indices();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '"' => {
+                let mut end = start + 1;
+                let mut escaped = false;
+                for (i, c) in chars.by_ref() {
+                    end = i;
+                    if escaped {
+                        escaped = false;
+                    } else if c == '\\' {
+                        escaped = true;
+                    } else if c == '"' {
+                        break;
+                    }
+                }
+                let text = &content[start + 1..end];
+                if after_colon {
+                    let mut full: Vec<String> = path.iter().flatten().cloned().collect();
+                    full.extend(key.take());
+                    values.push((full, start + 1..end));
+                    after_colon = false;
+                } else {
+                    key = Some(text.to_string());
+                }
+            }
+            ':' => after_colon = true,
+            '{' | '[' => {
+                path.push(if after_colon { key.take() } else { None });
+                after_colon = false;
+            }
+            '}' | ']' => {
+                path.pop();
+            }
+            ',' => {
+                after_colon = false;
+                key = None;
+            }
+            _ => {}
+        }
+    }
+    values
+}
+
+/// Replace the JSON string values at each of `targets` (key paths), returning how many
+/// were replaced.
+fn replace_json_values(content: &str, targets: &[(&[&str], &str)]) -> (String, usize) {
+    let mut output = content.to_string();
+    let mut replaced = 0;
+    for (path, range) in json_string_values(content).into_iter().rev() {
+        if let Some((_, new_value)) = targets.iter().find(|(target, _)| *target == path) {
+            output.replace_range(range, new_value);
+            replaced += 1;
+        }
+    }
+    (output, replaced)
+}
+
+/// electron-builder's `buildVersion` (CFBundleVersion on macOS, FileVersion on
+/// Windows): the `[version_code]` when configured, otherwise the version.
+fn electron_build_version(version: &Version) -> Result<String, BumpError> {
+    match version_code::current(version)? {
+        Some(code) => Ok(code.to_string()),
+        None => print::to_string(version, &PrintOptions::no_prefix()),
+    }
+}
+
+/// Set `version` in package.json, and `build.buildVersion` when the electron-builder
+/// config embedded there has one.
+pub fn package_json(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let build_version = electron_build_version(version)?;
+
+    let (output, replaced) = replace_json_values(
+        &content,
+        &[
+            (&["version"], &v_str),
+            (&["build", "buildVersion"], &build_version),
+        ],
+    );
+    if !json_string_values(&content)
+        .iter()
+        .any(|(key, _)| key == &["version"])
+    {
+        return Err(BumpError::ParseError(format!(
+            "no top-level \"version\" found in {}",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    if replaced > 1 {
+        println!(
+            "{} updated to version {v_str} (buildVersion {build_version})",
+            path.display()
+        );
+    } else {
+        println!("{} updated to version {v_str}", path.display());
+    }
+    Ok(())
+}
+
+/// Set the top-level `version` in composer.json.
+pub fn composer_json(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let (output, replaced) = replace_json_values(&content, &[(&["version"], &v_str)]);
+    if replaced == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no top-level \"version\" found in {}; Packagist takes it from the git tag otherwise",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!("{} updated to version {v_str}", path.display());
+    Ok(())
+}
+
+/// Set the top-level `version` in a Dart or Flutter pubspec.yaml. Flutter's `+build`
+/// number becomes the `[version_code]` when configured and is kept otherwise.
+pub fn pubspec_yaml(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let code = version_code::current(version)?;
+
+    let mut updated = None;
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let Some(range) = yaml_scalar(line, "version") else {
+                return line.to_string();
+            };
+            let build = match code {
+                Some(code) => format!("+{code}"),
+                None => line[range.clone()].find('+').map_or(String::new(), |i| {
+                    line[range.start + i..range.end].to_string()
+                }),
+            };
+            let new_value = format!("{v_str}{build}");
+            let line = format!("{}{new_value}{}", &line[..range.start], &line[range.end..]);
+            updated = Some(new_value);
+            line
+        })
+        .collect();
+    let Some(new_value) = updated else {
+        return Err(BumpError::ParseError(format!(
+            "no top-level version found in {}",
+            path.display()
+        )));
+    };
+    write_file(path, output)?;
+    println!("{} updated to version {new_value}", path.display());
+    Ok(())
+}
+
+/// Byte range of the string literal following `marker` on a line, as in `version: "1.0.0"`
+/// and `@version "1.0.0"`.
+fn quoted_after(line: &str, marker: &str) -> Option<std::ops::Range<usize>> {
+    let at = line.find(marker)? + marker.len();
+    let rest = &line[at..];
+    let value = rest.trim_start().strip_prefix('"')?;
+    let start = line.len() - value.len();
+    Some(start..start + value.find('"')?)
+}
+
+/// Set the version in an Elixir mix.exs: the `@version "..."` module attribute that
+/// `project/0` reads, or else a literal `version: "..."`.
+pub fn mix_exs(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?;
+    let marker = if content
+        .lines()
+        .any(|line| line.trim_start().starts_with("@version "))
+    {
+        "@version"
+    } else {
+        "version:"
+    };
+
+    let mut updated = 0;
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let range = if marker == "@version" {
+                trimmed
+                    .starts_with("@version ")
+                    .then(|| quoted_after(line, marker))
+                    .flatten()
+            } else if trimmed.starts_with('#') {
+                None
+            } else {
+                quoted_after(line, marker)
+            };
+            let Some(range) = range else {
+                return line.to_string();
+            };
+            updated += 1;
+            format!("{}{v_str}{}", &line[..range.start], &line[range.end..])
+        })
+        .collect();
+    if updated == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no @version attribute or literal version: \"...\" found in {}",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!("{} updated to version {v_str}", path.display());
+    Ok(())
+}
+
+/// The byte range of the literal in a `spec.version = "..."` gemspec line (any block
+/// variable, single or double quotes, an optional `.freeze`).
+fn gemspec_version(line: &str) -> Option<std::ops::Range<usize>> {
+    let (lhs, rhs) = line.split_once('=')?;
+    let (receiver, attribute) = lhs.trim().split_once('.')?;
+    if attribute != "version"
+        || receiver.is_empty()
+        || !receiver.chars().all(|c| c.is_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+    let value = rhs.trim_start();
+    let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let start = line.len() - value.len() + 1;
+    Some(start..start + line[start..].find(quote)?)
+}
+
+/// Set `spec.version` in a Ruby .gemspec. RubyGems has no `-` in versions, so
+/// pre-releases are written with dots (`1.0.0.rc.1`), which still sort first.
+pub fn gemspec(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let v_str = print::to_string(version, &PrintOptions::no_prefix())?.replace('-', ".");
+
+    let mut updated = 0;
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let Some(range) = gemspec_version(line) else {
+                return line.to_string();
+            };
+            updated += 1;
+            format!("{}{v_str}{}", &line[..range.start], &line[range.end..])
+        })
+        .collect();
+    if updated == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no literal spec.version = \"...\" in {}; a version taken from a constant \
+             is set where the constant is defined",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!("{} updated to version {v_str}", path.display());
+    Ok(())
+}
+
+/// One piece of a `[markdown]` pattern: literal text or a `{placeholder}`.
+enum Piece<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+fn pattern_pieces(pattern: &str) -> Result<Vec<Piece<'_>>, BumpError> {
+    let mut pieces = Vec::new();
+    let mut rest = pattern;
+    while let Some(open) = rest.find('{') {
+        let close = rest[open..].find('}').ok_or_else(|| {
+            BumpError::ParseError(format!("unclosed '{{' in [markdown] pattern '{pattern}'"))
+        })?;
+        if open > 0 {
+            pieces.push(Piece::Text(&rest[..open]));
+        }
+        pieces.push(Piece::Placeholder(&rest[open + 1..open + close]));
+        rest = &rest[open + close + 1..];
+    }
+    if !rest.is_empty() {
+        pieces.push(Piece::Text(rest));
+    }
+    Ok(pieces)
+}
+
+fn is_version_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-' | '_' | '~')
+}
+
+/// Where a version written at the start of `text` may end, shortest first. In shields.io
+/// badge paths `--` and `__` are escaped `-` and `_`, so a version never ends before one.
+fn version_ends(text: &str, shields: bool) -> Vec<usize> {
+    let escaped =
+        |at: usize| shields && (text[at..].starts_with("--") || text[at..].starts_with("__"));
+    let mut ends = Vec::new();
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next().filter(|c| is_version_char(*c)) {
+        i += if escaped(i) { 2 } else { c.len_utf8() };
+        let token = &text[..i];
+        if token.contains(|c: char| c.is_ascii_digit())
+            && !token.ends_with(['.', '-', '_'])
+            && !escaped(i)
+        {
+            ends.push(i);
+        }
+    }
+    ends
+}
+
+/// Match `pieces` against `text` at `pos`, collecting the range each placeholder covers.
+/// A version followed by more of the pattern is matched as short as possible; one
+/// ending the pattern as long as possible.
+fn match_pieces<'a>(
+    text: &str,
+    pos: usize,
+    pieces: &[Piece<'a>],
+    shields: bool,
+    found: &mut Vec<(std::ops::Range<usize>, &'a str)>,
+) -> Option<usize> {
+    let Some((first, rest)) = pieces.split_first() else {
+        return Some(pos);
+    };
+    match first {
+        Piece::Text(literal) => {
+            if !text[pos..].starts_with(literal) {
+                return None;
+            }
+            match_pieces(text, pos + literal.len(), rest, shields, found)
+        }
+        Piece::Placeholder(name) => {
+            let mut ends = version_ends(&text[pos..], shields);
+            if rest.is_empty() {
+                ends.reverse();
+            }
+            for end in ends {
+                found.push((pos..pos + end, name));
+                if let Some(stop) = match_pieces(text, pos + end, rest, shields, found) {
+                    return Some(stop);
+                }
+                found.pop();
+            }
+            None
+        }
+    }
+}
+
+/// Every version the `[markdown]` patterns find in `content`, with its replacement.
+fn markdown_versions(
+    version: &Version,
+    content: &str,
+) -> Result<Vec<(std::ops::Range<usize>, String)>, BumpError> {
+    let values = artifact::builtins(version)?;
+    let mut versions = Vec::new();
+    for pattern in &version.markdown.clone().unwrap_or_default().patterns {
+        // Unknown placeholders fail here even when the pattern matches nothing.
+        artifact::render(pattern, &values)?;
+        let pieces = pattern_pieces(pattern)?;
+        if !pieces
+            .iter()
+            .any(|piece| matches!(piece, Piece::Placeholder(_)))
+        {
+            return Err(BumpError::ParseError(format!(
+                "[markdown] pattern '{pattern}' has no placeholder to mark the version"
+            )));
+        }
+        let shields = pattern.contains("img.shields.io/badge/");
+        let mut pos = 0;
+        while pos < content.len() {
+            let after_version_char = content[..pos]
+                .chars()
+                .next_back()
+                .is_some_and(is_version_char);
+            let mut found = Vec::new();
+            let end = match pieces.first() {
+                Some(Piece::Placeholder(_)) if after_version_char => None,
+                _ => match_pieces(content, pos, &pieces, shields, &mut found),
+            };
+            let Some(end) = end.filter(|end| *end > pos) else {
+                pos += content[pos..].chars().next().map_or(1, char::len_utf8);
+                continue;
+            };
+            for (range, name) in found {
+                let value = artifact::render(&format!("{{{name}}}"), &values)?;
+                let value = if shields {
+                    value.replace('-', "--").replace('_', "__")
+                } else {
+                    value
+                };
+                versions.push((range, value));
+            }
+            pos = end;
+        }
+    }
+    versions.sort_by_key(|(range, _)| range.start);
+    versions.dedup_by_key(|(range, _)| range.start);
+    Ok(versions)
+}
+
+/// Rewrite the versions the `[markdown]` patterns find in a Markdown file, such as a
+/// shields.io badge or a documented install command.
+pub fn markdown(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let versions = markdown_versions(version, &content)?;
+    if versions.is_empty() {
+        return Err(BumpError::ParseError(format!(
+            "none of the [markdown] patterns found in {}",
+            path.display()
+        )));
+    }
+    let mut output = content.clone();
+    for (range, value) in versions.iter().rev() {
+        output.replace_range(range.clone(), value);
+    }
+    if output != content {
+        write_file(path, output)?;
+    }
+    println!(
+        "{} updated {} version(s) in the docs",
+        path.display(),
+        versions.len()
+    );
+    Ok(())
+}
+
+/// Expand one `workspaces` pattern under `base`: `*` matches within one directory level.
+fn expand_workspace_pattern(base: &Path, pattern: &str) -> Result<Vec<PathBuf>, BumpError> {
+    let mut dirs = vec![base.to_path_buf()];
+    for segment in pattern.split('/').filter(|s| !s.is_empty() && *s != ".") {
+        if !segment.contains('*') {
+            dirs = dirs.into_iter().map(|dir| dir.join(segment)).collect();
+            continue;
+        }
+        let mut matched = Vec::new();
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            for entry in fs::read_dir(dir).map_err(BumpError::IoError)? {
+                let entry = entry.map_err(BumpError::IoError)?;
+                let name = entry.file_name().to_string_lossy().to_string();
+                if entry.path().is_dir()
+                    && !name.starts_with('.')
+                    && !SKIPPED_DIRS.contains(&name.as_str())
+                    && glob_matches(segment, &name)
+                {
+                    matched.push(entry.path());
+                }
+            }
+        }
+        dirs = matched;
+    }
+    Ok(dirs)
+}
+
+/// The package.json of every workspace the root `path` declares in `workspaces` (npm's
+/// array of patterns, or yarn's `{ "packages": [...] }`), minus `!pattern` exclusions.
+fn workspace_manifests(path: &Path) -> Result<Vec<PathBuf>, BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let root: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| BumpError::ParseError(format!("failed to parse {}: {e}", path.display())))?;
+    let workspaces = &root["workspaces"];
+    let patterns: Vec<&str> = workspaces
+        .get("packages")
+        .unwrap_or(workspaces)
+        .as_array()
+        .ok_or_else(|| {
+            BumpError::ParseError(format!(
+                "no \"workspaces\" patterns found in {}",
+                path.display()
+            ))
+        })?
+        .iter()
+        .filter_map(serde_json::Value::as_str)
+        .collect();
+    let base = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let relative = |dir: &Path| {
+        let dir = dir.strip_prefix(base).unwrap_or(dir);
+        dir.components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+    let (excluded, included): (Vec<&str>, Vec<&str>) =
+        patterns.into_iter().partition(|p| p.starts_with('!'));
+    let mut dirs = Vec::new();
+    for pattern in included {
+        dirs.extend(expand_workspace_pattern(base, pattern)?);
+    }
+    dirs.sort();
+    dirs.dedup();
+    Ok(dirs
+        .into_iter()
+        .filter(|dir| {
+            let dir = relative(dir);
+            !excluded.iter().any(|pattern| {
+                let pattern = pattern[1..].trim_start_matches("./").trim_end_matches('/');
+                glob_matches(pattern, &dir)
+            })
+        })
+        .map(|dir| dir.join("package.json"))
+        .filter(|manifest| manifest.is_file())
+        .collect())
+}
+
+/// Set `version` in each workspace package.json of the root `path`. Workspaces without
+/// a `version` (private packages that are never published) are left alone.
+fn package_json_workspaces(version: &Version, path: &Path) -> Result<(), BumpError> {
+    for manifest in workspace_manifests(path)? {
+        let content = fs::read_to_string(&manifest).map_err(BumpError::IoError)?;
+        if json_string_values(&content)
+            .iter()
+            .any(|(key, _)| key == &["version"])
+        {
+            package_json(version, &manifest)?;
+        } else {
+            println!("{} has no version, skipping", manifest.display());
+        }
+    }
+    Ok(())
+}
+
+/// Set the top-level `buildVersion` in an electron-builder.json/.yml config.
+pub fn electron_builder(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let build_version = electron_build_version(version)?;
+
+    let (output, replaced) = if path.extension().is_some_and(|ext| ext == "json") {
+        replace_json_values(&content, &[(&["buildVersion"], &build_version)])
+    } else {
+        let mut replaced = 0;
+        let output = content
+            .split_inclusive('\n')
+            .map(|line| {
+                if line.starts_with("buildVersion:") {
+                    replaced += 1;
+                    replace_yaml_value(line, &format!("\"{build_version}\""))
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect();
+        (output, replaced)
+    };
+    if replaced == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no top-level buildVersion found in {}",
+            path.display()
+        )));
+    }
+    write_file(path, output)?;
+    println!("{} updated to buildVersion {build_version}", path.display());
+    Ok(())
+}
+
+/// Byte offset of the `(` opening the first `project()` call outside a comment. CMake
+/// command names are case-insensitive.
+fn cmake_project_call(content: &str) -> Option<usize> {
+    let lower = content.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(found) = lower[from..].find("project") {
+        let start = from + found;
+        let end = start + "project".len();
+        from = end;
+        let line_start = lower[..start].rfind('\n').map_or(0, |i| i + 1);
+        let boundary = lower[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_ascii_alphanumeric() || c == '_'));
+        let rest = lower[end..].trim_start_matches([' ', '\t']);
+        if boundary && !lower[line_start..start].contains('#') && rest.starts_with('(') {
+            return Some(lower.len() - rest.len());
+        }
+    }
+    None
+}
+
+/// Byte range of the value after `VERSION` in the first `project()` call, inside the
+/// quotes when it is quoted.
+fn cmake_project_version(content: &str) -> Option<std::ops::Range<usize>> {
+    let bytes = content.as_bytes();
+    let mut i = cmake_project_call(content)? + 1;
+    let mut after_keyword = false;
+    while i < bytes.len() {
+        match bytes[i] {
+            b')' => return None,
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            c if c.is_ascii_whitespace() => i += 1,
+            b'"' => {
+                let end = i + 1 + content[i + 1..].find('"')?;
+                if after_keyword {
+                    return Some(i + 1..end);
+                }
+                after_keyword = false;
+                i = end + 1;
+            }
+            _ => {
+                let end = content[i..]
+                    .find(|c: char| c.is_whitespace() || c == ')' || c == '#')
+                    .map_or(content.len(), |n| i + n);
+                if after_keyword {
+                    return Some(i..end);
+                }
+                after_keyword = &content[i..end] == "VERSION";
+                i = end;
+            }
+        }
+    }
+    None
+}
+
+/// Rewrite the `VERSION` argument of the `project()` call in a CMakeLists.txt, leaving
+/// every other byte alone. CMake versions are numeric, so the prefix and phase are
+/// dropped.
+pub fn cmake_lists(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let Some(range) = cmake_project_version(&content) else {
+        return Err(BumpError::ParseError(format!(
+            "no project(<name> VERSION <version>) call found in {}",
+            path.display()
+        )));
+    };
+    let current = &content[range.clone()];
+    if current.contains("${") {
+        return Err(BumpError::LogicError(format!(
+            "{}: project() takes its VERSION from {current}; update that variable instead",
+            path.display()
+        )));
+    }
+    let components: Vec<String> = [
+        Some(version.base.major),
+        version.base.minor,
+        version.base.patch,
+    ]
+    .into_iter()
+    .flatten()
+    .map(|n| n.to_string())
+    .collect();
+    let v_str = components.join(".");
+    let full = print::to_string(version, &PrintOptions::no_prefix())?;
+    if full != v_str {
+        println!("CMake versions are numeric; writing {v_str} for {full}");
+    }
+
+    let mut output = content.clone();
+    output.replace_range(range, &v_str);
+    write_file(path, output)?;
+    println!("{} updated to project VERSION {v_str}", path.display());
+    Ok(())
+}
+
+/// A VERSIONINFO quadruple: major, minor, patch, and a fourth field chosen by `build`.
+fn rc_quad(version: &Version, build: RcBuild) -> Result<[u32; 4], BumpError> {
+    let build = match build {
+        RcBuild::Candidate => version.build_number(),
+        RcBuild::Counter => version_code::current(version)?.ok_or_else(|| {
+            BumpError::LogicError("[rc] build 'counter' needs [version_code]".to_string())
+        })?,
+        RcBuild::Zero => 0,
+    };
+    let quad = [
+        version.base.major,
+        version.base.minor.unwrap_or(0),
+        version.base.patch.unwrap_or(0),
+        build,
+    ];
+    if let Some(field) = quad.iter().find(|field| **field > 65535) {
+        return Err(BumpError::LogicError(format!(
+            "Windows version fields are 16-bit, {field} does not fit"
+        )));
+    }
+    Ok(quad)
+}
+
+/// Rewrite the VERSIONINFO resource of a Windows .rc file: the numeric `FILEVERSION`
+/// and `PRODUCTVERSION` quadruples, derived as configured in `[rc]`, and the
+/// `"FileVersion"` / `"ProductVersion"` strings, which mirror them.
+pub fn windows_rc(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    if version.base.mode != VersionMode::Semver {
+        return Err(BumpError::LogicError(
+            "Windows version resources are only valid for version.type = 'semver'".to_string(),
+        ));
+    }
+    let config = version.rc.clone().unwrap_or_default();
+    let file = rc_quad(version, config.file_build)?;
+    let product = rc_quad(version, config.product_build)?;
+    let numeric = |quad: [u32; 4]| quad.map(|n| n.to_string()).join(",");
+    let dotted = |quad: [u32; 4]| quad.map(|n| n.to_string()).join(".");
+
+    let mut updated = 0;
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let indent = &line[..line.len() - trimmed.len()];
+            let ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+            let keyword = trimmed.split_whitespace().next().unwrap_or_default();
+            match keyword {
+                "FILEVERSION" | "PRODUCTVERSION" => {
+                    let quad = if keyword == "FILEVERSION" {
+                        file
+                    } else {
+                        product
+                    };
+                    updated += 1;
+                    return format!("{indent}{keyword} {}{ending}", numeric(quad));
+                }
+                "VALUE" => {
+                    for (name, quad) in [("FileVersion", file), ("ProductVersion", product)] {
+                        let quoted = format!("\"{name}\"");
+                        if let Some(after) = trimmed.find(&quoted).map(|i| i + quoted.len()) {
+                            updated += 1;
+                            let head = &line[..indent.len() + after];
+                            return format!("{head}, \"{}\"{ending}", dotted(quad));
+                        }
+                    }
+                }
+                _ => {}
+            }
+            line.to_string()
+        })
+        .collect();
+    if updated == 0 {
+        return Err(BumpError::ParseError(format!(
+            "no VERSIONINFO fields found in {}",
+            path.display()
+        )));
+    }
+
+    write_file(path, output)?;
+    println!(
+        "{} updated to FILEVERSION {} and PRODUCTVERSION {}",
+        path.display(),
+        dotted(file),
+        dotted(product)
+    );
+    Ok(())
+}
+
+/// The quoted value of a `PV = "..."` assignment (also `?=`, `??=` and `:=`).
+fn bitbake_pv(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix("PV")?;
+    let (op, value) = rest.trim_start().split_once('=')?;
+    if !matches!(op, "" | "?" | "??" | ":") {
+        return None;
+    }
+    value.trim().strip_prefix('"')?.strip_suffix('"')
+}
+
+/// The recipe name of a `name_1.2.3.bb` file, whose PV comes from the file name.
+fn versioned_recipe_name(path: &Path) -> Option<&str> {
+    if path.extension().is_none_or(|ext| ext != "bb") {
+        return None;
+    }
+    let (name, pv) = path.file_stem()?.to_str()?.rsplit_once('_')?;
+    pv.starts_with(|c: char| c.is_ascii_digit()).then_some(name)
+}
+
+/// Set `PV` in a BitBake recipe or include file. A recipe without a PV assignment
+/// takes its version from the file name (`name_1.2.3.bb`), so it is renamed instead.
+/// Pre-releases use `~` (`1.2.3~rc.1`) so they sort before the release, and a
+/// `+git${SRCPV}` tail is kept.
+pub fn bitbake(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let pv = print::to_string(version, &PrintOptions::no_prefix())?.replacen('-', "~", 1);
+
+    let mut updated = 0;
+    let output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let Some(current) = bitbake_pv(line) else {
+                return line.to_string();
+            };
+            let tail = current.find("+git").map_or("", |i| &current[i..]);
+            updated += 1;
+            replace_quoted_value(line, &format!("{pv}{tail}")).unwrap_or_else(|| line.to_string())
+        })
+        .collect();
+    if updated > 0 {
+        write_file(path, output)?;
+        println!("{} updated to PV {pv}", path.display());
+        return Ok(());
+    }
+
+    let Some(name) = versioned_recipe_name(path) else {
+        return Err(BumpError::ParseError(format!(
+            "no PV assignment or versioned recipe name (name_1.2.3.bb) in {}",
+            path.display()
+        )));
+    };
+    let renamed = path.with_file_name(format!("{name}_{pv}.bb"));
+    if renamed != path {
+        rename_file(path, &renamed)?;
+    }
+    println!("{} renamed to {}", path.display(), renamed.display());
+    Ok(())
+}
+
+/// The version as Debian and RPM order it: a phase becomes `~rc1`, which sorts before
+/// the release, and a CalVer same-day revision `.1`, which sorts after it.
+fn package_version(version: &Version) -> Result<String, BumpError> {
+    let base = print::to_string(
+        version,
+        &PrintOptions {
+            only_base: true,
+            ..PrintOptions::default()
+        },
+    )?;
+    Ok(
+        match (version.phase.name.as_str(), version.phase.distance) {
+            ("", 0) => base,
+            ("", distance) => format!("{base}.{distance}"),
+            (name, 0) => format!("{base}~{name}"),
+            (name, distance) => format!("{base}~{name}{distance}"),
+        },
+    )
+}
+
+/// When the new changelog entry was made: `SOURCE_DATE_EPOCH` if set, else now.
+fn release_time() -> Result<chrono::DateTime<chrono::Utc>, BumpError> {
+    Ok(lang::source_date_epoch()?.unwrap_or_else(chrono::Utc::now))
+}
+
+/// `Name <email>` for a new changelog entry from the first of the `name` and `email`
+/// variables that are set, then git's user.name and user.email.
+fn packager(name: &[&str], email: &[&str]) -> Option<String> {
+    let var = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+    };
+    let name = var(name).or_else(|| history::git_config("user.name"))?;
+    let email = var(email).or_else(|| history::git_config("user.email"))?;
+    Some(format!("{name} <{email}>"))
+}
+
+fn is_debian_changelog(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "changelog")
+        && path
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|dir| dir == "debian")
+}
+
+/// The `package (version) distribution; urgency=...` line opening a Debian changelog
+/// entry, split into the package, the version and the rest.
+fn debian_entry_header(line: &str) -> Option<(&str, &str, &str)> {
+    if line.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (package, rest) = line.split_once(" (")?;
+    let (version, rest) = rest.split_once(')')?;
+    Some((package, version, rest.trim()))
+}
+
+/// Prepend an entry for the new upstream version to debian/changelog, in the format
+/// `dch` writes. The Debian revision restarts at `-1` unless the package is native, an
+/// epoch is kept, and pre-releases sort first as `1.2.0~rc1`. The maintainer is taken
+/// from `DEBFULLNAME`/`DEBEMAIL`, then git, then the previous entry.
+pub fn debian_changelog(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let upstream = package_version(version)?;
+    let Some((package, current, rest)) = content
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .and_then(debian_entry_header)
+    else {
+        return Err(BumpError::ParseError(format!(
+            "no 'package (version) distribution; urgency=...' entry at the top of {}",
+            path.display()
+        )));
+    };
+
+    let (epoch, current) = current
+        .split_once(':')
+        .map_or(("", current), |(epoch, rest)| {
+            (&current[..=epoch.len()], rest)
+        });
+    let (current_upstream, revision) = match current.rsplit_once('-') {
+        Some((current_upstream, _)) => (current_upstream, "-1"),
+        None => (current, ""),
+    };
+    if current_upstream == upstream {
+        println!("{} already has an entry for {upstream}", path.display());
+        return Ok(());
+    }
+
+    let maintainer = packager(&["DEBFULLNAME", "NAME"], &["DEBEMAIL", "EMAIL"])
+        .or_else(|| {
+            content.lines().find_map(|line| {
+                let trailer = line.strip_prefix(" -- ")?;
+                Some(trailer.split_once("  ")?.0.to_string())
+            })
+        })
+        .ok_or_else(|| {
+            BumpError::LogicError(format!(
+                "no maintainer for the new entry in {}; set DEBFULLNAME and DEBEMAIL",
+                path.display()
+            ))
+        })?;
+    let date = release_time()?.to_rfc2822();
+    let entry = format!(
+        "{package} ({epoch}{upstream}{revision}) {rest}\n\n  * New upstream release {upstream}.\n\n -- {maintainer}  {date}\n\n"
+    );
+    write_file(path, entry + &content)?;
+    println!(
+        "{} updated with an entry for {epoch}{upstream}{revision}",
+        path.display()
+    );
+    Ok(())
+}
+
+/// The value of a `Tag: value` line in an RPM spec (tags are case-insensitive), or
+/// `None` when the line sets another tag or takes the value from a macro.
+fn spec_tag<'a>(line: &'a str, tag: &str) -> Option<&'a str> {
+    let (name, value) = line.split_once(':')?;
+    if !name.trim_end().eq_ignore_ascii_case(tag) || name.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let value = value.trim();
+    (!value.is_empty() && !value.starts_with('%')).then_some(value)
+}
+
+/// Set `Version:` in an RPM spec, restart `Release:` at 1 (keeping a `%{?dist}` tail)
+/// and add a `%changelog` entry. Pre-releases sort first as `1.2.0~rc1`; the packager
+/// comes from `RPM_PACKAGER` (`Name <email>`), then git, then the previous entry.
+pub fn rpm_spec(version: &Version, path: &Path) -> Result<(), BumpError> {
+    let content = fs::read_to_string(path).map_err(BumpError::IoError)?;
+    let rpm_version = package_version(version)?;
+    let Some(current) = content.lines().find_map(|line| spec_tag(line, "Version")) else {
+        return Err(BumpError::ParseError(format!(
+            "no literal Version: tag in {}",
+            path.display()
+        )));
+    };
+    if current == rpm_version {
+        println!("{} already at version {rpm_version}", path.display());
+        return Ok(());
+    }
+
+    let mut output: String = content
+        .split_inclusive('\n')
+        .map(|line| {
+            if let Some(value) = spec_tag(line, "Version") {
+                return line.replacen(value, &rpm_version, 1);
+            }
+            let Some(value) = spec_tag(line, "Release") else {
+                return line.to_string();
+            };
+            let number = value.len() - value.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if number == 0 {
+                return line.to_string();
+            }
+            line.replacen(value, &format!("1{}", &value[number..]), 1)
+        })
+        .collect();
+
+    let previous_entry = content
+        .split_once("%changelog")
+        .and_then(|(_, entries)| entries.lines().find_map(|line| line.strip_prefix("* ")));
+    let packager = std::env::var("RPM_PACKAGER")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .or_else(|| packager(&[], &[]))
+        .or_else(|| {
+            // `* Mon Jan 02 2006 Name <email> - 1.0-1`: the packager follows the date.
+            let entry = previous_entry?.splitn(5, ' ').nth(4)?;
+            Some(
+                entry
+                    .rsplit_once(" - ")
+                    .map_or(entry, |(who, _)| who)
+                    .to_string(),
+            )
+        })
+        .ok_or_else(|| {
+            BumpError::LogicError(format!(
+                "no packager for the %changelog entry in {}; set RPM_PACKAGER",
+                path.display()
+            ))
+        })?;
+    let date = release_time()?.format("%a %b %d %Y");
+    let entry = format!("* {date} {packager} - {rpm_version}-1\n- Update to {rpm_version}\n\n");
+    match output.find("%changelog\n") {
+        Some(i) => output.insert_str(i + "%changelog\n".len(), &entry),
+        None => {
+            if !output.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push_str(&format!("\n%changelog\n{}", entry.trim_end_matches('\n')));
+            output.push('\n');
+        }
     }
+    write_file(path, output)?;
+    println!("{} updated to version {rpm_version}", path.display());
     Ok(())
 }
@@ -1,6 +1,8 @@
 use crate::bump::{BumpError, BumpType};
 use chrono::Datelike;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -82,10 +84,74 @@ impl LabelPosition {
     }
 }
 
+/// `[timestamp].clock`: the time zone CalVer dates and `last` are read in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Clock {
+    #[default]
+    Utc,
+    /// The system time zone, following its daylight saving rules.
+    Local,
+    /// A fixed UTC offset such as `-05:00`, which ignores daylight saving.
+    Offset(chrono::FixedOffset),
+}
+
+impl Clock {
+    const fn is_utc(&self) -> bool {
+        matches!(self, Self::Utc)
+    }
+
+    /// The date of `now` on this clock, and `now` as `format`ted there.
+    pub fn read(
+        self,
+        now: chrono::DateTime<chrono::Utc>,
+        format: &str,
+    ) -> (chrono::NaiveDate, String) {
+        match self {
+            Self::Utc => (now.date_naive(), now.format(format).to_string()),
+            Self::Local => {
+                let local = now.with_timezone(&chrono::Local);
+                (local.date_naive(), local.format(format).to_string())
+            }
+            Self::Offset(offset) => {
+                let shifted = now.with_timezone(&offset);
+                (shifted.date_naive(), shifted.format(format).to_string())
+            }
+        }
+    }
+}
+
+impl TryFrom<String> for Clock {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "utc" => Ok(Self::Utc),
+            "local" => Ok(Self::Local),
+            offset => offset.parse().map(Self::Offset).map_err(|_| {
+                format!("[timestamp].clock = '{offset}' is not 'utc', 'local' or an offset such as '-05:00'")
+            }),
+        }
+    }
+}
+
+impl From<Clock> for String {
+    fn from(clock: Clock) -> Self {
+        match clock {
+            Clock::Utc => "utc".to_string(),
+            Clock::Local => "local".to_string(),
+            Clock::Offset(offset) => offset.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Timestamp {
     pub format: String,
     pub last: String,
+    /// The clock read by `bump --calendar` and for `last`; UTC when unset.
+    #[serde(default, skip_serializing_if = "Clock::is_utc")]
+    pub clock: Clock,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -103,6 +169,67 @@ pub struct Base {
     #[serde(alias = "day")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub patch: Option<u32>,
+
+    /// CalVer components, such as `%Y.%q`; see [`CalendarField`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Length in weeks of the `%t` release train.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period_weeks: Option<u32>,
+}
+
+/// One CalVer component of `[base].format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarField {
+    /// `%Y`
+    Year,
+    /// `%m`, printed zero-padded.
+    Month,
+    /// `%d`, printed zero-padded.
+    Day,
+    /// `%q`, 1-4.
+    Quarter,
+    /// `%t`, the 1-based `period_weeks`-week train of the year, counted from January 1.
+    Train,
+}
+
+impl CalendarField {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            "%Y" => Some(Self::Year),
+            "%m" => Some(Self::Month),
+            "%d" => Some(Self::Day),
+            "%q" => Some(Self::Quarter),
+            "%t" => Some(Self::Train),
+            _ => None,
+        }
+    }
+
+    /// Whether the printed value is zero-padded to two digits.
+    pub const fn padded(self) -> bool {
+        matches!(self, Self::Month | Self::Day)
+    }
+
+    fn range(self, period_weeks: u32) -> std::ops::RangeInclusive<u32> {
+        match self {
+            Self::Year => 0..=9999,
+            Self::Month => 1..=12,
+            Self::Day => 1..=31,
+            Self::Quarter => 1..=4,
+            Self::Train => 1..=365 / (7 * period_weeks) + 1,
+        }
+    }
+
+    fn value(self, date: chrono::NaiveDate, period_weeks: u32) -> u32 {
+        match self {
+            Self::Year => date.year().cast_unsigned(),
+            Self::Month => date.month(),
+            Self::Day => date.day(),
+            Self::Quarter => date.quarter(),
+            Self::Train => date.ordinal0() / (7 * period_weeks) + 1,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -117,6 +244,18 @@ pub struct Phase {
 pub struct Suffix {
     pub mode: SuffixMode,
     pub separator: String,
+    /// Used by `branch` mode on a detached HEAD; the short sha when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detached: Option<String>,
+    /// Rewrite `branch` payloads into valid SemVer build-metadata identifiers.
+    #[serde(default = "Suffix::default_sanitize")]
+    pub sanitize: bool,
+}
+
+impl Suffix {
+    const fn default_sanitize() -> bool {
+        true
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -124,6 +263,299 @@ pub struct Label {
     pub position: LabelPosition,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyKind {
+    Slack,
+    Discord,
+    #[default]
+    Generic,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notify {
+    pub url: String,
+    #[serde(default)]
+    pub kind: NotifyKind,
+    #[serde(default = "Notify::default_message")]
+    pub message: String,
+}
+
+impl Notify {
+    fn default_message() -> String {
+        "{event} {version}".to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Hooks {
+    #[serde(default)]
+    pub pre_bump: Vec<String>,
+    #[serde(default)]
+    pub post_bump: Vec<String>,
+    #[serde(default)]
+    pub pre_tag: Vec<String>,
+    #[serde(default)]
+    pub post_tag: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Publish {
+    #[serde(default)]
+    pub commands: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Issues {
+    /// Project keys, each matching `KEY-<number>`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<String>,
+    /// Regular expressions for other reference styles, such as `#\d+`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WasmTarget {
+    pub target: String,
+    pub module: String,
+}
+
+/// How `[version_code]` derives the monotonic integer app stores require.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeStrategy {
+    /// `major * 1000000 + minor * 10000 + patch * 100 + phase distance`.
+    Positional,
+    /// Number of commits reachable from HEAD.
+    Commits,
+    /// Stored in `last` and incremented on every bump.
+    Counter,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionCode {
+    pub strategy: CodeStrategy,
+    /// The code recorded at the last bump; derived codes may never go below it.
+    #[serde(default)]
+    pub last: u32,
+}
+
+/// What `[msi]` does with a phase (release candidate) in the three-part ProductVersion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MsiCandidate {
+    /// `major.minor.patch`; candidates share their release's ProductVersion.
+    #[default]
+    Drop,
+    /// `major.minor.(patch * 100 + distance)`, with releases at distance 99.
+    Build,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Msi {
+    #[serde(default)]
+    pub candidate: MsiCandidate,
+    /// Stable per-product GUID; also seeds the generated ProductCode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgrade_code: Option<String>,
+    /// Emit a ProductCode GUID derived from `upgrade_code` and the version.
+    #[serde(default)]
+    pub product_code: bool,
+}
+
+/// Where `[rc]` takes the fourth field of a Windows VERSIONINFO quadruple from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RcBuild {
+    /// The phase distance of a pre-release, 0 for a release.
+    #[default]
+    Candidate,
+    /// The `[version_code]` build number.
+    Counter,
+    /// Always 0.
+    Zero,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Rc {
+    #[serde(default)]
+    pub file_build: RcBuild,
+    #[serde(default)]
+    pub product_build: RcBuild,
+}
+
+/// A Chart.yaml field `bump update` sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HelmField {
+    /// The chart's own SemVer version.
+    Version,
+    /// The version of the application the chart deploys.
+    AppVersion,
+}
+
+impl HelmField {
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Version => "version",
+            Self::AppVersion => "appVersion",
+        }
+    }
+}
+
+/// `[docker]`: the docker-compose images `bump update` retags.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Docker {
+    /// Image repositories such as `ghcr.io/acme/*`; `*` matches any run of characters.
+    #[serde(default)]
+    pub images: Vec<String>,
+    /// Tag template with the `bump artifact-name` placeholders.
+    #[serde(default = "Docker::default_tag")]
+    pub tag: String,
+}
+
+impl Docker {
+    fn default_tag() -> String {
+        "{tag}".to_string()
+    }
+}
+
+/// `[markdown]`: where `bump update` rewrites the version in Markdown docs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Markdown {
+    /// Text around the version, with the `bump artifact-name` placeholders marking it,
+    /// such as `cargo install bump --version {version}`.
+    #[serde(default = "Markdown::default_patterns")]
+    pub patterns: Vec<String>,
+}
+
+impl Markdown {
+    fn default_patterns() -> Vec<String> {
+        vec!["img.shields.io/badge/version-{version}-".to_string()]
+    }
+}
+
+impl Default for Markdown {
+    fn default() -> Self {
+        Self {
+            patterns: Self::default_patterns(),
+        }
+    }
+}
+
+/// Which Chart.yaml fields `bump update` sets; both by default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Helm {
+    #[serde(default = "Helm::all_fields")]
+    pub fields: Vec<HelmField>,
+}
+
+impl Helm {
+    fn all_fields() -> Vec<HelmField> {
+        vec![HelmField::Version, HelmField::AppVersion]
+    }
+}
+
+impl Default for Helm {
+    fn default() -> Self {
+        Self {
+            fields: Self::all_fields(),
+        }
+    }
+}
+
+/// Floating tags and pointer files `bump tag` moves to every release.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Aliases {
+    /// Tag names; `{prefix}`, `{major}`, `{minor}`, `{patch}` and `{version}` expand.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Files rewritten with the release tag, relative to the bumpfile.
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+/// `[update]`: files rewritten on every formal bump without a dedicated updater.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Update {
+    #[serde(default)]
+    pub file: Vec<UpdateFile>,
+}
+
+/// One `[[update.file]]`: `search` rendered with the version before the bump is
+/// replaced by `replace` rendered with the new one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateFile {
+    /// Relative to the bumpfile.
+    pub path: String,
+    #[serde(default = "UpdateFile::default_template")]
+    pub search: String,
+    #[serde(default = "UpdateFile::default_template")]
+    pub replace: String,
+}
+
+impl UpdateFile {
+    fn default_template() -> String {
+        "{version}".to_string()
+    }
+}
+
+/// `[integrity]`: a checksum over the version fields that bump rewrites on every save,
+/// so `bump verify --integrity` can detect hand edits.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Integrity {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Environment variable holding an HMAC key; without it the checksum is a plain
+    /// SHA-256.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_env: Option<String>,
+}
+
+/// `[history]`: an append-only journal of bumps and tags for `bump history`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    /// JSON Lines file, relative to the bumpfile.
+    #[serde(default = "History::default_file")]
+    pub file: String,
+}
+
+impl History {
+    fn default_file() -> String {
+        "bump.history.jsonl".to_string()
+    }
+}
+
+/// `[guard]`: which changes require a version bump for `bump guard`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Guard {
+    /// Patterns relative to the bumpfile's directory; `*` matches any run of
+    /// characters. Empty means any change.
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+/// Which tag counts as the latest release for changelogs, `[issues]` and `bump auto`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatestTag {
+    /// The nearest tag reachable from HEAD (`git describe`).
+    #[default]
+    Nearest,
+    /// The highest version, by precedence, among the tags matching the prefix.
+    Highest,
+}
+
+/// `[tags]`: how bump reads the release tags of the repository.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Tags {
+    #[serde(default)]
+    pub latest: LatestTag,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Version {
     pub prefix: String,
@@ -132,9 +564,80 @@ pub struct Version {
     pub suffix: Suffix,
     pub timestamp: Timestamp,
     pub label: Label,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub branch_prefix: BTreeMap<String, String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<Notify>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish: Option<Publish>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<Hooks>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issues: Option<Issues>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wasm: Vec<WasmTarget>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_code: Option<VersionCode>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msi: Option<Msi>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rc: Option<Rc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub helm: Option<Helm>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docker: Option<Docker>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<Markdown>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<Aliases>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guard: Option<Guard>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<Integrity>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<History>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Tags>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update: Option<Update>,
+}
+
+fn increment(value: u32, component: &str) -> Result<u32, BumpError> {
+    value.checked_add(1).ok_or_else(|| {
+        BumpError::LogicError(format!(
+            "{component} is already at its maximum ({value}); cannot bump"
+        ))
+    })
 }
 
 impl Version {
+    /// The phase distance of a pre-release, or 0 for a release.
+    pub fn build_number(&self) -> u32 {
+        if self.phase.name.is_empty() {
+            0
+        } else {
+            self.phase.distance
+        }
+    }
+
     fn right_mode(&self, expected_mode: VersionMode) -> Result<(), BumpError> {
         if self.base.mode == expected_mode {
             Ok(())
@@ -146,57 +649,285 @@ impl Version {
         }
     }
 
+    /// The CalVer components: `[base].format` split on the delimiter, or year, month
+    /// and day without a format.
+    pub fn calendar_fields(&self) -> Result<Vec<CalendarField>, String> {
+        let Some(format) = &self.base.format else {
+            return Ok(vec![
+                CalendarField::Year,
+                CalendarField::Month,
+                CalendarField::Day,
+            ]);
+        };
+        let fields = format
+            .split(&self.base.delimiter)
+            .map(|token| {
+                CalendarField::parse(token).ok_or_else(|| {
+                    format!("[base].format token '{token}' is not one of %Y, %m, %d, %q, %t")
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if fields.first() != Some(&CalendarField::Year) || fields.len() > 3 {
+            return Err(format!(
+                "[base].format '{format}' must start with %Y and have at most three components"
+            ));
+        }
+        if fields.contains(&CalendarField::Train) && self.base.period_weeks.unwrap_or(0) == 0 {
+            return Err("[base].format uses %t, which needs [base].period_weeks".to_string());
+        }
+        Ok(fields)
+    }
+
+    /// Reject delimiters, separators and prefixes with characters that would make the
+    /// printed version invalid in this mode, such as spaces or `#`.
+    fn validate_characters(&self) -> Result<(), String> {
+        let mode = self.base.mode.as_str();
+        let (base, separator, delimiter, suffix) = match self.base.mode {
+            VersionMode::Semver => (".", "-", ".-", "+-"),
+            VersionMode::Calver => (".-_", ".-_", ".-_", "+-._"),
+        };
+        let fields = [
+            ("[base].delimiter", &self.base.delimiter, base, true),
+            ("[phase].separator", &self.phase.separator, separator, true),
+            ("[phase].delimiter", &self.phase.delimiter, delimiter, false),
+            ("[suffix].separator", &self.suffix.separator, suffix, true),
+        ];
+        for (field, value, allowed, required) in fields {
+            let list = allowed
+                .chars()
+                .map(|c| format!("'{c}'"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            if required && value.is_empty() {
+                return Err(format!("{field} is empty; use {list}"));
+            }
+            if let Some(c) = value.chars().find(|c| !allowed.contains(*c)) {
+                return Err(format!(
+                    "{field} = \"{value}\" contains '{c}', which {mode} does not allow there; use {list}"
+                ));
+            }
+        }
+        let prefixes = std::iter::once(("prefix".to_string(), &self.prefix)).chain(
+            self.branch_prefix
+                .iter()
+                .map(|(branch, prefix)| (format!("[branch_prefix].\"{branch}\""), prefix)),
+        );
+        for (field, prefix) in prefixes {
+            if let Some(c) = prefix
+                .chars()
+                .find(|c| !c.is_ascii_alphanumeric() && !"-_./@".contains(*c))
+            {
+                return Err(format!(
+                    "{field} = \"{prefix}\" contains '{c}'; use letters, digits, '-', '_', '.', '/' or '@'"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject delimiters and prefixes with characters this mode does not allow, and
+    /// component values no valid version of this mode can hold.
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_characters()?;
+        if self.base.mode != VersionMode::Calver {
+            return Ok(());
+        }
+        let fields = self.calendar_fields()?;
+        let period_weeks = self.base.period_weeks.unwrap_or(1);
+        let values = [Some(self.base.major), self.base.minor, self.base.patch];
+        for ((name, value), field) in ["year", "month", "day"].iter().zip(values).zip(&fields) {
+            let range = field.range(period_weeks);
+            if let Some(value) = value.filter(|value| !range.contains(value)) {
+                return Err(format!(
+                    "[base].{name} = {value} is out of range ({}..={}) for calver",
+                    range.start(),
+                    range.end()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Switch the bumpfile to `mode`. CalVer starts from today's date; SemVer keeps the
+    /// CalVer numbers (the year becomes major) so later releases still sort after the
+    /// last CalVer one. The phase is cleared either way.
+    pub fn convert(&mut self, mode: VersionMode) -> Result<(), BumpError> {
+        if self.base.mode == mode {
+            return Err(BumpError::LogicError(format!(
+                "the bumpfile is already {}",
+                mode.as_str()
+            )));
+        }
+        self.clear_phase();
+        self.base.mode = mode;
+        match mode {
+            VersionMode::Calver => {
+                self.base.major = 0;
+                self.base.minor = Some(0);
+                self.base.patch = Some(0);
+                self.bump(&BumpType::Calendar)
+            }
+            VersionMode::Semver => {
+                self.base.format = None;
+                self.base.period_weeks = None;
+                self.timestamp.last = self
+                    .timestamp
+                    .clock
+                    .read(chrono::Utc::now(), &self.timestamp.format)
+                    .1;
+                Ok(())
+            }
+        }
+    }
+
     fn clear_phase(&mut self) {
         self.phase.name = String::new();
         self.phase.distance = 0;
     }
 
+    /// Set the prefix, base and phase from a version string such as `v1.0.0-alpha.1`,
+    /// split with this version's delimiters and separators. The prefix is kept when
+    /// `text` starts with a digit; missing minor or patch components are dropped.
+    pub fn set_from_str(&mut self, text: &str) -> Result<(), BumpError> {
+        let invalid =
+            |reason: &str| BumpError::ParseError(format!("invalid version '{text}': {reason}"));
+        if !self.suffix.separator.is_empty() && text.contains(&self.suffix.separator) {
+            return Err(invalid("the suffix is computed from git and cannot be set"));
+        }
+        let start = text
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(|| invalid("no numeric version found"))?;
+        let (prefix, rest) = text.split_at(start);
+        let (base, phase) = match rest.split_once(&self.phase.separator) {
+            Some((base, phase)) if !self.phase.separator.is_empty() => (base, Some(phase)),
+            _ => (rest, None),
+        };
+
+        let numbers = base
+            .split(&self.base.delimiter)
+            .map(|part| {
+                part.parse::<u32>()
+                    .map_err(|_| invalid(&format!("'{part}' is not a number")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if numbers.len() > 3 {
+            return Err(invalid("expected at most three components"));
+        }
+        let (name, distance) = match phase {
+            None => (String::new(), 0),
+            Some(phase) => phase
+                .rsplit_once(&self.phase.delimiter)
+                .and_then(|(name, distance)| Some((name.to_string(), distance.parse().ok()?)))
+                .unwrap_or_else(|| (phase.to_string(), 0)),
+        };
+        if phase.is_some() && name.is_empty() {
+            return Err(invalid("the phase needs a name"));
+        }
+
+        if !prefix.is_empty() {
+            self.prefix = prefix.to_string();
+        }
+        self.base.major = numbers[0];
+        self.base.minor = numbers.get(1).copied();
+        self.base.patch = numbers.get(2).copied();
+        self.phase.name = name;
+        self.phase.distance = distance;
+        Ok(())
+    }
+
+    /// `tag` read as a release of this bumpfile, or `None` when it has another prefix or
+    /// component count (alias tags such as `v1` or `latest`).
+    pub fn parse_tag(&self, tag: &str) -> Option<Version> {
+        let rest = tag.strip_prefix(self.prefix.as_str())?;
+        if !rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let mut version = self.clone();
+        version.set_from_str(tag).ok()?;
+        (version.base.minor.is_some() == self.base.minor.is_some()
+            && version.base.patch.is_some() == self.base.patch.is_some())
+        .then_some(version)
+    }
+
+    /// Order by release precedence: the base numbers, then a release above its
+    /// pre-releases, then the phase name and distance.
+    pub fn cmp_precedence(&self, other: &Version) -> Ordering {
+        let base = |v: &Version| (v.base.major, v.base.minor, v.base.patch);
+        base(self)
+            .cmp(&base(other))
+            .then_with(|| {
+                let release = |v: &Version| v.phase.name.is_empty();
+                release(self).cmp(&release(other))
+            })
+            .then_with(|| self.phase.name.cmp(&other.phase.name))
+            .then_with(|| self.phase.distance.cmp(&other.phase.distance))
+    }
+
     pub fn bump(&mut self, bump_type: &BumpType) -> Result<(), BumpError> {
-        let now = chrono::Utc::now();
+        // One instant, read on one clock, for both the CalVer date and `last`.
+        let (today, now) = self
+            .timestamp
+            .clock
+            .read(chrono::Utc::now(), &self.timestamp.format);
         match bump_type {
             BumpType::Major => {
                 self.right_mode(VersionMode::Semver)?;
-                self.base.major += 1;
+                self.base.major = increment(self.base.major, "major")?;
                 self.base.minor = self.base.minor.map(|_| 0);
                 self.base.patch = self.base.patch.map(|_| 0);
                 self.clear_phase();
             }
             BumpType::Minor => {
                 self.right_mode(VersionMode::Semver)?;
-                self.base.minor = self.base.minor.map(|m| m + 1);
+                self.base.minor = self.base.minor.map(|m| increment(m, "minor")).transpose()?;
                 self.base.patch = self.base.patch.map(|_| 0);
                 self.clear_phase();
             }
             BumpType::Patch => {
                 self.right_mode(VersionMode::Semver)?;
-                self.base.patch = self.base.patch.map(|p| p + 1);
+                self.base.patch = self.base.patch.map(|p| increment(p, "patch")).transpose()?;
                 self.clear_phase();
             }
             BumpType::Phase(cli_phase_name) => {
                 if cli_phase_name == &self.phase.name {
-                    self.phase.distance += 1;
+                    self.phase.distance = increment(self.phase.distance, "phase distance")?;
                 } else if *cli_phase_name != "__increment__" {
                     self.phase.name.clone_from(cli_phase_name);
                     self.phase.distance = 1;
                 } else {
-                    self.phase.distance += 1;
+                    self.phase.distance = increment(self.phase.distance, "phase distance")?;
                 }
             }
             BumpType::Calendar => {
                 self.right_mode(VersionMode::Calver)?;
-                if now.year().cast_unsigned() == self.base.major
-                    && now.month() == self.base.minor.unwrap_or(0)
-                    && now.day() == self.base.patch.unwrap_or(0)
-                {
-                    self.phase.distance += 1;
+                let fields = self.calendar_fields().map_err(BumpError::LogicError)?;
+                let period_weeks = self.base.period_weeks.unwrap_or(1);
+                let today: Vec<u32> = fields
+                    .iter()
+                    .map(|field| field.value(today, period_weeks))
+                    .collect();
+                // Without a format, only the components present in the bumpfile are kept.
+                let (minor, patch) = if self.base.format.is_some() {
+                    (today.get(1).copied(), today.get(2).copied())
+                } else {
+                    (
+                        self.base.minor.map(|_| today[1]),
+                        self.base.patch.map(|_| today[2]),
+                    )
+                };
+                let major = today[0];
+                if (major, minor, patch) == (self.base.major, self.base.minor, self.base.patch) {
+                    // Another release in the same period: bump the conflict revision.
+                    self.phase.distance = increment(self.phase.distance, "phase distance")?;
                 } else {
-                    self.base.major = now.year().cast_unsigned();
-                    self.base.minor = self.base.minor.map(|_| now.month());
-                    self.base.patch = self.base.patch.map(|_| now.day());
+                    self.base.major = major;
+                    self.base.minor = minor;
+                    self.base.patch = patch;
+                    self.phase.distance = 0;
                 }
             }
         }
-        self.timestamp.last = now.format(&self.timestamp.format).to_string();
+        self.timestamp.last = now;
         Ok(())
     }
 }
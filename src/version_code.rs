@@ -0,0 +1,103 @@
+use crate::bump::{BumpError, is_git_repository, run_git};
+use crate::version::{CodeStrategy, Version};
+
+/// Google Play's upper bound for versionCode.
+const MAX_CODE: u32 = 2_100_000_000;
+
+fn positional(version: &Version) -> Result<u32, BumpError> {
+    let minor = version.base.minor.unwrap_or(0);
+    let patch = version.base.patch.unwrap_or(0);
+    let build = version.build_number();
+    for (name, value) in [("minor", minor), ("patch", patch), ("build", build)] {
+        if value > 99 {
+            return Err(BumpError::LogicError(format!(
+                "positional versionCode needs {name} <= 99, found {value}"
+            )));
+        }
+    }
+    let code = u64::from(version.base.major) * 1_000_000
+        + u64::from(minor) * 10_000
+        + u64::from(patch) * 100
+        + u64::from(build);
+    u32::try_from(code)
+        .ok()
+        .filter(|code| *code <= MAX_CODE)
+        .ok_or_else(|| {
+            BumpError::LogicError(format!("versionCode {code} exceeds the maximum {MAX_CODE}"))
+        })
+}
+
+fn commits() -> Result<u32, BumpError> {
+    if !is_git_repository() {
+        return Err(BumpError::LogicError(
+            "versionCode strategy 'commits' needs a git repository".to_string(),
+        ));
+    }
+    let count = run_git("rev-list --count HEAD")?;
+    count
+        .parse()
+        .map_err(|e| BumpError::Git(format!("git rev-list --count HEAD: {e}")))
+}
+
+fn derive(version: &Version, strategy: CodeStrategy, last: u32) -> Result<u32, BumpError> {
+    match strategy {
+        CodeStrategy::Positional => positional(version),
+        CodeStrategy::Commits => commits(),
+        CodeStrategy::Counter => Ok(last),
+    }
+}
+
+fn check_not_lower(code: u32, floor: u32, what: &str) -> Result<u32, BumpError> {
+    if code < floor {
+        return Err(BumpError::LogicError(format!(
+            "versionCode {code} is lower than {what} {floor}; versionCode must never decrease"
+        )));
+    }
+    Ok(code)
+}
+
+/// The `[version_code]` for `version`, or `None` when the bumpfile has none.
+pub fn current(version: &Version) -> Result<Option<u32>, BumpError> {
+    let Some(config) = &version.version_code else {
+        return Ok(None);
+    };
+    let code = derive(version, config.strategy, config.last)?;
+    check_not_lower(code, config.last, "the last recorded").map(Some)
+}
+
+/// Record the code of a freshly bumped version in `[version_code].last`; the counter
+/// strategy advances by one.
+pub fn advance(version: &mut Version) -> Result<(), BumpError> {
+    let Some(config) = version.version_code.clone() else {
+        return Ok(());
+    };
+    let code = match config.strategy {
+        CodeStrategy::Counter => config.last.checked_add(1).ok_or_else(|| {
+            BumpError::LogicError("versionCode counter is at its maximum".to_string())
+        })?,
+        strategy => derive(version, strategy, config.last)?,
+    };
+    let code = check_not_lower(code, config.last, "the last recorded")?;
+    if let Some(config) = &mut version.version_code {
+        config.last = code;
+    }
+    Ok(())
+}
+
+/// The code to write into a file currently holding `file_code`. Without
+/// `[version_code]`, the file's own code goes up by one when its version name changes.
+pub fn for_file(
+    version: &Version,
+    name_changed: bool,
+    file_code: Option<u32>,
+) -> Result<u32, BumpError> {
+    match (current(version)?, file_code) {
+        (Some(code), Some(old)) => check_not_lower(code, old, "the file's current"),
+        (Some(code), None) => Ok(code),
+        (None, Some(old)) if name_changed => old
+            .checked_add(1)
+            .ok_or_else(|| BumpError::LogicError("versionCode is at its maximum".to_string())),
+        (None, Some(old)) => Ok(old),
+        (None, None) => Ok(1),
+    }
+}
@@ -0,0 +1,97 @@
+use crate::bump::BumpError;
+use crate::print::glob_matches;
+use crate::version::{Version, WasmTarget};
+use std::path::Path;
+
+/// The `[[wasm]]` entry whose `target` pattern matches `path` or its file name.
+pub fn target_for<'a>(version: &'a Version, path: &Path) -> Option<&'a WasmTarget> {
+    let full = path.to_string_lossy();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    version
+        .wasm
+        .iter()
+        .find(|target| glob_matches(&target.target, &full) || glob_matches(&target.target, &name))
+}
+
+/// Call `export` in the WASM module at `module_path` with `input`, returning its output.
+///
+/// Plugin ABI: the module exports `memory`, `alloc(len: i32) -> i32`, and the called
+/// function `(ptr: i32, len: i32) -> i64`, whose result packs the output pointer in the
+/// high 32 bits and the output length in the low 32 bits. Input and output are UTF-8.
+/// No host functions are imported, so plugins cannot touch the filesystem or network.
+#[cfg(feature = "wasm")]
+pub fn call(module_path: &Path, export: &str, input: &str) -> Result<String, BumpError> {
+    use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+    // Enough for any sane formatter; stops runaway plugins instead of hanging the build.
+    const FUEL: u64 = 1_000_000_000;
+    // Linear memory a plugin may grow to; `memory.grow` fails beyond it.
+    const MAX_MEMORY: usize = 64 << 20;
+
+    let plugin_error = |e: wasmtime::Error| {
+        BumpError::LogicError(format!("wasm plugin {}: {e}", module_path.display()))
+    };
+
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config).map_err(plugin_error)?;
+    let module = Module::from_file(&engine, module_path).map_err(plugin_error)?;
+    let limits: StoreLimits = StoreLimitsBuilder::new()
+        .memory_size(MAX_MEMORY)
+        .instances(1)
+        .build();
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits| limits);
+    store.set_fuel(FUEL).map_err(plugin_error)?;
+    let instance = Instance::new(&mut store, &module, &[]).map_err(plugin_error)?;
+
+    let memory = instance
+        .get_memory(&mut store, "memory")
+        .ok_or_else(|| plugin_error(wasmtime::Error::msg("missing 'memory' export")))?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&mut store, "alloc")
+        .map_err(plugin_error)?;
+    let func = instance
+        .get_typed_func::<(i32, i32), i64>(&mut store, export)
+        .map_err(plugin_error)?;
+
+    let len = i32::try_from(input.len())
+        .map_err(|_| plugin_error(wasmtime::Error::msg("input too large")))?;
+    let ptr = alloc.call(&mut store, len).map_err(plugin_error)?;
+    memory
+        .write(&mut store, ptr.cast_unsigned() as usize, input.as_bytes())
+        .map_err(|e| plugin_error(e.into()))?;
+
+    let packed = func
+        .call(&mut store, (ptr, len))
+        .map_err(plugin_error)?
+        .cast_unsigned();
+    let out_ptr = (packed >> 32) as usize;
+    let out_len = (packed & 0xffff_ffff) as usize;
+    // The plugin picks the length; check it against its memory before allocating.
+    if out_ptr
+        .checked_add(out_len)
+        .is_none_or(|end| end > memory.data_size(&store))
+    {
+        return Err(plugin_error(wasmtime::Error::msg(format!(
+            "output {out_ptr}+{out_len} is outside the plugin's memory"
+        ))));
+    }
+    let mut output = vec![0; out_len];
+    memory
+        .read(&store, out_ptr, &mut output)
+        .map_err(|e| plugin_error(e.into()))?;
+    String::from_utf8(output)
+        .map_err(|_| plugin_error(wasmtime::Error::msg("output is not valid UTF-8")))
+}
+
+#[cfg(not(feature = "wasm"))]
+pub fn call(module_path: &Path, _export: &str, _input: &str) -> Result<String, BumpError> {
+    Err(BumpError::LogicError(format!(
+        "'{}' needs WASM plugin support; rebuild bump with '--features wasm'",
+        module_path.display()
+    )))
+}
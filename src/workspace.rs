@@ -0,0 +1,286 @@
+use crate::bump::{
+    BumpError, BumpType, bump_bumpfile, get_git_latest_tag, git_cmd, git_tag_exists,
+    is_git_repository, resolve_path,
+};
+use crate::bumpfile::BumpFile;
+use crate::print::{self, PrintOptions};
+use clap::ArgMatches;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Strategy {
+    /// Each member has its own `bump.toml` and version.
+    #[default]
+    Independent,
+    /// Every member shares the version held by the root bumpfile.
+    Fixed,
+}
+
+/// How far a bump propagates to the members that depend on the bumped one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cascade {
+    None,
+    #[default]
+    Patch,
+    Minor,
+    Major,
+}
+
+/// `[workspace]` in a root bumpfile: member directories and how they are versioned.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Workspace {
+    pub members: Vec<String>,
+    #[serde(default)]
+    pub strategy: Strategy,
+    /// Member name -> names of the members it depends on.
+    #[serde(default)]
+    pub depends: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub cascade: Cascade,
+}
+
+#[derive(Deserialize)]
+struct Root {
+    workspace: Option<Workspace>,
+}
+
+pub struct Member {
+    /// Last component of the member directory (`crates/core` -> `core`).
+    pub name: String,
+    pub dir: PathBuf,
+    pub bumpfile: BumpFile,
+}
+
+fn member_name(entry: &str) -> String {
+    Path::new(entry).file_name().map_or_else(
+        || entry.to_string(),
+        |name| name.to_string_lossy().to_string(),
+    )
+}
+
+/// Read `[workspace]` from the root bumpfile; with the independent strategy the root
+/// needs no version of its own.
+pub fn load(root: &Path) -> Result<Workspace, BumpError> {
+    let content = fs::read_to_string(root).map_err(BumpError::IoError)?;
+    let parsed: Root = toml::from_str(&content).map_err(|e| {
+        BumpError::ParseError(format!("invalid [workspace] in {}: {e}", root.display()))
+    })?;
+    let workspace = parsed.workspace.ok_or_else(|| {
+        BumpError::LogicError(format!(
+            "--member/--all need a [workspace] section in {}",
+            root.display()
+        ))
+    })?;
+    let names: Vec<String> = workspace.members.iter().map(|m| member_name(m)).collect();
+    for (name, deps) in &workspace.depends {
+        if let Some(unknown) = std::iter::once(name)
+            .chain(deps)
+            .find(|dep| !names.contains(dep))
+        {
+            return Err(BumpError::LogicError(format!(
+                "[workspace.depends] names '{unknown}', which is not a member in {}",
+                root.display()
+            )));
+        }
+    }
+    Ok(workspace)
+}
+
+/// Whether the command targets workspace members (`--member` or `--all`).
+pub fn is_selected(matches: &ArgMatches) -> bool {
+    matches.get_flag("all") || matches.contains_id("member")
+}
+
+fn root_path(matches: &ArgMatches) -> PathBuf {
+    resolve_path(
+        matches
+            .get_one::<String>("bumpfile")
+            .expect("BUMPFILE has a default"),
+    )
+}
+
+/// The root bumpfile when the workspace uses the fixed strategy, so bumps and tags
+/// happen once for all members.
+pub fn fixed_root(matches: &ArgMatches) -> Result<Option<BumpFile>, BumpError> {
+    let root = root_path(matches);
+    if load(&root)?.strategy == Strategy::Fixed {
+        return Ok(Some(BumpFile::load(root)?));
+    }
+    Ok(None)
+}
+
+/// The members picked by `--member NAME` (name or directory, repeatable), or every
+/// member for `--all` or when neither is given.
+pub fn members(matches: &ArgMatches) -> Result<Vec<Member>, BumpError> {
+    let root = root_path(matches);
+    let workspace = load(&root)?;
+
+    let entries: Vec<&String> = if !matches.contains_id("member") {
+        workspace.members.iter().collect()
+    } else {
+        matches
+            .get_many::<String>("member")
+            .into_iter()
+            .flatten()
+            .map(|wanted| {
+                workspace
+                    .members
+                    .iter()
+                    .find(|entry| *entry == wanted || member_name(entry) == *wanted)
+                    .ok_or_else(|| {
+                        BumpError::LogicError(format!(
+                            "'{wanted}' is not a member of the workspace in {}",
+                            root.display()
+                        ))
+                    })
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    entries
+        .into_iter()
+        .map(|entry| load_member(&root, &workspace, entry))
+        .collect()
+}
+
+fn load_member(root: &Path, workspace: &Workspace, entry: &str) -> Result<Member, BumpError> {
+    let dir = root.parent().unwrap_or(Path::new(".")).join(entry);
+    let bumpfile = match workspace.strategy {
+        Strategy::Independent => dir.join("bump.toml"),
+        Strategy::Fixed => root.to_path_buf(),
+    };
+    Ok(Member {
+        name: member_name(entry),
+        bumpfile: BumpFile::load(bumpfile)?,
+        dir,
+    })
+}
+
+/// After `bumped` members got a major, minor, or patch bump, bump every member
+/// depending on them, directly or transitively, by `[workspace].cascade`. Fixed
+/// workspaces share one version and never cascade.
+pub fn cascade(matches: &ArgMatches, bumped: &[String]) -> Result<(), BumpError> {
+    let root = root_path(matches);
+    let workspace = load(&root)?;
+    if workspace.strategy == Strategy::Fixed {
+        return Ok(());
+    }
+    let names: Vec<String> = workspace.members.iter().map(|m| member_name(m)).collect();
+    let bump_type = match workspace.cascade {
+        Cascade::None => return Ok(()),
+        Cascade::Patch => BumpType::Patch,
+        Cascade::Minor => BumpType::Minor,
+        Cascade::Major => BumpType::Major,
+    };
+
+    let mut done: HashSet<&str> = bumped.iter().map(String::as_str).collect();
+    let mut queue: VecDeque<&str> = bumped.iter().map(String::as_str).collect();
+    while let Some(changed) = queue.pop_front() {
+        for (name, deps) in &workspace.depends {
+            if done.contains(name.as_str()) || !deps.iter().any(|dep| dep == changed) {
+                continue;
+            }
+            done.insert(name);
+            queue.push_back(name);
+            let entry = &workspace.members[names
+                .iter()
+                .position(|n| n == name)
+                .expect("load checks depends names")];
+            let mut member = load_member(&root, &workspace, entry)?;
+            println!(
+                "{name}: depends on {changed}, bumping {}",
+                level_name(&bump_type)
+            );
+            let version = member.bumpfile.version()?;
+            bump_bumpfile(&mut member.bumpfile, version, &bump_type)?;
+        }
+    }
+    Ok(())
+}
+
+const fn level_name(bump_type: &BumpType) -> &'static str {
+    match bump_type {
+        BumpType::Major => "major",
+        BumpType::Minor => "minor",
+        _ => "patch",
+    }
+}
+
+/// Number of commits in `since..HEAD` (all of history without `since`) touching `dir`.
+fn commits_touching(dir: &Path, since: Option<&str>) -> Result<usize, BumpError> {
+    let range = since.map_or_else(|| "HEAD".to_string(), |tag| format!("{tag}..HEAD"));
+    let output = git_cmd()
+        .args(["rev-list", "--count", &range, "--"])
+        .arg(dir)
+        .output()
+        .map_err(|e| BumpError::Git(format!("git rev-list: {e}")))?;
+    if !output.status.success() {
+        return Err(BumpError::Git(format!(
+            "git rev-list {range}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|e| BumpError::Git(format!("git rev-list {range}: {e}")))
+}
+
+/// Bump only the members with commits since the last release, reporting each decision.
+pub fn auto(matches: &ArgMatches) -> Result<(), BumpError> {
+    if !is_git_repository() {
+        return Err(BumpError::LogicError("Not in a git repository".to_string()));
+    }
+    let since_flag = matches.get_one::<String>("since");
+    let level = matches
+        .get_one::<String>("level")
+        .expect("LEVEL has a default");
+    let bump_type = match level.as_str() {
+        "major" => BumpType::Major,
+        "minor" => BumpType::Minor,
+        _ => BumpType::Patch,
+    };
+
+    let mut changed = Vec::new();
+    for member in members(matches)? {
+        // Without --since, an independent member is compared against the tag of its own
+        // current version when that tag exists.
+        let member_version = member.bumpfile.version()?;
+        let member_tag = print::to_string(&member_version, &PrintOptions::default())?;
+        let since = match since_flag {
+            Some(since) => Some(since.clone()),
+            None if git_tag_exists(&member_tag)? => Some(member_tag),
+            None => get_git_latest_tag(&member_version)?,
+        };
+        let since_label = since.as_deref().unwrap_or("the first commit");
+        let commits = commits_touching(&member.dir, since.as_deref())?;
+        if commits == 0 {
+            println!("{}: unchanged since {since_label}, skipping", member.name);
+        } else {
+            println!(
+                "{}: {commits} commit(s) since {since_label}, bumping {level}",
+                member.name
+            );
+            changed.push(member);
+        }
+    }
+
+    if let Some(mut root) = fixed_root(matches)? {
+        if changed.is_empty() {
+            return Ok(());
+        }
+        let version = root.version()?;
+        return bump_bumpfile(&mut root, version, &bump_type);
+    }
+    let names: Vec<String> = changed.iter().map(|member| member.name.clone()).collect();
+    for mut member in changed {
+        let version = member.bumpfile.version()?;
+        bump_bumpfile(&mut member.bumpfile, version, &bump_type)?;
+    }
+    cascade(matches, &names)
+}
@@ -0,0 +1,154 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bump check --pypi, against a static PyPI JSON API served
+# from a temporary directory. PEP 440 ordering shows in which release counts as latest.
+# Without --pypi, check compares the version with the latest tag and [[gen]] outputs.
+
+source "$(dirname "$0")/lib.sh"
+
+if ! command -v python3 >/dev/null; then
+    echo "python3 not found, skipping check tests"
+    exit 0
+fi
+
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+WORK="$(mktemp -d)"
+SERVER_PID=""
+trap '[[ -n "$SERVER_PID" ]] && kill "$SERVER_PID" 2>/dev/null; rm -rf "$WORK"' EXIT
+cd "$WORK"
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+mkdir registry
+python3 -u -m http.server 0 --bind 127.0.0.1 --directory registry >"$WORK/server.log" 2>&1 &
+SERVER_PID=$!
+for _ in $(seq 50); do
+    grep -q "^Serving" "$WORK/server.log" 2>/dev/null && break
+    sleep 0.1
+done
+INDEX_URL="http://127.0.0.1:$(sed -n 's/^Serving HTTP on .* port \([0-9]*\) .*/\1/p' "$WORK/server.log")"
+
+# publish PACKAGE RELEASE...: the JSON API answer for PACKAGE lists RELEASEs
+publish() {
+    local package="$1"
+    shift
+    local releases=()
+    for release in "$@"; do
+        releases+=("\"$release\": []")
+    done
+    mkdir -p "registry/$package"
+    (IFS=,; echo "{\"info\": {\"name\": \"$package\"}, \"releases\": {${releases[*]}}}") > "registry/$package/json"
+}
+
+# The check result line for PACKAGE against the local bumpfile
+check_line() {
+    bump check --pypi "$1" --index-url "$INDEX_URL" 2>/dev/null || true
+}
+
+bump init >/dev/null
+bump --major >/dev/null
+
+check "pypi/no-releases" "pypi:nothing ... ok (1.0.0, no published releases)" "$(check_line nothing)"
+
+# Ordering: 1.0.dev1 < 1.0a1 < 1.0rc1 < 1.0 < 1.0.post1
+publish ordered "1.0rc1" "1.0.post1" "1.0.dev1" "1.0" "1.0a1"
+check "pep440/post-latest" "pypi:ordered ... FAILED (1.0.0 is not newer than published 1.0.post1)" \
+    "$(check_line ordered)"
+publish final "1.0rc1" "1.0.dev1" "1.0" "1.0a1"
+check "pep440/final-equal" "pypi:final ... FAILED (1.0.0 is not newer than published 1.0)" \
+    "$(check_line final)"
+publish candidate "1.0a1" "1.0rc1" "1.0.dev1"
+check "pep440/rc-after-alpha" "pypi:candidate ... ok (1.0.0 is newer than published 1.0rc1)" \
+    "$(check_line candidate)"
+publish alpha "1.0.dev1" "1.0a1"
+check "pep440/alpha-after-dev" "pypi:alpha ... ok (1.0.0 is newer than published 1.0a1)" \
+    "$(check_line alpha)"
+publish dev "0.9.post3" "1.0.dev1"
+check "pep440/dev-after-previous" "pypi:dev ... ok (1.0.0 is newer than published 1.0.dev1)" \
+    "$(check_line dev)"
+
+# Normalization: spellings and trailing zeros compare as their normal form
+publish spelled "1.0.0-ALPHA.2" "v1.0a1" "1.0-c1"
+check "pep440/spellings" "pypi:spelled ... ok (1.0.0 is newer than published 1.0-c1)" \
+    "$(check_line spelled)"
+publish padded "1.0.0.0"
+check "pep440/trailing-zeros" "pypi:padded ... FAILED (1.0.0 is not newer than published 1.0.0.0)" \
+    "$(check_line padded)"
+
+# Local versions sort after the public one, numeric segments after text ones
+publish local "1.0+abc" "1.0"
+check "pep440/local-after-public" "pypi:local ... FAILED (1.0.0 is not newer than published 1.0+abc)" \
+    "$(check_line local)"
+publish local-longer "1.0+abc.1" "1.0+abc"
+check "pep440/local-longer" "pypi:local-longer ... FAILED (1.0.0 is not newer than published 1.0+abc.1)" \
+    "$(check_line local-longer)"
+publish local-numeric "1.0+5" "1.0+abc"
+check "pep440/local-numeric" "pypi:local-numeric ... FAILED (1.0.0 is not newer than published 1.0+5)" \
+    "$(check_line local-numeric)"
+
+# Invalid releases are ignored, including an empty local segment
+publish invalid "2.0+" "2.0+abc..1" "1.0rc1"
+check "pep440/empty-local" "pypi:invalid ... ok (1.0.0 is newer than published 1.0rc1)" \
+    "$(check_line invalid)"
+
+echo "[check/fails-when-not-newer]"
+if bump check --pypi ordered --index-url "$INDEX_URL" >/dev/null 2>&1; then
+    echo "expected failure against a newer release"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Reports: TAP on stdout, or JUnit XML in --report-file with the text summary on stdout
+
+check "report/tap" $'TAP version 13\n1..1\nok 1 - pypi:alpha: 1.0.0 is newer than published 1.0a1' \
+    "$(bump check --pypi alpha --index-url "$INDEX_URL" --report tap)"
+check "report/junit-summary" "pypi:ordered ... FAILED (1.0.0 is not newer than published 1.0.post1)" \
+    "$(bump check --pypi ordered --index-url "$INDEX_URL" --report junit --report-file check.xml 2>/dev/null || true)"
+check "report/junit" $'<testsuite name="bump check" tests="1" failures="1">\n  <testcase classname="bump.check" name="pypi:ordered">\n    <failure message="1.0.0 is not newer than published 1.0.post1"/>' \
+    "$(sed -n '2,4p' check.xml)"
+
+# Without --pypi: the version against the latest tag, and each [[gen]] output's freshness
+
+export GIT_AUTHOR_NAME=test GIT_AUTHOR_EMAIL=test@example.com
+export GIT_COMMITTER_NAME=test GIT_COMMITTER_EMAIL=test@example.com
+mkdir repo
+cd repo
+git init -q
+bump init >/dev/null
+printf '\n[[gen]]\nlang = "json"\noutput = "version.json"\n' >> bump.toml
+git add -A
+git commit -q -m "initial"
+
+check "tag/no-tags" $'tag ... ok (v0.1.0, no release tags)\ngen:version.json ... FAILED (missing, run bump gen)' \
+    "$(bump check 2>/dev/null || true)"
+
+bump gen >/dev/null
+git tag v0.1.0
+check "tag/tagged" $'tag ... ok (v0.1.0 is tagged)\ngen:version.json ... ok (up to date)' "$(bump check)"
+
+bump --patch >/dev/null
+check "gen/out-of-date" "gen:version.json ... FAILED (out of date, run bump gen)" \
+    "$(bump check 2>/dev/null | grep '^gen:' || true)"
+bump gen >/dev/null
+check "tag/ahead" "tag ... ok (v0.1.1 is ahead of the latest tag v0.1.0)" "$(bump check | grep '^tag')"
+
+git commit -q -am "release"
+git tag v0.2.0
+check "tag/behind" "tag ... FAILED (v0.1.1 is behind the latest tag v0.2.0)" \
+    "$(bump check 2>/dev/null | grep '^tag' || true)"
+
+echo "All check tests passed."
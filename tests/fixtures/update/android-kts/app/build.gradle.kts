@@ -0,0 +1,6 @@
+android {
+    defaultConfig {
+        versionCode = 41
+        versionName = "0.9.0"
+    }
+}
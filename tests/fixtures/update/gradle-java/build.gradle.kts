@@ -0,0 +1,16 @@
+plugins {
+    `java-library`
+}
+
+group = "com.example.tiger"
+version = "0.1.0"
+
+dependencies {
+    implementation("org.slf4j:slf4j-api:2.0.9")
+}
+
+tasks.jar {
+    manifest {
+        attributes("Implementation-Version" to project.version)
+    }
+}
@@ -0,0 +1,18 @@
+defmodule Tiger.MixProject do
+  use Mix.Project
+
+  @version "0.9.0"
+
+  def project do
+    [
+      app: :tiger,
+      version: @version,
+      elixir: "~> 1.16",
+      deps: deps()
+    ]
+  end
+
+  defp deps do
+    [{:jason, "~> 1.4"}]
+  end
+end
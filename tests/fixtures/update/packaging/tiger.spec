@@ -0,0 +1,16 @@
+Name:           tiger
+Version:        0.9.0
+Release:        3%{?dist}
+Summary:        Tiger command line tool
+License:        MIT
+Source0:        https://example.org/tiger-%{version}.tar.gz
+
+%description
+Tiger command line tool.
+
+%files
+%{_bindir}/tiger
+
+%changelog
+* Mon Mar 02 2026 Old Packager <old@example.org> - 0.9.0-3
+- Rebuild against libroar 2
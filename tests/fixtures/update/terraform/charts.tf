@@ -0,0 +1,5 @@
+resource "helm_release" "cache" {
+  name    = "redis"
+  chart   = "redis"
+  version = "18.6.1"
+}
@@ -0,0 +1,18 @@
+terraform {
+  required_providers {
+    helm = {
+      source  = "hashicorp/helm"
+      version = "2.12.1"
+    }
+  }
+}
+
+locals {
+  app_version = "0.9.0"
+}
+
+resource "helm_release" "ingress" {
+  name    = "ingress-nginx"
+  chart   = "ingress-nginx"
+  version = "4.2.0"
+}
@@ -0,0 +1,8 @@
+variable "version" {
+  type    = string
+  default = "0.9.0"
+}
+
+variable "replicas" {
+  default = "3"
+}
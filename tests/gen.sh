@@ -0,0 +1,836 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bump gen output options.
+
+source "$(dirname "$0")/lib.sh"
+
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+BUMPFILE="$WORK/bump.toml"
+bump init "$BUMPFILE" >/dev/null
+bump --major "$BUMPFILE" >/dev/null
+for _ in 1 2 3 4 5 6 7 8 9 10 11 12; do
+    bump --minor "$BUMPFILE" >/dev/null
+done
+bump --patch "$BUMPFILE" >/dev/null
+bump --phase rc "$BUMPFILE" >/dev/null
+check "version" "v1.12.1-rc.1" "$(bump print "$BUMPFILE")"
+
+# Packed version word
+
+bump gen --lang c --output "$WORK/bytes.h" --packed bytes "$BUMPFILE" >/dev/null
+check "packed/bytes" "#define VERSION_PACKED 0x010C0101" "$(grep PACKED "$WORK/bytes.h")"
+
+bump gen --lang c --output "$WORK/bcd.h" --packed bcd --symbol-prefix FW_ "$BUMPFILE" >/dev/null
+check "packed/bcd" "#define FW_VERSION_PACKED 0x01120101" "$(grep PACKED "$WORK/bcd.h")"
+
+bump gen --lang c --output "$WORK/plain.h" "$BUMPFILE" >/dev/null
+check "packed/off" "" "$(grep PACKED "$WORK/plain.h" || true)"
+
+echo "[packed/not-c]"
+if bump gen --lang go --output "$WORK/version.go" --packed bytes "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --packed with --lang go"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Fixed-size version string fields
+
+bump gen --lang c --output "$WORK/field.h" --max-string-length 13 --symbol-prefix FW_ "$BUMPFILE" >/dev/null
+check "max-length/assert" '_Static_assert(sizeof(FW_VERSION_STRING) <= 13, "FW_VERSION_STRING does not fit the 13-byte version field");' \
+    "$(grep '^_Static_assert' "$WORK/field.h")"
+if command -v cc >/dev/null 2>&1; then
+    printf '#include "%s"\n' "$WORK/field.h" > "$WORK/field.c"
+    check "max-length/compiles" "ok" "$(cc -std=c11 -fsyntax-only "$WORK/field.c" && echo ok)"
+fi
+
+echo "[max-length/too-long]"
+if OUTPUT="$(bump gen --lang c --output "$WORK/short.h" --max-string-length 12 "$BUMPFILE" 2>&1)"; then
+    echo "expected failure for a 13-byte version string in a 12-byte field"
+    exit 1
+fi
+if [[ "$OUTPUT" != *"needs 13 bytes"* ]] || [[ -e "$WORK/short.h" ]]; then
+    echo "expected a length error and no header, got: $OUTPUT"
+    exit 1
+fi
+echo "ok"
+echo
+
+echo "[max-length/not-c]"
+if bump gen --lang rust --output "$WORK/field.rs" --max-string-length 16 "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --max-string-length with --lang rust"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Per-output languages: --output FILE:LANG
+
+bump gen --output "$WORK/multi.h:c" --output "$WORK/Multi.java:java" --lang python --output "$WORK/multi.py" "$BUMPFILE" >/dev/null
+check "multi/c" '#define VERSION_STRING "v1.12.1-rc.1"' "$(grep '^#define VERSION_STRING' "$WORK/multi.h")"
+check "multi/java" '    public static final String STRING = "v1.12.1-rc.1";' "$(grep ' STRING ' "$WORK/Multi.java")"
+check "multi/lang-default" 'VERSION_STRING = "v1.12.1-rc.1"' "$(grep '^VERSION_STRING' "$WORK/multi.py")"
+
+echo "[multi/no-lang]"
+if bump gen --output "$WORK/nolang.h" "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for an --output without a language"
+    exit 1
+fi
+echo "ok"
+echo
+
+echo "[multi/all-or-nothing]"
+if bump gen --output "$WORK/first.h:c" --output "$WORK/second.ld:ld" --git-info "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --git-info with an ld output"
+    exit 1
+fi
+if [[ -e "$WORK/first.h" ]]; then
+    echo "first.h was written although another output failed"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Include guards
+
+bump gen --lang c --output "$WORK/core_version.h" --symbol-prefix CORE_ "$BUMPFILE" >/dev/null
+check "guard/default" $'#ifndef CORE_CORE_VERSION_H\n#define CORE_CORE_VERSION_H\n#endif /* CORE_CORE_VERSION_H */' \
+    "$(grep -E '^#(ifndef|define CORE_CORE|endif)' "$WORK/core_version.h")"
+
+bump gen --lang c --output "$WORK/app_version.h" --symbol-prefix APP_ --guard ACME_APP_VERSION_H "$BUMPFILE" >/dev/null
+check "guard/custom" "#ifndef ACME_APP_VERSION_H" "$(grep '^#ifndef' "$WORK/app_version.h")"
+
+bump gen --lang c --output "$WORK/once.h" --symbol-prefix ONCE_ --guard pragma-once "$BUMPFILE" >/dev/null
+check "guard/pragma-once" "#pragma once" "$(grep -E '^#(pragma|ifndef|endif)' "$WORK/once.h")"
+
+if command -v cc >/dev/null 2>&1; then
+    echo "[guard/compile]"
+    printf '#include "core_version.h"\n#include "app_version.h"\n#include "once.h"\n#include "once.h"\nint main(void) { return CORE_VERSION_MAJOR - APP_VERSION_MAJOR + ONCE_VERSION_MINOR - 12; }\n' \
+        >"$WORK/guards.c"
+    cc -o "$WORK/guards" "$WORK/guards.c"
+    "$WORK/guards"
+    echo "ok"
+    echo
+fi
+
+echo "[guard/invalid]"
+if bump gen --lang c --output "$WORK/bad-guard.h" --guard "1_BAD" "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for a guard that is not a C identifier"
+    exit 1
+fi
+if bump gen --lang go --output "$WORK/guard.go" --guard GO_H "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --guard with --lang go"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Numeric version and VERSION_AT_LEAST
+
+if command -v cc >/dev/null 2>&1; then
+    echo "[number/compile]"
+    cat >"$WORK/number.c" <<'EOF'
+#include "core_version.h"
+#if CORE_VERSION_NUMBER != 0x010C01
+#error "CORE_VERSION_NUMBER"
+#endif
+#if !CORE_VERSION_AT_LEAST(1, 12, 1) || !CORE_VERSION_AT_LEAST(1, 2, 9) || CORE_VERSION_AT_LEAST(1, 13, 0)
+#error "CORE_VERSION_AT_LEAST"
+#endif
+int main(void) { return 0; }
+EOF
+    cc -o "$WORK/number" "$WORK/number.c"
+    "$WORK/number"
+    echo "ok"
+    echo
+fi
+
+echo "[number/minor-overflow]"
+bump init --initial 1.256.0 "$WORK/wide.toml" >/dev/null
+if OUTPUT="$(bump gen --lang c --output "$WORK/wide.h" "$WORK/wide.toml" 2>&1)" || [[ -e "$WORK/wide.h" ]]; then
+    echo "expected failure for a minor version VERSION_NUMBER cannot hold"
+    exit 1
+fi
+if [[ "$OUTPUT" != *"minor 256 does not fit VERSION_NUMBER (max 255)"* ]]; then
+    echo "unexpected error: $OUTPUT"
+    exit 1
+fi
+echo "ok"
+echo
+check "number/other-languages" "ok" "$(bump gen --lang go --output "$WORK/wide.go" "$WORK/wide.toml" >/dev/null && echo ok)"
+
+# Linker script and assembly outputs
+
+bump gen --lang ld --output "$WORK/version.ld" --symbol-prefix fw_ "$BUMPFILE" >/dev/null
+check "ld/minor" "PROVIDE(__fw_version_minor = 12);" "$(grep version_minor "$WORK/version.ld")"
+
+bump gen --lang asm --output "$WORK/version.s" "$BUMPFILE" >/dev/null
+check "asm/words" $'    .4byte 1\n    .4byte 12\n    .4byte 1\n    .asciz "v1.12.1-rc.1"' \
+    "$(grep -E '\.(4byte|asciz)' "$WORK/version.s")"
+
+bump gen --lang gas --output "$WORK/gas.inc" --symbol-prefix FW_ "$BUMPFILE" >/dev/null
+check "gas/equ" $'    .equ FW_VERSION_MAJOR, 1\n    .equ FW_VERSION_MINOR, 12\n    .equ FW_VERSION_PATCH, 1' \
+    "$(grep '\.equ' "$WORK/gas.inc")"
+if command -v as >/dev/null 2>&1; then
+    printf '    .include "%s"\n    .if FW_VERSION_MINOR != 12\n    .error "wrong minor"\n    .endif\n' "$WORK/gas.inc" > "$WORK/gas.s"
+    check "gas/assembles" "ok" "$(as -o "$WORK/gas.o" "$WORK/gas.s" && echo ok)"
+fi
+
+bump gen --lang armasm --output "$WORK/armasm.inc" "$BUMPFILE" >/dev/null
+check "armasm/equ" $'VERSION_MAJOR EQU 1\nVERSION_MINOR EQU 12\nVERSION_PATCH EQU 1' \
+    "$(grep ' EQU ' "$WORK/armasm.inc")"
+
+# Package, namespace and type names
+
+bump gen --lang java --output "$WORK/BuildInfo.java" --package com.acme.build --class-name BuildInfo "$BUMPFILE" >/dev/null
+check "names/java" $'package com.acme.build;\npublic class BuildInfo {' \
+    "$(grep -E '^(package|public class)' "$WORK/BuildInfo.java")"
+bump gen --lang go --output "$WORK/buildinfo.go" --package buildinfo "$BUMPFILE" >/dev/null
+check "names/go" "package buildinfo" "$(grep '^package' "$WORK/buildinfo.go")"
+bump gen --lang go --output "$WORK/default.go" "$BUMPFILE" >/dev/null
+check "names/go-default" "package version" "$(grep '^package' "$WORK/default.go")"
+bump gen --lang csharp --output "$WORK/Version.cs" --namespace Acme.Build "$BUMPFILE" >/dev/null
+check "names/csharp" $'namespace Acme.Build;\npublic static class Version {' \
+    "$(grep -E '^(namespace|public)' "$WORK/Version.cs")"
+
+echo "[names/unsupported]"
+if bump gen --lang python --output "$WORK/names.py" --package acme "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --package with --lang python"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Kotlin and Swift for mobile builds
+
+bump gen --lang kotlin --output "$WORK/Version.kt" --symbol-prefix APP_ "$BUMPFILE" >/dev/null
+check "kotlin/constants" $'object Version {\n    const val APP_MAJOR = 1\n    const val APP_STRING = "v1.12.1-rc.1"' \
+    "$(grep -E '^object|APP_(MAJOR|STRING)' "$WORK/Version.kt")"
+bump gen --lang swift --output "$WORK/Version.swift" "$BUMPFILE" >/dev/null
+check "swift/constants" $'public enum Version {\n    public static let minor = 12\n    public static let phase = "rc"' \
+    "$(grep -E '^public|let (minor|phase) ' "$WORK/Version.swift")"
+
+# Rust constants for build scripts and include!()
+
+bump gen --lang rust --output "$WORK/version.rs" "$BUMPFILE" >/dev/null
+check "rust/constants" $'pub const VERSION_MAJOR: u32 = 1;\npub const VERSION_MINOR: u32 = 12;\npub const VERSION_PATCH: u32 = 1;\npub const VERSION_CANDIDATE: u32 = 1;\npub const VERSION_STRING: &str = "v1.12.1-rc.1";' \
+    "$(grep -E 'VERSION_(MAJOR|MINOR|PATCH|CANDIDATE|STRING)' "$WORK/version.rs")"
+if command -v rustc >/dev/null; then
+    echo "[rust/compiles]"
+    rustc --edition 2021 --crate-type lib --out-dir "$WORK" "$WORK/version.rs"
+    echo "ok"
+    echo
+fi
+
+# TypeScript and JavaScript: a frozen VERSION object, ESM by default or CommonJS
+
+bump gen --lang typescript --output "$WORK/version.ts" "$BUMPFILE" >/dev/null
+check "typescript/esm" $'export const VERSION = Object.freeze({\n} as const);\nexport default VERSION;' \
+    "$(grep -E '^(export|\})' "$WORK/version.ts")"
+bump gen --lang typescript --output "$WORK/version-cjs.ts" --module cjs "$BUMPFILE" >/dev/null
+check "typescript/cjs" "export = { VERSION };" "$(grep '^export' "$WORK/version-cjs.ts")"
+
+bump gen --lang javascript --output "$WORK/version.mjs" "$BUMPFILE" >/dev/null
+bump gen --lang javascript --output "$WORK/version.cjs" --module cjs --symbol-prefix WEB_ "$BUMPFILE" >/dev/null
+check "javascript/fields" $'  major: 1,\n  minor: 12,\n  patch: 1,\n  candidate: 1,\n  string: "v1.12.1-rc.1",' \
+    "$(grep -E '^  (major|minor|patch|candidate|string):' "$WORK/version.mjs")"
+check "javascript/cjs" "module.exports = { WEB_VERSION };" "$(grep '^module' "$WORK/version.cjs")"
+if command -v node >/dev/null; then
+    check "javascript/esm-import" "v1.12.1-rc.1 true" \
+        "$(node --input-type=module -e "import VERSION from '$WORK/version.mjs'; console.log(VERSION.string, Object.isFrozen(VERSION))")"
+    check "javascript/cjs-require" "12" \
+        "$(node -e "console.log(require('$WORK/version.cjs').WEB_VERSION.minor)")"
+fi
+
+echo "[module/not-js]"
+if bump gen --lang c --output "$WORK/module.h" --module cjs "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --module with --lang c"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Shell and dotenv files for CI scripts and Makefiles
+
+(cd "$WORK" && bump gen --lang sh --output version.sh "$BUMPFILE" >/dev/null)
+check "sh/sourced" "1 12 1 1 v1.12.1-rc.1 v1.12.1-rc.1" \
+    "$(bash -c ". '$WORK/version.sh' && bash -c 'echo \$VERSION_MAJOR \$VERSION_MINOR \$VERSION_PATCH \$VERSION_CANDIDATE \$VERSION_STRING \$VERSION_FULL'")"
+
+SH_REPO="$WORK/sh-repo"
+mkdir -p "$SH_REPO"
+(
+    cd "$SH_REPO"
+    git init -q
+    git -c user.name=test -c user.email=test@example.com commit -q --allow-empty -m "initial"
+    bump gen --lang dotenv --output version.env "$BUMPFILE" >/dev/null
+)
+check "dotenv/full" "VERSION_FULL=v1.12.1-rc.1+$(git -C "$SH_REPO" rev-parse --short HEAD)" \
+    "$(grep '^VERSION_FULL=' "$SH_REPO/version.env")"
+check "dotenv/unquoted" "VERSION_TIMESTAMP=$(grep '^last' "$BUMPFILE" | cut -d'"' -f2)" \
+    "$(grep '^VERSION_TIMESTAMP=' "$SH_REPO/version.env")"
+
+# PowerShell and Batch for Windows CI
+
+bump gen --lang powershell --output "$WORK/version.ps1" "$BUMPFILE" >/dev/null
+check "powershell/env" $'$env:VERSION_MAJOR = \'1\'\n$env:VERSION_STRING = \'v1.12.1-rc.1\'' \
+    "$(grep -E '^\$env:VERSION_(MAJOR|STRING) ' "$WORK/version.ps1")"
+if command -v pwsh >/dev/null 2>&1; then
+    check "powershell/dot-sourced" "v1.12.1-rc.1" "$(pwsh -NoProfile -Command ". '$WORK/version.ps1'; \$env:VERSION_STRING")"
+fi
+
+(cd "$WORK" && bump gen --lang bat --output version.bat --symbol-prefix APP_ "$BUMPFILE" >/dev/null)
+check "bat/set" '@set "APP_VERSION_FULL=v1.12.1-rc.1"' "$(grep FULL "$WORK/version.bat" | tr -d '\r')"
+check "bat/crlf" "$(wc -l < "$WORK/version.bat")" "$(grep -c $'\r$' "$WORK/version.bat")"
+
+# JSON and YAML for deployment tooling, with the git state
+
+(cd "$WORK" && bump gen --lang json --output version.json "$BUMPFILE" >/dev/null)
+check "json/outside-git" $'  "candidate": 1,\n  "string": "v1.12.1-rc.1",\n  "full": "v1.12.1-rc.1",\n  "sha": null,\n  "branch": null,\n  "dirty": null' \
+    "$(grep -E '"(candidate|string|full|sha|branch|dirty)"' "$WORK/version.json")"
+(cd "$SH_REPO" && bump gen --lang yaml --output version.yaml "$BUMPFILE" >/dev/null)
+check "yaml/git" "sha: \"$(git -C "$SH_REPO" rev-parse HEAD)\"" "$(grep '^sha:' "$SH_REPO/version.yaml")"
+check "yaml/branch" "branch: \"$(git -C "$SH_REPO" rev-parse --abbrev-ref HEAD)\"" \
+    "$(grep '^branch:' "$SH_REPO/version.yaml")"
+if command -v node >/dev/null; then
+    check "json/parses" "1.12.1 rc" \
+        "$(node -e "const v = require('$WORK/version.json'); console.log([v.major, v.minor, v.patch].join('.'), v.phase)")"
+fi
+
+# OCI image labels, as a --label-file or as docker build arguments
+
+(cd "$SH_REPO" && bump gen --lang oci --output labels.env "$BUMPFILE" >/dev/null)
+check "oci/label-file" $'org.opencontainers.image.version=v1.12.1-rc.1\norg.opencontainers.image.revision='"$(git -C "$SH_REPO" rev-parse HEAD)" \
+    "$(grep -E 'image\.(version|revision)=' "$SH_REPO/labels.env")"
+(cd "$SH_REPO" && SOURCE_DATE_EPOCH=0 bump gen --lang oci-args --output labels.args "$BUMPFILE" >/dev/null)
+check "oci/args" "--label org.opencontainers.image.version=v1.12.1-rc.1 --label org.opencontainers.image.revision=$(git -C "$SH_REPO" rev-parse HEAD) --label org.opencontainers.image.created=1970-01-01T00:00:00Z" \
+    "$(grep -v '^#' "$SH_REPO/labels.args")"
+
+# Java properties for Jenkins readProperties and EnvInject
+
+bump gen --lang properties --output "$WORK/version.properties" --symbol-prefix APP_ "$BUMPFILE" >/dev/null
+check "properties/values" $'APP_VERSION=v1.12.1-rc.1\nAPP_VERSION_PREFIX=v\nAPP_VERSION_MAJOR=1\nAPP_VERSION_MINOR=12\nAPP_VERSION_PATCH=1\nAPP_VERSION_PHASE=rc' \
+    "$(grep -E '^APP_VERSION(|_PREFIX|_MAJOR|_MINOR|_PATCH|_PHASE)=' "$WORK/version.properties")"
+
+# Buildkite meta-data: a key=value file, also set in the running build under BUILDKITE=true
+
+mkdir -p "$WORK/agent-bin"
+cat > "$WORK/agent-bin/buildkite-agent" <<'AGENT'
+#!/usr/bin/env bash
+echo "$*" >> "$AGENT_LOG"
+AGENT
+chmod +x "$WORK/agent-bin/buildkite-agent"
+export AGENT_LOG="$WORK/agent.log"
+env -u BUILDKITE "$BUMP_BIN" gen --lang buildkite --output "$WORK/buildkite.env" "$BUMPFILE" >/dev/null
+check "buildkite/file" $'version=v1.12.1-rc.1\nversion-major=1\nversion-phase=rc' \
+    "$(grep -E '^version(|-major|-phase)=' "$WORK/buildkite.env")"
+check "buildkite/outside-buildkite" "no" "$([[ -e "$AGENT_LOG" ]] && echo yes || echo no)"
+PATH="$WORK/agent-bin:$PATH" BUILDKITE=true \
+    bump gen --lang buildkite --output "$WORK/buildkite.env" --force "$BUMPFILE" >/dev/null
+check "buildkite/meta-data" $'meta-data set version v1.12.1-rc.1\nmeta-data set version-major 1' \
+    "$(grep -E ' version(|-major) ' "$AGENT_LOG")"
+
+# Git queries are memoized per invocation: more outputs spawn no more git processes
+
+mkdir -p "$WORK/git-bin"
+cat > "$WORK/git-bin/git" <<GIT
+#!/usr/bin/env bash
+echo "\$*" >> "\$GIT_LOG"
+exec "$(command -v git)" "\$@"
+GIT
+chmod +x "$WORK/git-bin/git"
+count_git() {
+    local log="$WORK/git-$1.log"
+    shift
+    (cd "$SH_REPO" && PATH="$WORK/git-bin:$PATH" GIT_LOG="$log" bump gen "$@" "$BUMPFILE" >/dev/null)
+    wc -l < "$log" | tr -d ' '
+}
+ONE_OUTPUT="$(count_git one --lang oci --output one.env)"
+check "git-cache/many-outputs" "$ONE_OUTPUT" \
+    "$(count_git many --lang oci --output a.env --output b.env --output c.env --output d.env)"
+
+# Lua, Ruby and PHP, for plugins that used to scrape the C header
+
+bump gen --lang lua --output "$WORK/version.lua" "$BUMPFILE" >/dev/null
+check "lua/table" $'return {\n  major = 1,\n  string = "v1.12.1-rc.1",' \
+    "$(grep -E '^(return|  major|  string) ' "$WORK/version.lua")"
+if command -v lua >/dev/null 2>&1; then
+    check "lua/loads" "v1.12.1-rc.1 12" "$(lua -e "local v = dofile('$WORK/version.lua'); print(v.string, v.minor)")"
+fi
+
+bump gen --lang ruby --output "$WORK/version.rb" "$BUMPFILE" >/dev/null
+check "ruby/module" $'module VERSION\n  MAJOR = 1\n  STRING = "v1.12.1-rc.1"' \
+    "$(grep -E '^(module|  MAJOR|  STRING) ' "$WORK/version.rb")"
+bump gen --lang ruby --output "$WORK/build_info.rb" --class-name BuildInfo "$BUMPFILE" >/dev/null
+check "ruby/class-name" "module BuildInfo" "$(grep '^module' "$WORK/build_info.rb")"
+if command -v ruby >/dev/null 2>&1; then
+    check "ruby/loads" "v1.12.1-rc.1 12" "$(ruby -e "require '$WORK/version.rb'; puts \"#{VERSION::STRING} #{VERSION::MINOR}\"")"
+fi
+
+bump gen --lang php --output "$WORK/Version.php" --namespace 'Acme\Build' "$BUMPFILE" >/dev/null
+check "php/class" $'namespace Acme\\Build;\nfinal class Version\n    public const MAJOR = 1;\n    public const STRING = \'v1.12.1-rc.1\';' \
+    "$(grep -E '^(namespace|final class|    public const (MAJOR|STRING) )' "$WORK/Version.php")"
+if command -v php >/dev/null 2>&1; then
+    check "php/loads" "v1.12.1-rc.1 12" \
+        "$(php -r "require '$WORK/Version.php'; echo \Acme\Build\Version::STRING, ' ', \Acme\Build\Version::MINOR;")"
+fi
+
+if bump gen --lang lua --output "$WORK/version.lua" --class-name Build "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --class-name with lua"
+    exit 1
+fi
+
+# Nix and Bazel, loaded without running bump
+
+bump gen --lang nix --output "$WORK/version.nix" "$BUMPFILE" >/dev/null
+check "nix/attrs" $'  version = "1.12.1-rc.1";\n  major = 1;\n  string = "v1.12.1-rc.1";' \
+    "$(grep -E '^  (version|major|string) ' "$WORK/version.nix")"
+if command -v nix-instantiate >/dev/null 2>&1; then
+    check "nix/evaluates" '"1.12.1-rc.1"' "$(nix-instantiate --eval -E "(import $WORK/version.nix).version")"
+fi
+
+bump gen --lang bazel --output "$WORK/version.bzl" --symbol-prefix APP_ "$BUMPFILE" >/dev/null
+check "bazel/version" 'APP_VERSION = "1.12.1-rc.1"' "$(grep '^APP_VERSION =' "$WORK/version.bzl")"
+if command -v python3 >/dev/null 2>&1; then
+    # Starlark constants are valid Python.
+    check "bazel/loads" "1.12.1-rc.1 v1.12.1-rc.1 12" \
+        "$(python3 -c "exec(open('$WORK/version.bzl').read()); print(APP_VERSION, APP_VERSION_STRING, APP_VERSION_MINOR)")"
+fi
+
+# Documentation builds: Doxyfile fragment and roff strings
+
+bump gen --lang doxygen --output "$WORK/version.doxy" "$BUMPFILE" >/dev/null
+check "doxygen/project-number" 'PROJECT_NUMBER = "v1.12.1-rc.1"' "$(grep '^PROJECT_NUMBER' "$WORK/version.doxy")"
+
+bump gen --lang roff --output "$WORK/version.roff" --symbol-prefix bump- "$BUMPFILE" >/dev/null
+check "roff/strings" ".ds bump-VERSION v1.12.1-rc.1" "$(grep '^\.ds bump-VERSION ' "$WORK/version.roff")"
+if command -v groff >/dev/null 2>&1; then
+    printf '.so %s\n\\*[bump-VERSION]\n' "$WORK/version.roff" > "$WORK/page.roff"
+    check "roff/renders" "v1.12.1-rc.1" "$(groff -U -T ascii "$WORK/page.roff" | grep -o 'v[0-9.rc-]*')"
+fi
+
+# CI build number from the environment
+
+(cd "$WORK" && BUILD_NUMBER=42 bump gen --output build.sh:sh --output build.rs:rust --output build.json:json \
+    --build-number-env BUILD_NUMBER --build-number-in-full "$BUMPFILE" >/dev/null)
+check "build-number/sh" $'export VERSION_FULL=\'v1.12.1-rc.1+42\'\nexport VERSION_BUILD=42' \
+    "$(grep -E '^export VERSION_(FULL|BUILD)=' "$WORK/build.sh")"
+check "build-number/rust" "pub const VERSION_BUILD: u32 = 42;" "$(grep VERSION_BUILD "$WORK/build.rs")"
+check "build-number/json" $'  "full": "v1.12.1-rc.1+42",\n  "build": 42' "$(grep -E '"(full|build)"' "$WORK/build.json")"
+(cd "$SH_REPO" && BUILD_NUMBER=7 bump gen --lang dotenv --output build.env \
+    --build-number-env BUILD_NUMBER --build-number-in-full "$BUMPFILE" >/dev/null)
+check "build-number/after-suffix" "VERSION_FULL=v1.12.1-rc.1+$(git -C "$SH_REPO" rev-parse --short HEAD).7" \
+    "$(grep '^VERSION_FULL=' "$SH_REPO/build.env")"
+(cd "$WORK" && env -u BUILD_NUMBER "$BUMP_BIN" gen --lang sh --output local.sh \
+    --build-number-env BUILD_NUMBER --build-number-in-full "$BUMPFILE" >/dev/null)
+check "build-number/unset" $'export VERSION_FULL=\'v1.12.1-rc.1\'\nexport VERSION_BUILD=0' \
+    "$(grep -E '^export VERSION_(FULL|BUILD)=' "$WORK/local.sh")"
+
+echo "[build-number/invalid]"
+if BUILD_NUMBER=abc bump gen --lang c --output "$WORK/build.h" --build-number-env BUILD_NUMBER "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for a non-numeric build number"
+    exit 1
+fi
+if bump gen --lang ld --output "$WORK/build.ld" --build-number-env BUILD_NUMBER "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --build-number-env with --lang ld"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Protocol Buffers schema for health-check responses
+
+bump gen --lang proto --output "$WORK/version.proto" --package acme.health.v1 --class-name ServiceVersion "$BUMPFILE" >/dev/null
+check "proto/declarations" $'syntax = "proto3";\npackage acme.health.v1;\nmessage ServiceVersion {' \
+    "$(grep -E '^(syntax|package|message)' "$WORK/version.proto")"
+check "proto/fields" "mode prefix major minor patch phase candidate version full timestamp sha branch dirty code" \
+    "$(sed -n 's/^  [a-z0-9 ]* \([a-z]*\) = [0-9]*;$/\1/p' "$WORK/version.proto" | xargs)"
+if command -v protoc >/dev/null 2>&1; then
+    echo "[proto/compiles]"
+    protoc --proto_path="$WORK" --descriptor_set_out=/dev/null "$WORK/version.proto"
+    echo "ok"
+    echo
+fi
+
+# --lang template: {{ name }} placeholders in a user template
+
+printf '%s\n' '\newcommand{\version}{{{ string }}}' 'major={{major}} phase={{ phase }} sha=[{{ sha }}]' > "$WORK/version.tex.in"
+(cd "$WORK" && bump gen --lang template --template version.tex.in --output version.tex "$BUMPFILE" >/dev/null)
+check "template/rendered" $'\\newcommand{\\version}{v1.12.1-rc.1}\nmajor=1 phase=rc sha=[]' "$(cat "$WORK/version.tex")"
+
+printf 'x = {{ majr }}\n' > "$WORK/typo.in"
+echo "[template/unknown]"
+if OUTPUT="$(bump gen --lang template --template "$WORK/typo.in" --output "$WORK/typo" "$BUMPFILE" 2>&1)"; then
+    echo "expected failure for an unknown placeholder"
+    exit 1
+fi
+if [[ "$OUTPUT" != *"unknown placeholder {{ majr }}"* ]]; then
+    echo "expected an unknown placeholder error, got: $OUTPUT"
+    exit 1
+fi
+echo "ok"
+echo
+
+# .tera templates render with Tera in --features tera builds (run with
+# BUMP_TEMPLATES=tera); the default build refuses them rather than half-render
+
+printf '%s\n' '{% if phase %}{{ phase | upper }} {{ candidate }}{% else %}release{% endif %}' \
+    '{% for part in [major, minor, patch] %}<{{ part }}>{% endfor %}' > "$WORK/version.txt.tera"
+printf 'x = {{ majr }}\n' > "$WORK/typo.tera"
+if [[ "${BUMP_TEMPLATES:-builtin}" == tera ]]; then
+    bump gen --lang template --template "$WORK/version.txt.tera" --output "$WORK/version.txt" "$BUMPFILE" >/dev/null
+    check "tera/rendered" $'RC 1\n<1><12><1>' "$(cat "$WORK/version.txt")"
+    TERA_ERROR="Variable \`majr\` not found"
+else
+    TERA_ERROR="rebuild bump with '--features tera'"
+fi
+echo "[tera/fails]"
+if OUTPUT="$(bump gen --lang template --template "$WORK/typo.tera" --output "$WORK/typo" "$BUMPFILE" 2>&1)"; then
+    echo "expected failure for the .tera template"
+    exit 1
+fi
+if [[ "$OUTPUT" != *"$TERA_ERROR"* ]]; then
+    echo "expected an error containing $TERA_ERROR, got: $OUTPUT"
+    exit 1
+fi
+echo "ok"
+echo
+
+# --git-info: commit SHA, branch and dirty flag
+
+(cd "$SH_REPO" && bump gen --lang c --output build_info.h --git-info "$BUMPFILE" >/dev/null)
+check "git-info/clean" $'#define GIT_SHA "'"$(git -C "$SH_REPO" rev-parse HEAD)"$'"\n#define GIT_BRANCH "'"$(git -C "$SH_REPO" rev-parse --abbrev-ref HEAD)"$'"\n#define GIT_DIRTY 0' \
+    "$(grep '^#define GIT_' "$SH_REPO/build_info.h")"
+(
+    cd "$SH_REPO"
+    echo "tracked" > tracked.txt
+    git add tracked.txt
+    bump gen --lang python --output git.py --git-info "$BUMPFILE" >/dev/null
+)
+check "git-info/dirty" "GIT_DIRTY = True" "$(grep '^GIT_DIRTY' "$SH_REPO/git.py")"
+check "json/dirty" '  "dirty": true' \
+    "$(cd "$SH_REPO" && bump gen --lang json --output version.json "$BUMPFILE" >/dev/null && grep '"dirty"' version.json)"
+(cd "$WORK" && bump gen --lang go --output git.go --git-info "$BUMPFILE" >/dev/null)
+check "git-info/outside-git" $'\tGIT_SHA    = ""\n\tGIT_BRANCH = ""\n\tGIT_DIRTY  = false' \
+    "$(grep 'GIT_' "$WORK/git.go")"
+# A detached HEAD takes the branch from CI variables, which a fork's pull request names.
+HOSTILE_REPO="$WORK/hostile-repo"
+git init -q "$HOSTILE_REPO"
+git -C "$HOSTILE_REPO" -c user.name=test -c user.email=test@example.com commit -q --allow-empty -m "initial"
+git -C "$HOSTILE_REPO" checkout -q --detach
+HOSTILE_BRANCH="a';touch pwned;'\"b\\"
+(
+    cd "$HOSTILE_REPO"
+    export GITHUB_HEAD_REF="$HOSTILE_BRANCH"
+    bump gen --lang sh --output v.sh --git-info "$BUMPFILE" >/dev/null
+    bump gen --lang c --output v.h --git-info "$BUMPFILE" >/dev/null
+)
+check "git-info/hostile-branch-sh" "$HOSTILE_BRANCH" "$(cd "$HOSTILE_REPO" && . ./v.sh && echo "$GIT_BRANCH")"
+check "git-info/hostile-branch-not-run" "" "$(ls "$HOSTILE_REPO" | grep pwned || true)"
+check "git-info/hostile-branch-c" '#define GIT_BRANCH "a'"';touch pwned;'"'\"b\\"' \
+    "$(grep GIT_BRANCH "$HOSTILE_REPO/v.h")"
+if command -v cc >/dev/null 2>&1; then
+    printf '#include <stdio.h>\n#include "v.h"\nint main(void) { puts(GIT_BRANCH); return 0; }\n' > "$HOSTILE_REPO/main.c"
+    cc -o "$HOSTILE_REPO/branch" "$HOSTILE_REPO/main.c"
+    check "git-info/hostile-branch-compiles" "$HOSTILE_BRANCH" "$("$HOSTILE_REPO/branch")"
+fi
+
+bump gen --lang rust --output "$WORK/plain.rs" "$BUMPFILE" >/dev/null
+check "git-info/off" "" "$(grep GIT_ "$WORK/plain.rs" || true)"
+
+echo "[git-info/unsupported]"
+if bump gen --lang ld --output "$WORK/git.ld" --git-info "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --git-info with --lang ld"
+    exit 1
+fi
+echo "ok"
+echo
+
+# MCUboot image version
+
+bump gen --lang mcuboot --output "$WORK/version.conf" "$BUMPFILE" >/dev/null
+check "mcuboot/version" 'CONFIG_MCUBOOT_IMGTOOL_SIGN_VERSION="1.12.1+1"' \
+    "$(grep '^CONFIG_' "$WORK/version.conf")"
+
+# WiX include
+
+bump gen --lang wix --output "$WORK/drop.wxi" "$BUMPFILE" >/dev/null
+check "wix/drop" '  <?define ProductVersion = "1.12.1" ?>' "$(grep 'define ProductVersion' "$WORK/drop.wxi")"
+
+printf '\n[msi]\ncandidate = "build"\nupgrade_code = "8F2D6E1A-3B4C-4D5E-9F60-718293A4B5C6"\nproduct_code = true\n' >> "$BUMPFILE"
+bump gen --lang wix --output "$WORK/build.wxi" "$BUMPFILE" >/dev/null
+check "wix/build" '  <?define ProductVersion = "1.12.101" ?>' "$(grep 'define ProductVersion' "$WORK/build.wxi")"
+CODE="$(grep 'define ProductCode' "$WORK/build.wxi")"
+bump gen --lang wix --output "$WORK/again.wxi" "$BUMPFILE" >/dev/null
+check "wix/product-code-stable" "$CODE" "$(grep 'define ProductCode' "$WORK/again.wxi")"
+
+# [version_code] constant
+
+printf '\n[version_code]\nstrategy = "positional"\n' >> "$BUMPFILE"
+bump gen --lang c --output "$WORK/code.h" "$BUMPFILE" >/dev/null
+check "version-code/c" "#define VERSION_CODE 1120101" "$(grep VERSION_CODE "$WORK/code.h")"
+bump gen --lang java --output "$WORK/Version.java" "$BUMPFILE" >/dev/null
+check "version-code/java" "    public static final int CODE = 1120101;" "$(grep ' CODE' "$WORK/Version.java")"
+bump gen --lang kotlin --output "$WORK/Code.kt" "$BUMPFILE" >/dev/null
+check "version-code/kotlin" "    const val CODE = 1120101" "$(grep ' CODE' "$WORK/Code.kt")"
+bump gen --lang rust --output "$WORK/code.rs" "$BUMPFILE" >/dev/null
+check "version-code/rust" "pub const VERSION_CODE: u32 = 1120101;" "$(grep VERSION_CODE "$WORK/code.rs")"
+bump gen --lang javascript --output "$WORK/code.js" "$BUMPFILE" >/dev/null
+check "version-code/javascript" "  code: 1120101," "$(grep 'code:' "$WORK/code.js")"
+
+# --version-from-stdin: a version string or JSON instead of a bumpfile
+
+echo "2.3.4-rc.1" | bump gen --lang c --output "$WORK/stdin.h" --version-from-stdin >/dev/null
+check "stdin/plain" '#define VERSION_STRING "v2.3.4-rc.1"' "$(grep VERSION_STRING "$WORK/stdin.h")"
+
+echo '{"version_string": "v3.0.0"}' | bump gen --lang c --output "$WORK/stdin-json.h" --version-from-stdin >/dev/null
+check "stdin/json" '#define VERSION_MAJOR 3' "$(grep "^#define VERSION_MAJOR " "$WORK/stdin-json.h")"
+
+echo "[stdin/invalid]"
+if echo "not a version" | bump gen --lang c --output "$WORK/bad.h" --version-from-stdin 2>/dev/null; then
+    echo "expected failure for a non-version on stdin"
+    exit 1
+fi
+echo "ok"
+echo
+
+# --check: regenerate in memory and fail with a diff when an output is out of date
+
+CHECK_BUMPFILE="$WORK/check.toml"
+cp "$BUMPFILE" "$CHECK_BUMPFILE"
+bump gen --lang c --output "$WORK/check.h" "$CHECK_BUMPFILE" >/dev/null
+check "check/fresh" "" "$(bump gen --lang c --output "$WORK/check.h" --check "$CHECK_BUMPFILE")"
+
+bump --phase rc "$CHECK_BUMPFILE" >/dev/null
+BEFORE="$(cat "$WORK/check.h")"
+echo "[check/stale]"
+if OUTPUT="$(bump gen --lang c --output "$WORK/check.h" --check "$CHECK_BUMPFILE" 2>&1)"; then
+    echo "expected failure for an out-of-date output"
+    exit 1
+fi
+echo "$OUTPUT"
+if [[ "$OUTPUT" != *'-#define VERSION_STRING "v1.12.1-rc.1"'* \
+    || "$OUTPUT" != *'+#define VERSION_STRING "v1.12.1-rc.2"'* \
+    || "$OUTPUT" != *"1 generated file(s) out of date"* ]]; then
+    echo "expected a diff of VERSION_STRING and a failure summary"
+    exit 1
+fi
+echo "ok"
+echo
+check "check/writes-nothing" "$BEFORE" "$(cat "$WORK/check.h")"
+
+# Identical content leaves the file, and its modification time, alone unless --force
+
+MARKER="$WORK/marker"
+bump gen --lang c --output "$WORK/mtime.h" "$BUMPFILE" >/dev/null
+touch -t 200001010000 "$WORK/mtime.h"
+touch "$MARKER"
+check "keep-mtime/unchanged" "C header file unchanged at $WORK/mtime.h" \
+    "$(bump gen --lang c --output "$WORK/mtime.h" "$BUMPFILE")"
+check "keep-mtime/kept" "older" "$([[ "$WORK/mtime.h" -ot "$MARKER" ]] && echo older || echo newer)"
+
+# --keep-mtime is still accepted
+check "keep-mtime/flag" "C header file unchanged at $WORK/mtime.h" \
+    "$(bump gen --lang c --output "$WORK/mtime.h" --keep-mtime "$BUMPFILE")"
+
+check "keep-mtime/force" "C header file written to $WORK/mtime.h" \
+    "$(bump gen --lang c --output "$WORK/mtime.h" --force "$BUMPFILE")"
+check "keep-mtime/forced" "newer" "$([[ "$WORK/mtime.h" -ot "$MARKER" ]] && echo older || echo newer)"
+
+echo "/* edited */" >> "$WORK/mtime.h"
+touch -t 200001010000 "$WORK/mtime.h"
+bump gen --lang c --output "$WORK/mtime.h" "$BUMPFILE" >/dev/null
+check "keep-mtime/changed" "newer" "$([[ "$WORK/mtime.h" -ot "$MARKER" ]] && echo older || echo newer)"
+
+# Reproducible outputs: no timestamp, or SOURCE_DATE_EPOCH's, and byte-identical reruns
+
+(cd "$WORK" && env -u SOURCE_DATE_EPOCH "$BUMP_BIN" gen --lang c --output "$WORK/repro.h" --reproducible "$BUMPFILE" >/dev/null)
+check "reproducible/no-timestamp" '#define VERSION_TIMESTAMP ""' "$(grep VERSION_TIMESTAMP "$WORK/repro.h")"
+
+(cd "$WORK" && SOURCE_DATE_EPOCH=0 bump gen --lang c --output "$WORK/repro.h" --reproducible "$BUMPFILE" >/dev/null)
+check "reproducible/source-date-epoch" '#define VERSION_TIMESTAMP "1970-01-01 00:00:00 UTC"' \
+    "$(grep VERSION_TIMESTAMP "$WORK/repro.h")"
+
+(cd "$WORK" && SOURCE_DATE_EPOCH=86400 bump gen --lang json --output "$WORK/repro.json" --reproducible "$BUMPFILE" >/dev/null)
+check "reproducible/json" '  "timestamp": "1970-01-02 00:00:00 UTC",' "$(grep '"timestamp"' "$WORK/repro.json")"
+
+(cd "$WORK" && SOURCE_DATE_EPOCH=86400 bump gen --lang oci --output "$WORK/repro1.txt" --reproducible "$BUMPFILE" >/dev/null)
+(cd "$WORK" && SOURCE_DATE_EPOCH=86400 bump gen --lang oci --output "$WORK/repro2.txt" --reproducible "$BUMPFILE" >/dev/null)
+check "reproducible/identical" "identical" \
+    "$(cmp -s "$WORK/repro1.txt" "$WORK/repro2.txt" && echo identical || echo different)"
+check "reproducible/oci-created" "1" "$(grep -c '1970-01-02T00:00:00Z' "$WORK/repro1.txt")"
+
+# --reproducible also covers the [[gen]] entries
+cat > "$WORK/repro.toml" <<EOF
+$(cat "$BUMPFILE")
+
+[[gen]]
+lang = "c"
+output = "repro-entry.h"
+EOF
+(cd "$WORK" && SOURCE_DATE_EPOCH=0 bump gen --reproducible repro.toml >/dev/null)
+check "reproducible/entries" '#define VERSION_TIMESTAMP "1970-01-01 00:00:00 UTC"' \
+    "$(grep VERSION_TIMESTAMP "$WORK/repro-entry.h")"
+
+if SOURCE_DATE_EPOCH=yesterday bump gen --lang c --output "$WORK/repro.h" --reproducible "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for a non-numeric SOURCE_DATE_EPOCH"
+    exit 1
+fi
+
+# Read-only source trees: explain the failure, and --output-dir writes elsewhere
+
+READONLY="$WORK/readonly"
+mkdir -p "$READONLY"
+cp "$BUMPFILE" "$READONLY/bump.toml"
+chmod a-w "$READONLY/bump.toml" "$READONLY"
+(
+    cd "$READONLY"
+    bump gen --lang c --output include/version.h --output-dir "$WORK/generated" >/dev/null
+)
+check "output-dir/written" '#define VERSION_STRING "v1.12.1-rc.1"' \
+    "$(grep VERSION_STRING "$WORK/generated/include/version.h")"
+
+if touch "$READONLY/probe" 2>/dev/null; then
+    echo "[read-only/explained]"
+    echo "skipped: permissions are not enforced for this user"
+    echo
+else
+    echo "[read-only/explained]"
+    if OUTPUT="$(cd "$READONLY" && bump gen --lang c --output version.h 2>&1)"; then
+        echo "expected failure writing into a read-only directory"
+        exit 1
+    fi
+    if [[ "$OUTPUT" != *"looks read-only"*"--output-dir"* ]]; then
+        echo "expected a read-only explanation, got: $OUTPUT"
+        exit 1
+    fi
+    echo "ok"
+    echo
+fi
+chmod u+w "$READONLY" "$READONLY/bump.toml"
+
+# Outputs and updates that resolve to the bumpfile, or repeat an output, are refused
+
+CLOBBER="$WORK/clobber"
+mkdir -p "$CLOBBER/sub"
+cp "$BUMPFILE" "$CLOBBER/bump.toml"
+ln -s "$CLOBBER/bump.toml" "$CLOBBER/link.h"
+cp "$CLOBBER/bump.toml" "$WORK/clobber.before"
+for case in "dotdot:$CLOBBER/sub/../bump.toml" "symlink:$CLOBBER/link.h"; do
+    echo "[clobber/gen-${case%%:*}]"
+    if OUTPUT="$(bump gen --lang c --output "${case#*:}" "$CLOBBER/bump.toml" 2>&1)"; then
+        echo "expected failure writing the bumpfile"
+        exit 1
+    fi
+    if [[ "$OUTPUT" != *"refusing to overwrite the bumpfile"* ]]; then
+        echo "unexpected error: $OUTPUT"
+        exit 1
+    fi
+    echo "ok"
+    echo
+done
+echo "[clobber/update]"
+if bump update "$CLOBBER/sub/../bump.toml" "$CLOBBER/bump.toml" >/dev/null 2>&1; then
+    echo "expected failure updating the bumpfile"
+    exit 1
+fi
+echo "ok"
+echo
+check "clobber/bumpfile-intact" "" "$(diff "$WORK/clobber.before" "$CLOBBER/bump.toml")"
+echo "[clobber/duplicate-output]"
+if OUTPUT="$(bump gen --lang c --output "$CLOBBER/a.h" --output "$CLOBBER/sub/../a.h" "$CLOBBER/bump.toml" 2>&1)" \
+    || [[ -e "$CLOBBER/a.h" ]]; then
+    echo "expected failure, before writing anything, for an output given twice"
+    exit 1
+fi
+if [[ "$OUTPUT" != *"is given more than once"* ]]; then
+    echo "unexpected error: $OUTPUT"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Remote bumpfile over HTTP: readable by print and gen, never written
+
+echo "[remote/unreachable]"
+if bump print http://127.0.0.1:9/bump.toml 2>/dev/null; then
+    echo "expected failure fetching from a closed port"
+    exit 1
+fi
+echo "ok"
+echo
+
+if command -v python3 >/dev/null; then
+    SERVED="$WORK/served"
+    mkdir -p "$SERVED"
+    cp "$BUMPFILE" "$SERVED/bump.toml"
+    python3 - "$SERVED" "$WORK/port" <<'PY' &
+import functools, http.server, sys
+class Quiet(http.server.SimpleHTTPRequestHandler):
+    def log_message(self, *args):
+        pass
+server = http.server.HTTPServer(("127.0.0.1", 0), functools.partial(Quiet, directory=sys.argv[1]))
+open(sys.argv[2], "w").write(str(server.server_port))
+server.serve_forever()
+PY
+    SERVER_PID=$!
+    trap 'kill "$SERVER_PID" 2>/dev/null; rm -rf "$WORK"' EXIT
+    for _ in $(seq 50); do
+        [[ -s "$WORK/port" ]] && break
+        sleep 0.1
+    done
+    URL="http://127.0.0.1:$(cat "$WORK/port")/bump.toml"
+
+    check "remote/print" "v1.12.1-rc.1" "$(bump print "$URL")"
+    bump gen --lang c --output "$WORK/remote.h" "$URL" >/dev/null
+    check "remote/gen" '#define VERSION_STRING "v1.12.1-rc.1"' \
+        "$(grep VERSION_STRING "$WORK/remote.h")"
+
+    echo "[remote/read-only]"
+    if OUTPUT="$(bump --patch "$URL" 2>&1)"; then
+        echo "expected failure bumping a remote bumpfile"
+        exit 1
+    fi
+    if [[ "$OUTPUT" != *"remote bumpfile and is read-only"* ]]; then
+        echo "expected a read-only error, got: $OUTPUT"
+        exit 1
+    fi
+    echo "ok"
+    echo
+
+    echo "[remote/not-found]"
+    if OUTPUT="$(bump print "${URL%/*}/missing.toml" 2>&1)"; then
+        echo "expected failure fetching a missing bumpfile"
+        exit 1
+    fi
+    if [[ "$OUTPUT" != *"HTTP 404"* ]]; then
+        echo "expected an HTTP 404 error, got: $OUTPUT"
+        exit 1
+    fi
+    echo "ok"
+    echo
+else
+    echo "[remote/served]"
+    echo "skipped: python3 is not available to serve the bumpfile"
+    echo
+fi
+
+echo "All gen tests passed."
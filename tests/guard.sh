@@ -0,0 +1,74 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bump guard, run in a throwaway git repository.
+
+source "$(dirname "$0")/lib.sh"
+
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+cd "$WORK"
+
+export GIT_AUTHOR_NAME=test GIT_AUTHOR_EMAIL=test@example.com
+export GIT_COMMITTER_NAME=test GIT_COMMITTER_EMAIL=test@example.com
+
+# guard_case NAME STATUS PATTERN: run guard against $BASE and check its exit status and output
+guard_case() {
+    local name="$1"
+    local expected_status="$2"
+    local pattern="$3"
+    local output
+    local status=0
+
+    echo "[$name]"
+    set +e
+    output="$(bump guard --base "$BASE" 2>&1)"
+    status=$?
+    set -e
+    echo "$output"
+    if [[ "$status" -ne "$expected_status" ]]; then
+        echo "expected exit status $expected_status, got $status"
+        exit 1
+    fi
+    if [[ "$output" != *"$pattern"* ]]; then
+        echo "expected output containing: $pattern"
+        exit 1
+    fi
+    echo
+}
+
+git init -q
+bump init >/dev/null
+mkdir -p src docs
+echo one > src/lib.txt
+echo one > docs/guide.md
+git add -A
+git commit -q -m "initial"
+BASE="$(git rev-parse --abbrev-ref HEAD)"
+git checkout -q -b feature
+
+guard_case "guard/no-changes" 0 "no changes since $BASE require a version bump (v0.1.0)"
+
+echo two > src/lib.txt
+git commit -q -am "change source"
+guard_case "guard/missing-bump" 1 $'but the version is still v0.1.0; bump it:\n  src/lib.txt'
+
+bump --patch >/dev/null
+git commit -q -am "bump"
+guard_case "guard/bumped" 0 "version bumped since $BASE: v0.1.0 -> v0.1.1"
+
+# [guard].paths: only matching changes require a bump
+
+git checkout -q "$BASE"
+printf '\n[guard]\npaths = ["src/*"]\n' >> bump.toml
+git commit -q -am "guard source only"
+git checkout -q -b docs-only
+echo two > docs/guide.md
+git commit -q -am "change docs"
+guard_case "guard/paths-unmatched" 0 "no changes since $BASE require a version bump"
+
+echo two > src/lib.txt
+git commit -q -am "change source"
+guard_case "guard/paths-matched" 1 "1 file(s) changed since $BASE"
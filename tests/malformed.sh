@@ -65,6 +65,111 @@ assert_fails \
     "unknown variant \`middle\`" \
     "$FIXTURES/bad-label-position.toml"
 
+# Component bounds
+
+assert_fails \
+    "negative-patch" \
+    "expected u32" \
+    "$FIXTURES/negative-patch.toml"
+
+assert_fails \
+    "calver-bad-month" \
+    "[base].month = 13 is out of range" \
+    "$FIXTURES/calver-bad-month.toml"
+
+assert_fails \
+    "bad-clock" \
+    "[timestamp].clock = 'EST' is not 'utc', 'local' or an offset such as '-05:00'" \
+    "$FIXTURES/bad-clock.toml"
+
+# Delimiters and prefixes
+
+assert_fails \
+    "bad-phase-delimiter" \
+    "[phase].delimiter = \"#\" contains '#', which semver does not allow there; use '.', '-'" \
+    "$FIXTURES/bad-phase-delimiter.toml"
+
+assert_fails \
+    "semver-base-delimiter" \
+    "[base].delimiter = \"-\" contains '-', which semver does not allow there; use '.'" \
+    "$FIXTURES/semver-base-dash.toml"
+
+assert_fails \
+    "bad-prefix" \
+    "prefix = \"v 1\" contains ' '" \
+    "$FIXTURES/bad-prefix.toml"
+
+echo "[bad-prefix-flag]"
+PREFIX_DIR="$(mktemp -d)"
+cp "$FIXTURES/valid.toml" "$PREFIX_DIR/bump.toml"
+if output="$(bump --prefix "v#" "$PREFIX_DIR/bump.toml" 2>&1)"; then
+    echo "expected failure, but command succeeded"
+    exit 1
+fi
+if [[ "$output" != *"prefix = \"v#\" contains '#'"* ]] \
+    || ! cmp -s "$FIXTURES/valid.toml" "$PREFIX_DIR/bump.toml"; then
+    echo "got: $output"
+    exit 1
+fi
+rm -rf "$PREFIX_DIR"
+echo "ok"
+echo
+
+echo "[patch-overflow]"
+OVERFLOW_DIR="$(mktemp -d)"
+cp "$FIXTURES/patch-at-max.toml" "$OVERFLOW_DIR/bump.toml"
+if output="$(bump --patch "$OVERFLOW_DIR/bump.toml" 2>&1)"; then
+    echo "expected failure, but command succeeded"
+    exit 1
+fi
+if [[ "$output" != *"patch is already at its maximum"* ]] \
+    || ! cmp -s "$FIXTURES/patch-at-max.toml" "$OVERFLOW_DIR/bump.toml"; then
+    echo "got: $output"
+    exit 1
+fi
+rm -rf "$OVERFLOW_DIR"
+echo "ok"
+echo
+
+# Suggested fixes
+
+assert_fails \
+    "v6-hint" \
+    "found bump v6 [semver.*] tables" \
+    "$FIXTURES/v6-semver.toml"
+
+assert_fails \
+    "array-of-tables-hint" \
+    "[[base]] is an array of tables, use [base]" \
+    "$FIXTURES/base-not-table.toml"
+
+assert_fails \
+    "misspelled-table" \
+    "did you mean [base] instead of [bsae]?" \
+    "$FIXTURES/misspelled-table.toml"
+
+assert_fails \
+    "misspelled-key" \
+    "did you mean \`distance\` instead of \`distnace\` in [phase]?" \
+    "$FIXTURES/misspelled-key.toml"
+
+echo "[known-key-not-suggested]"
+if output="$(bump print "$FIXTURES/update-file-missing-path.toml" 2>&1)"; then
+    echo "expected failure, but command succeeded"
+    exit 1
+fi
+if [[ "$output" != *"missing field \`path\`"* || "$output" == *"did you mean"* ]]; then
+    echo "expected no suggestion for [guard].paths, got: $output"
+    exit 1
+fi
+echo "ok"
+echo
+
+assert_fails \
+    "syntax-error-span" \
+    "line 6, column 9" \
+    "$FIXTURES/invalid-toml.toml"
+
 # Compatibility warnings and valid input
 
 assert_warns_and_prints \
@@ -73,6 +178,12 @@ assert_warns_and_prints \
     "v2020.1.1" \
     "$FIXTURES/semver-with-calver-keys.toml"
 
+assert_warns_and_prints \
+    "misspelled-optional-table" \
+    "did you mean [integrity]?" \
+    "v0.1.0" \
+    "$FIXTURES/misspelled-new-table.toml"
+
 assert_prints \
     "valid" \
     "v0.1.0" \
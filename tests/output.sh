@@ -296,6 +296,59 @@ bump --calendar >/dev/null
 refresh_metadata
 run_print_permutations "calendar/same-day" "" "$CALVER_TODAY" "" "1" "$DEFAULT_LABEL_POSITION"
 
+# [base].format: quarter and N-week train periods; the revision resets per period
+
+set_calver_format() {
+    init_calver
+    awk -v format="$1" '{ print } /^mode = "calver"/ { print "format = \"" format "\""; print "period_weeks = 6" }' \
+        bump.toml > bump.toml.tmp && mv bump.toml.tmp bump.toml
+}
+
+QUARTER="$(date -u +%Y).$(( (10#$(date -u +%m) + 2) / 3 ))"
+TRAIN="$(date -u +%Y).$(( (10#$(date -u +%j) - 1) / 42 + 1 ))"
+
+set_calver_format "%Y.%q"
+bump --calendar >/dev/null
+assert_eq "calendar/quarter" "$QUARTER" print
+bump --calendar >/dev/null
+assert_eq "calendar/same-quarter" "$QUARTER-1" print
+sed -i.bak "s/^month = .*/month = 1/; s/^year = .*/year = 2020/" bump.toml && rm -f bump.toml.bak
+bump --calendar >/dev/null
+assert_eq "calendar/new-quarter-resets-revision" "$QUARTER" print
+
+set_calver_format "%Y.%t"
+bump --calendar >/dev/null
+assert_eq "calendar/six-week-train" "$TRAIN" print
+
+# [timestamp].clock: dates are read in UTC, the local zone or a fixed offset, so
+# "today" is the same day for the date and the same-day revision
+
+set_clock() {
+    init_calver
+    awk -v clock="$1" '{ print } /^\[timestamp\]/ { print "clock = \"" clock "\"" }' \
+        bump.toml > bump.toml.tmp && mv bump.toml.tmp bump.toml
+}
+
+# Etc/GMT-14 is UTC+14 and Etc/GMT+12 is UTC-12: at most one of them shares UTC's date.
+EAST_TODAY="$(TZ=Etc/GMT-14 date +%Y.%m.%d)"
+WEST_TODAY="$(TZ=Etc/GMT+12 date +%Y.%m.%d)"
+
+set_clock "+14:00"
+bump --calendar >/dev/null
+assert_eq "clock/offset-east" "$EAST_TODAY" print
+bump --calendar >/dev/null
+assert_eq "clock/offset-same-day" "$EAST_TODAY-1" print
+
+set_clock "-12:00"
+bump --calendar >/dev/null
+assert_eq "clock/offset-west" "$WEST_TODAY" print
+
+set_clock "local"
+TZ=Etc/GMT-14 bump --calendar >/dev/null
+assert_eq "clock/local" "$EAST_TODAY" print
+TZ=Etc/GMT-14 bump --calendar >/dev/null
+assert_eq "clock/local-same-day" "$EAST_TODAY-1" print
+
 # ---------------------------------------------------------------------------
 # Tier 2: Label position slots (all six)
 # ---------------------------------------------------------------------------
@@ -310,4 +363,77 @@ for label_pos in "${LABEL_POSITIONS[@]}"; do
     run_label_slots "$label_pos" "$PREFIX" "0.1.0" "$PHASE_NAMED" "1"
 done
 
+# ---------------------------------------------------------------------------
+# Init starting version
+# ---------------------------------------------------------------------------
+
+section_banner "Init --initial"
+
+bump init --initial 1.0.0-alpha.1 >/dev/null
+assert_eq "init/initial" "v1.0.0-alpha.1" print
+bump init --initial release-2.3 >/dev/null
+assert_eq "init/prefix-and-two-components" "release-2.3" print
+BUMP_INITIAL_VERSION=3.0.0-rc bump init >/dev/null
+assert_eq "init/environment-default" "v3.0.0-rc" print
+BUMP_INITIAL_VERSION=3.0.0 bump init --initial 4.0.0 >/dev/null
+assert_eq "init/flag-over-environment" "v4.0.0" print
+
+# ---------------------------------------------------------------------------
+# Artifact names
+# ---------------------------------------------------------------------------
+
+section_banner "artifact-name"
+
+bump init --initial 1.4.0-beta.2 >/dev/null
+assert_eq "artifact/builtins" "myapp-1.4.0-beta.2-beta-v1.4.0-beta.2.tar.gz" \
+    artifact-name --template "myapp-{version}-{channel}-{tag}.tar.gz"
+assert_eq "artifact/vars" "myapp-1.4-x86_64-linux.zip" \
+    artifact-name --template "myapp-{major}.{minor}-{target}.{ext}" --var target=x86_64-linux --var ext=zip
+bump --minor >/dev/null
+assert_eq "artifact/stable-channel" "myapp-stable-1.5.0" artifact-name --template "myapp-{channel}-{version}"
+echo "[artifact/unknown-placeholder]"
+if bump artifact-name --template "myapp-{targt}" 2>/dev/null; then
+    echo "expected failure for an unknown placeholder"
+    exit 1
+fi
+echo "ok"
+echo
+
+# ---------------------------------------------------------------------------
+# Safe variants: fully qualified, with characters legal in tags and file names
+# ---------------------------------------------------------------------------
+
+section_banner "Safe variants"
+
+setup_bumpfile
+bump --prefix "release/" >/dev/null
+bump --phase rc >/dev/null
+refresh_metadata
+assert_eq "safe/docker" "release0.1.0-rc.1-${GIT_SHA}" print --docker-safe
+assert_eq "safe/filename" "release0.1.0-rc.1-${GIT_SHA}" print --filename-safe
+assert_eq "safe/docker-no-prefix" "0.1.0-rc.1-${GIT_SHA}" print --docker-safe --no-prefix
+bump --prefix "-" >/dev/null
+assert_eq "safe/docker-leading-separator" "0.1.0-rc.1-${GIT_SHA}" print --docker-safe
+assert_eq "safe/filename-leading-separator" "-0.1.0-rc.1-${GIT_SHA}" print --filename-safe
+
+# ---------------------------------------------------------------------------
+# Notifications: an undeliverable webhook warns on stderr, leaving stdout intact
+# ---------------------------------------------------------------------------
+
+section_banner "Notifications"
+
+setup_bumpfile
+printf '\n[notify]\nurl = "http://127.0.0.1:9/hook"\n' >> bump.toml
+echo "[notify/warning-on-stderr]"
+NOTIFY_STDOUT="$(bump --patch 2>/dev/null)"
+NOTIFY_STDERR="$(bump --patch 2>&1 >/dev/null)"
+if [[ "$NOTIFY_STDOUT" == *"warning"* || "$NOTIFY_STDERR" != *"notification not delivered"* ]]; then
+    echo "expected the delivery warning on stderr only"
+    echo "stdout: $NOTIFY_STDOUT"
+    echo "stderr: $NOTIFY_STDERR"
+    exit 1
+fi
+echo "ok"
+echo
+
 echo "All output tests passed."
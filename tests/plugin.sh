@@ -0,0 +1,121 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bump-<verb> plugins found on PATH and [[wasm]] plugin targets.
+
+source "$(dirname "$0")/lib.sh"
+
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+cd "$WORK"
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+mkdir bin
+cat > bin/bump-hello <<'PLUGIN'
+#!/usr/bin/env bash
+context="$(cat)"
+echo "args: $*"
+echo "context: $(grep -o '"version_string":[^,}]*' <<< "$context")"
+exit "${HELLO_STATUS:-0}"
+PLUGIN
+chmod +x bin/bump-hello
+export PATH="$WORK/bin:$PATH"
+
+# The plugin gets the remaining arguments and ./bump.toml's version on stdin
+
+bump init >/dev/null
+check "plugin/run" $'args: --loud world\ncontext: "version_string":"v0.1.0"' "$(bump hello --loud world)"
+
+echo "[plugin/exit-status]"
+status=0
+HELLO_STATUS=3 bump hello >/dev/null || status=$?
+if [[ "$status" -ne 3 ]]; then
+    echo "expected the plugin's exit status 3, got $status"
+    exit 1
+fi
+echo "ok"
+echo
+
+mkdir empty
+check "plugin/no-bumpfile" 'context: "version_string":null' "$(cd empty && bump hello | grep context)"
+mkdir broken
+printf 'prefix = "v"\n[semver]\nmajor = 1\n' > broken/bump.toml
+check "plugin/invalid-bumpfile" 'context: "version_string":null' "$(cd broken && bump hello 2>/dev/null | grep context)"
+
+# Built-in subcommands and existing bumpfile paths win over plugins
+
+cat > bin/bump-print <<'PLUGIN'
+#!/usr/bin/env bash
+echo "plugin print"
+PLUGIN
+chmod +x bin/bump-print
+check "plugin/builtin-wins" "v0.1.0" "$(bump print)"
+cp bump.toml hello
+check "plugin/path-wins" "v0.2.0" "$(bump --minor hello >/dev/null && bump print hello)"
+
+echo "[plugin/unknown-verb]"
+if bump nosuchverb >/dev/null 2>&1; then
+    echo "expected failure without a bump-nosuchverb plugin"
+    exit 1
+fi
+echo "ok"
+echo
+
+# [[wasm]] targets: gen and update route matching paths to the module, never to the
+# built-in generators and updaters (the module here does not exist)
+
+cat >> bump.toml <<'TOML'
+
+[[wasm]]
+target = "*.fwver"
+module = "plugins/fwver.wasm"
+
+[[wasm]]
+target = "package.json"
+module = "plugins/npm.wasm"
+TOML
+echo "[wasm/unmatched-target]"
+if OUTPUT="$(bump gen --lang wasm --output version.txt 2>&1)"; then
+    echo "expected failure for an output no [[wasm]] target matches"
+    exit 1
+fi
+if [[ "$OUTPUT" != *"no [[wasm]] target matches 'version.txt'"* ]]; then
+    echo "unexpected error: $OUTPUT"
+    exit 1
+fi
+echo "ok"
+echo
+
+echo "[wasm/gen-fails-without-module]"
+if bump gen --lang wasm --output app.fwver >/dev/null 2>&1 || [[ -e app.fwver ]]; then
+    echo "expected failure and no output without the module"
+    exit 1
+fi
+echo "ok"
+echo
+
+echo '{ "name": "app", "version": "0.0.1" }' > package.json
+echo "[wasm/update-routes-before-builtin]"
+if bump update package.json >/dev/null 2>&1; then
+    echo "expected failure without the module"
+    exit 1
+fi
+echo "ok"
+echo
+check "wasm/update-leaves-file" '{ "name": "app", "version": "0.0.1" }' "$(cat package.json)"
+
+echo "All plugin tests passed."
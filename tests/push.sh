@@ -0,0 +1,112 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bump --push, racing two clones of a throwaway bare repository.
+
+source "$(dirname "$0")/lib.sh"
+
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+
+export GIT_AUTHOR_NAME=test GIT_AUTHOR_EMAIL=test@example.com
+export GIT_COMMITTER_NAME=test GIT_COMMITTER_EMAIL=test@example.com
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+git init -q --bare -b main "$WORK/origin.git"
+git clone -q "$WORK/origin.git" "$WORK/seed" 2>/dev/null  # warns about the empty repository
+cd "$WORK/seed"
+bump init >/dev/null
+git add -A
+git commit -q -m "initial"
+git push -q origin main
+
+git clone -q "$WORK/origin.git" "$WORK/first"
+git clone -q "$WORK/origin.git" "$WORK/second"
+
+# Uncontended: the bump is committed and pushed
+
+cd "$WORK/first"
+bump --minor --push >/dev/null
+git -C "$WORK/origin.git" show main:bump.toml > "$WORK/pushed.toml"
+check "push/remote" "v0.2.0" "$(bump print "$WORK/pushed.toml")"
+check "push/message" "chore(release): bump version to v0.2.0" "$(git log -1 --format=%s origin/main)"
+
+# Raced: without retries the rejected push fails and keeps the commit locally
+
+cd "$WORK/second"
+echo "[push/rejected]"
+if bump --patch --push >/dev/null 2>&1; then
+    echo "expected the push to be rejected"
+    exit 1
+fi
+echo "ok"
+echo
+check "push/kept-locally" "chore(release): bump version to v0.1.1" "$(git log -1 --format=%s)"
+git reset -q --hard origin/main
+
+# Raced: with --retries the bump is applied again on top of the fetched head
+
+git commit -q --allow-empty -m "local work"
+bump --patch --push --retries 2 >/dev/null 2>&1
+check "retry/version" "v0.2.1" "$(bump print)"
+check "retry/history" $'chore(release): bump version to v0.2.1\nlocal work\nchore(release): bump version to v0.2.0\ninitial' \
+    "$(git log --format=%s origin/main)"
+
+# Raced with hooks and a new history journal: the journal is committed, hooks run once
+
+cd "$WORK/first"
+git pull -q
+printf '\n[hooks]\npre_bump = ["echo pre $BUMP_VERSION >> ${HOOK_LOG:-/dev/null}"]\npost_bump = ["echo post $BUMP_VERSION >> ${HOOK_LOG:-/dev/null}"]\n\n[history]\n' >> bump.toml
+git commit -q -a -m "configure hooks and history"
+git push -q origin main
+cd "$WORK/second"
+git pull -q
+cd "$WORK/first"
+bump --patch --push >/dev/null
+cd "$WORK/second"
+git commit -q --allow-empty -m "more local work"
+HOOK_LOG="$WORK/hooks.log" bump --patch --push --retries 2 >/dev/null 2>&1
+check "retry/hooks-once" $'pre v0.2.2\npost v0.2.3' "$(cat "$WORK/hooks.log")"
+check "retry/history-committed" $'"to":"v0.2.2"\n"to":"v0.2.3"' \
+    "$(git -C "$WORK/origin.git" show main:bump.history.jsonl | grep -o '"to":"[^"]*"')"
+
+# Other push failures are not retried
+
+echo "[push/no-retry-on-failure]"
+if PUSH_ERR="$(bump --patch --push --remote nowhere --retries 2 2>&1 >/dev/null)"; then
+    echo "expected the push to fail"
+    exit 1
+fi
+if [[ "$PUSH_ERR" == *"bumping again"* ]]; then
+    echo "expected no retry: $PUSH_ERR"
+    exit 1
+fi
+echo "ok"
+echo
+check "push/failure-kept-locally" "chore(release): bump version to v0.2.4" "$(git log -1 --format=%s)"
+git reset -q --hard origin/main
+
+echo "[push/dirty-tree]"
+echo "# local" >> bump.toml
+if bump --patch --push >/dev/null 2>&1; then
+    echo "expected failure with uncommitted changes"
+    exit 1
+fi
+echo "ok"
+echo
+
+echo "All push tests passed."
@@ -0,0 +1,178 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bumpfile round-trips.
+# A bump must rewrite only the version and timestamp lines; every other key,
+# table, comment, and layout choice has to survive byte-for-byte.
+
+source "$(dirname "$0")/lib.sh"
+
+FIXTURES="$ROOT/tests/fixtures/roundtrip"
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+
+# Args: name fixture expected_patch_line
+assert_round_trip() {
+    local name="$1"
+    local fixture="$2"
+    local patch_line="$3"
+    local bumpfile="$WORK/$name.toml"
+
+    echo "[$name]"
+    cp "$fixture" "$bumpfile"
+    bump --patch "$bumpfile" >/dev/null
+
+    local changed
+    changed="$(diff "$fixture" "$bumpfile" | grep '^>' || true)"
+    local unexpected
+    unexpected="$(echo "$changed" | grep -v -e "$patch_line" -e 'last = "' || true)"
+    if [[ -n "$unexpected" ]]; then
+        echo "unexpected changes:"
+        diff "$fixture" "$bumpfile" || true
+        exit 1
+    fi
+    if ! echo "$changed" | grep -q -e "$patch_line"; then
+        echo "expected line not written: $patch_line"
+        diff "$fixture" "$bumpfile" || true
+        exit 1
+    fi
+
+    echo "ok"
+    echo
+}
+
+assert_round_trip "inline-tables" "$FIXTURES/inline-tables.toml" "^> patch = 1$"
+assert_round_trip "dotted-keys" "$FIXTURES/dotted-keys.toml" "^> base.patch = 1$"
+assert_round_trip "unknown-tables" "$FIXTURES/unknown-tables.toml" "^> patch = 1   # bumped by CI$"
+
+# include: shared settings come from the included file, which is never written;
+# the including file keeps only its own keys plus the ones a bump changes.
+
+cp -r "$ROOT/tests/fixtures/include" "$WORK/include"
+INCLUDE="$WORK/include"
+assert_prints "include/merged" "v1.2.3" "$INCLUDE/app/bump.toml"
+
+echo "[include/bump-writes-locally]"
+bump --phase rc "$INCLUDE/app/bump.toml" >/dev/null
+if ! diff -q "$ROOT/tests/fixtures/include/bump.base.toml" "$INCLUDE/bump.base.toml" >/dev/null; then
+    echo "included file was modified"
+    exit 1
+fi
+expected=$'include = "../bump.base.toml"\n\n[base]\nmajor = 1\nminor = 2\npatch = 3\n\n[phase]\nname = "rc"\ndistance = 1\n\n[timestamp]\nlast = TODAY'
+actual="$(sed 's/^last = .*/last = TODAY/' "$INCLUDE/app/bump.toml")"
+if [[ "$actual" != "$expected" ]]; then
+    echo "unexpected bumpfile:"
+    cat "$INCLUDE/app/bump.toml"
+    exit 1
+fi
+echo "ok"
+echo
+assert_prints "include/bumped" "v1.2.3-rc.1" "$INCLUDE/app/bump.toml"
+assert_fails "include/cycle" "include cycle" "$INCLUDE/cycle/a.toml"
+
+# A symlinked bumpfile (shared config linked into components) is written through:
+# the link stays a link and the target gets the new version.
+
+mkdir -p "$WORK/linked/shared" "$WORK/linked/component"
+bump init "$WORK/linked/shared/bump.toml" >/dev/null
+ln -s ../shared/bump.toml "$WORK/linked/component/bump.toml"
+bump --patch "$WORK/linked/component/bump.toml" >/dev/null
+echo "[symlink/kept]"
+if [[ ! -L "$WORK/linked/component/bump.toml" ]]; then
+    echo "the symlink was replaced by a regular file"
+    exit 1
+fi
+echo "ok"
+echo
+assert_prints "symlink/target-written" "v0.1.1" "$WORK/linked/shared/bump.toml"
+
+# bump convert: SemVer -> CalVer starts from today; CalVer -> SemVer keeps the numbers
+
+CONVERT="$WORK/convert.toml"
+bump init "$CONVERT" >/dev/null
+bump --phase rc "$CONVERT" >/dev/null
+bump convert --to calver "$CONVERT" >/dev/null
+assert_prints "convert/to-calver" "v$(date -u +%Y.%m.%d)" "$CONVERT"
+echo "[convert/calver-keys]"
+if ! grep -q '^year = ' "$CONVERT" || grep -q '^major = ' "$CONVERT"; then
+    echo "expected year/month/day keys:"
+    cat "$CONVERT"
+    exit 1
+fi
+echo "ok"
+echo
+printf 'format = "%%Y.%%m.%%d"\nperiod_weeks = 6\n' > "$WORK/format.line"
+sed -i.bak "/^mode = \"calver\"/r $WORK/format.line" "$CONVERT" && rm -f "$CONVERT.bak"
+bump convert --to semver "$CONVERT" >/dev/null
+assert_prints "convert/to-semver" "v$(date -u +%Y.%m.%d)" "$CONVERT"
+echo "[convert/semver-keys]"
+BASE_TABLE="$(sed -n '/^\[base\]/,/^$/p' "$CONVERT")"
+if ! grep -q '^major = ' <<<"$BASE_TABLE" || grep -q -e '^year = ' -e '^format = ' -e '^period_weeks = ' <<<"$BASE_TABLE"; then
+    echo "expected major/minor/patch keys and no calendar format:"
+    cat "$CONVERT"
+    exit 1
+fi
+echo "ok"
+echo
+
+# [integrity]: bump keeps the checksum current; hand edits to the version are caught
+
+# Args: name expected_status env... (runs bump verify --integrity on $INTEGRITY)
+assert_verify() {
+    local name="$1"
+    local expected_status="$2"
+    shift 2
+    local status=0
+
+    echo "[$name]"
+    env "$@" "$BUMP_BIN" verify --integrity "$INTEGRITY" >/dev/null 2>&1 || status=$?
+    if [[ "$status" -ne "$expected_status" ]]; then
+        echo "expected exit status $expected_status, got $status"
+        exit 1
+    fi
+    echo "ok"
+    echo
+}
+
+INTEGRITY="$WORK/integrity.toml"
+bump init "$INTEGRITY" >/dev/null
+printf '\n[integrity]\nkey_env = "BUMP_TEST_KEY"\n' >> "$INTEGRITY"
+assert_verify "integrity/unsealed" 1 BUMP_TEST_KEY=secret
+BUMP_TEST_KEY=secret bump verify --integrity --seal "$INTEGRITY" >/dev/null
+assert_verify "integrity/sealed" 0 BUMP_TEST_KEY=secret
+assert_verify "integrity/wrong-key" 1 BUMP_TEST_KEY=other
+BUMP_TEST_KEY=secret bump --minor "$INTEGRITY" >/dev/null
+assert_verify "integrity/after-bump" 0 BUMP_TEST_KEY=secret
+sed -i.bak 's/^patch = .*/patch = 7/' "$INTEGRITY" && rm -f "$INTEGRITY.bak"
+assert_verify "integrity/hand-edit" 1 BUMP_TEST_KEY=secret
+
+if command -v openssl >/dev/null; then
+    SEALED="$WORK/sealed.toml"
+    bump init "$SEALED" >/dev/null
+    printf '\n[integrity]\nkey_env = "BUMP_TEST_KEY"\n' >> "$SEALED"
+    BUMP_TEST_KEY=secret bump verify --integrity --seal "$SEALED" >/dev/null
+    echo "[integrity/hmac-sha256]"
+    EXPECTED="hmac-sha256:$(printf 'prefix=v\nmode=semver\nmajor=0\nminor=1\npatch=0\nphase=\ndistance=0\n' \
+        | openssl dgst -sha256 -hmac secret | awk '{ print $NF }')"
+    if ! grep -q "^checksum = \"$EXPECTED\"$" "$SEALED"; then
+        echo "expected checksum $EXPECTED"
+        grep '^checksum' "$SEALED"
+        exit 1
+    fi
+    echo "ok"
+    echo
+fi
+
+echo "[integrity/tap-report]"
+REPORT="$(BUMP_TEST_KEY=secret "$BUMP_BIN" verify --integrity --report tap "$INTEGRITY" 2>/dev/null || true)"
+EXPECTED=$'TAP version 13\n1..1\nnot ok 1 - integrity:'"$INTEGRITY"$': the prefix, [base] or [phase] were changed outside bump (checksum mismatch)'
+if [[ "$REPORT" != "$EXPECTED" ]]; then
+    echo "expected: $EXPECTED"
+    echo "actual:   $REPORT"
+    exit 1
+fi
+echo "ok"
+echo
+
+echo "All round-trip tests passed."
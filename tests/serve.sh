@@ -0,0 +1,71 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bump serve, run against a throwaway git repository.
+
+source "$(dirname "$0")/lib.sh"
+
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+WORK="$(mktemp -d)"
+SERVER_PID=""
+trap '[[ -n "$SERVER_PID" ]] && kill "$SERVER_PID" 2>/dev/null; rm -rf "$WORK"' EXIT
+cd "$WORK"
+
+export GIT_AUTHOR_NAME=test GIT_AUTHOR_EMAIL=test@example.com
+export GIT_COMMITTER_NAME=test GIT_COMMITTER_EMAIL=test@example.com
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+git init -q
+bump init >/dev/null
+git add -A
+git commit -q -m "initial"
+
+"$BUMP_BIN" serve --port 0 >"$WORK/serve.log" &
+SERVER_PID=$!
+for _ in $(seq 50); do
+    grep -q "^serving" "$WORK/serve.log" 2>/dev/null && break
+    sleep 0.1
+done
+BASE_URL="$(sed -n 's|^serving .* on \(http://[^/]*\)/version$|\1|p' "$WORK/serve.log")"
+check "serve/listening" "serving bump.toml on $BASE_URL/version" "$(cat "$WORK/serve.log")"
+
+FULL="$(bump print --full)"
+check "serve/text" "v0.1.0" "$(curl -sS "$BASE_URL/version")"
+check "serve/full" "$FULL" "$(curl -sS "$BASE_URL/version/full")"
+check "serve/json" \
+    "{\"base\":{\"major\":0,\"minor\":1,\"mode\":\"semver\",\"patch\":0},\"full\":\"$FULL\",\"phase\":{\"distance\":0,\"name\":\"\"},\"prefix\":\"v\",\"version\":\"v0.1.0\"}" \
+    "$(curl -sS "$BASE_URL/version.json")"
+check "serve/json-type" "application/json" \
+    "$(curl -sS -o /dev/null -w '%{content_type}' "$BASE_URL/version.json")"
+
+# Each request reads the bumpfile again
+bump --minor >/dev/null
+check "serve/live" "v0.2.0" "$(curl -sS "$BASE_URL/version")"
+
+# ...and the repository, so a new commit changes the suffix
+git commit -q -a -m "minor"
+check "serve/live-sha" "$(bump print --full)" "$(curl -sS "$BASE_URL/version/full")"
+
+# An idle connection is dropped after the read timeout instead of blocking other clients
+PORT="${BASE_URL##*:}"
+exec 3<>"/dev/tcp/127.0.0.1/$PORT"
+check "serve/idle-client" "v0.2.0" "$(curl -sS --max-time 15 "$BASE_URL/version")"
+exec 3>&-
+
+check "serve/not-found" "404" "$(curl -sS -o /dev/null -w '%{http_code}' "$BASE_URL/nope")"
+check "serve/method" "405" "$(curl -sS -o /dev/null -w '%{http_code}' -X POST "$BASE_URL/version")"
+
+echo "All serve tests passed."
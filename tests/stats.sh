@@ -0,0 +1,70 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bump stats, run in a throwaway git repository with fixed tag dates.
+
+source "$(dirname "$0")/lib.sh"
+
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+cd "$WORK"
+
+export GIT_AUTHOR_NAME=test GIT_AUTHOR_EMAIL=test@example.com
+export GIT_COMMITTER_NAME=test GIT_COMMITTER_EMAIL=test@example.com
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+# release DATE TAG: an empty commit tagged TAG, both dated DATE
+release() {
+    export GIT_AUTHOR_DATE="$1T12:00:00Z" GIT_COMMITTER_DATE="$1T12:00:00Z"
+    git commit -q --allow-empty -m "release $2"
+    git tag -a "$2" -m "$2"
+}
+
+git init -q
+bump init >/dev/null
+git add -A
+git commit -q -m "initial"
+
+check "stats/no-tags" "releases                        0" "$(bump stats | head -n 1)"
+
+release 2026-01-05 v0.1.0
+release 2026-01-10 v0.1.1
+release 2026-01-20 v0.1.2
+release 2026-02-01 v0.2.0-rc.1
+release 2026-02-03 v0.2.0-rc.2
+release 2026-02-05 v0.2.0
+release 2026-02-06 v0
+release 2026-03-01 latest
+
+check "stats/table" "releases                        4
+pre-releases                    2
+average days between patches    7.5
+average candidates per release  0.5
+
+month     releases
+2026-01   3
+2026-02   1
+
+release             candidates
+v0.1.0              0
+v0.1.1              0
+v0.1.2              0
+v0.2.0              2" "$(bump stats)"
+
+JSON="$(bump stats --format json)"
+check "stats/json" '"average_days_between_patches": 7.5, "pre_releases": 2, "releases": 4, "2026-01": 3' \
+    "$(grep -Eo '"(releases|pre_releases|average_days_between_patches|2026-01)": [0-9.]+' <<<"$JSON" | paste -sd, - | sed 's/,/, /g')"
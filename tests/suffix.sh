@@ -0,0 +1,105 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for [suffix] mode = "branch", run in a throwaway git repository.
+
+source "$(dirname "$0")/lib.sh"
+
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+cd "$WORK"
+
+export GIT_AUTHOR_NAME=test GIT_AUTHOR_EMAIL=test@example.com
+export GIT_COMMITTER_NAME=test GIT_COMMITTER_EMAIL=test@example.com
+
+# Ignore the branch variables of whatever CI system runs these tests.
+unset GITHUB_HEAD_REF GITHUB_REF_NAME CI_COMMIT_REF_NAME BUILDKITE_BRANCH BRANCH_NAME GIT_BRANCH
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+git init -q -b main
+bump init >/dev/null
+bump --suffix branch >/dev/null
+git add -A
+git commit -q -m "initial"
+
+check "branch/checked-out" "v0.1.0+main" "$(bump print --with-suffix)"
+
+# A detached HEAD takes the branch from CI variables, then the placeholder or the sha
+
+git checkout -q --detach
+SHA="$(git rev-parse --short HEAD)"
+check "detached/sha" "v0.1.0+$SHA" "$(bump print --with-suffix)"
+check "detached/ci-variable" "v0.1.0+develop" "$(GIT_BRANCH=origin/develop bump print --with-suffix)"
+check "detached/first-ci-variable" "v0.1.0+topic" \
+    "$(GITHUB_HEAD_REF=topic BUILDKITE_BRANCH=main bump print --with-suffix)"
+check "detached/empty-ci-variable" "v0.1.0+main" \
+    "$(GITHUB_HEAD_REF= BUILDKITE_BRANCH=main bump print --with-suffix)"
+awk '{ print } /^\[suffix\]$/ { print "detached = \"detached\"" }' bump.toml > bump.toml.new && mv bump.toml.new bump.toml
+check "detached/placeholder" "v0.1.0+detached" "$(bump print --with-suffix)"
+
+# Branch names become valid SemVer build metadata unless sanitize = false
+
+git checkout -q -b "feature/foo_bar+ümlaut"
+check "sanitize/default" "v0.1.0+feature-foo-barmlaut" "$(bump print --with-suffix)"
+awk '{ print } /^\[suffix\]$/ { print "sanitize = false" }' bump.toml > bump.toml.new && mv bump.toml.new bump.toml
+check "sanitize/off" "v0.1.0+feature/foo_bar+ümlaut" "$(bump print --with-suffix)"
+
+# Without a git binary, --features gix builds still read HEAD and branches from .git
+# (run with BUMP_GIT_BACKEND=gix); the default build fails rather than print a wrong suffix
+
+git checkout -q main
+mkdir -p "$WORK/no-git"
+NO_GIT_BRANCH="$(PATH="$WORK/no-git" bump print --with-suffix 2>/dev/null || echo failed)"
+bump --suffix git_sha >/dev/null
+NO_GIT_SHA="$(PATH="$WORK/no-git" bump print --with-suffix 2>/dev/null || echo failed)"
+# gen still works; only the git binary knows whether the worktree is dirty
+PATH="$WORK/no-git" bump gen --lang json --output "$WORK/version.json" >/dev/null
+NO_GIT_JSON="$(grep -E '"(sha|dirty)"' "$WORK/version.json" | tr -d ' ,')"
+if [[ "${BUMP_GIT_BACKEND:-git}" == gix ]]; then
+    check "no-git/branch" "v0.1.0+main" "$NO_GIT_BRANCH"
+    check "no-git/sha" "v0.1.0+$(git rev-parse --short HEAD)" "$NO_GIT_SHA"
+    check "no-git/gen-json" $'"sha":"'"$(git rev-parse HEAD)"$'"\n"dirty":null' "$NO_GIT_JSON"
+else
+    check "no-git/branch" "failed" "$NO_GIT_BRANCH"
+    check "no-git/sha" "failed" "$NO_GIT_SHA"
+    check "no-git/gen-json" $'"sha":null\n"dirty":null' "$NO_GIT_JSON"
+fi
+
+# Commands that show no suffix leave git alone; --no-git treats the repository as absent
+
+mkdir -p "$WORK/git-bin"
+cat > "$WORK/git-bin/git" <<GIT
+#!/usr/bin/env bash
+echo "\$*" >> "$WORK/git.log"
+exec "$(command -v git)" "\$@"
+GIT
+chmod +x "$WORK/git-bin/git"
+PATH="$WORK/git-bin:$PATH" bump --patch >/dev/null
+PATH="$WORK/git-bin:$PATH" bump print >/dev/null
+check "git-use/plain-commands" "0" "$(cat "$WORK/git.log" 2>/dev/null | wc -l | tr -d ' ')"
+echo "[no-git-flag/suffix-fails]"
+if PATH="$WORK/git-bin:$PATH" bump print --no-git --with-suffix >/dev/null 2>&1; then
+    echo "expected the suffix to fail with --no-git"
+    exit 1
+fi
+echo "ok"
+echo
+printf '\n[branch_prefix]\nmain = "main-"\n' >> bump.toml
+check "no-git-flag/branch-prefix" "main-0.1.1 v0.1.1" "$(bump print) $(bump print --no-git)"
+check "no-git-flag/runs-no-git" "0" "$(cat "$WORK/git.log" 2>/dev/null | wc -l | tr -d ' ')"
+
+echo "All suffix tests passed."
@@ -0,0 +1,219 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bump tag annotations, run in a throwaway git repository.
+
+source "$(dirname "$0")/lib.sh"
+
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+cd "$WORK"
+
+export GIT_AUTHOR_NAME=test GIT_AUTHOR_EMAIL=test@example.com
+export GIT_COMMITTER_NAME=test GIT_COMMITTER_EMAIL=test@example.com
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+git init -q
+bump init >/dev/null
+git add -A
+git commit -q -m "initial"
+bump tag >/dev/null
+
+for subject in "feat(core): add parser" "fix: crash on empty input" "docs: fix typo" \
+    "feat!: drop the old flag" "chore(release): bump version to v0.1.0" "plain message"; do
+    git commit -q --allow-empty -m "$subject"
+done
+bump --minor >/dev/null
+
+# tag --with-changelog: commits since the previous tag, grouped by type
+
+bump tag --with-changelog >/dev/null
+check "changelog/annotation" $'chore(release): bump version to v0.2.0\n\nFeatures:\n- core: add parser\n- BREAKING: drop the old flag\n\nBug Fixes:\n- crash on empty input\n\nOther:\n- docs: fix typo\n- plain message' \
+    "$(git tag -l --format='%(contents)' v0.2.0)"
+
+git commit -q --allow-empty -m "fix: later change"
+bump --patch >/dev/null
+bump tag >/dev/null
+check "changelog/off-by-default" "chore(release): bump version to v0.2.1" \
+    "$(git tag -l --format='%(contents)' v0.2.1)"
+
+# [aliases]: floating tags and pointer files follow releases, not pre-releases
+
+printf '\n[aliases]\ntags = ["{prefix}{major}", "stable"]\nfiles = ["dist/LATEST"]\n' >> bump.toml
+git commit -q -am "chore: add aliases"
+bump --minor >/dev/null
+bump tag >/dev/null
+check "aliases/tags" "$(git rev-parse v0.3.0^{commit}) $(git rev-parse v0.3.0^{commit})" \
+    "$(git rev-parse v0^{commit}) $(git rev-parse stable^{commit})"
+check "aliases/file" "v0.3.0" "$(cat dist/LATEST)"
+
+git commit -q --allow-empty -m "feat: next"
+bump --phase rc >/dev/null
+bump tag >/dev/null
+check "aliases/pre-release-leaves-aliases" "$(git rev-parse v0.3.0^{commit}) v0.3.0" \
+    "$(git rev-parse stable^{commit}) $(cat dist/LATEST)"
+
+git commit -q --allow-empty -m "fix: next"
+bump --minor >/dev/null
+bump tag >/dev/null
+check "aliases/moved" "$(git rev-parse v0.4.0^{commit}) v0.4.0" \
+    "$(git rev-parse v0^{commit}) $(cat dist/LATEST)"
+check "aliases/latest-tag-is-release" "v0.4.0" "$(git describe --tags --abbrev=0)"
+
+# [history]: bumps and tags append to the journal with the actor and CI job
+
+printf '\n[history]\n' >> bump.toml
+git config user.name "Release Bot"
+git config user.email bot@example.com
+git commit -q -am "chore: add history"
+env -u GITHUB_RUN_ID -u CI_JOB_URL BUILD_URL=https://ci.example.com/job/42 "$BUMP_BIN" --patch >/dev/null
+bump tag >/dev/null
+check "history/entries" $'bumped v0.4.0 -> v0.4.1  by Release Bot <bot@example.com>\ntagged - -> v0.4.1  by Release Bot <bot@example.com>' \
+    "$(bump history | cut -d' ' -f3- | sed 's/  */ /; s/^ *//')"
+check "history/json" '"ci_job_url": "https://ci.example.com/job/42",' \
+    "$(bump history --json | grep ci_job_url | head -n 1 | sed 's/^ *//')"
+
+# [tags] latest = "highest": changelogs start at the highest release tag, not at a
+# lower backport tag that happens to be nearer to HEAD
+
+git commit -q --allow-empty -m "fix: backport"
+git tag -a v0.2.2 -m "v0.2.2"
+git commit -q --allow-empty -m "feat: after backport"
+bump --patch >/dev/null
+check "latest/nearest-by-default" $'\nFeatures:\n- after backport' \
+    "$(bump tag --dry-run --with-changelog | sed -n '/^dry run: changelog:/,$p' | sed '1d')"
+printf '\n[tags]\nlatest = "highest"\n' >> bump.toml
+check "latest/highest" $'\nFeatures:\n- after backport\n\nBug Fixes:\n- backport' \
+    "$(bump tag --dry-run --with-changelog | sed -n '/^dry run: changelog:/,$p' | sed '1d')"
+
+# [branch_prefix]: maintenance branches print and tag with their own prefix
+
+printf '\n[branch_prefix]\n"release/*" = "release-"\n"release/legacy" = "legacy-"\n' >> bump.toml
+git commit -q -am "chore: add branch prefixes"
+check "branch-prefix/default" "v0.4.2" "$(bump print)"
+git checkout -q -b release/0.4
+check "branch-prefix/pattern" "release-0.4.2" "$(bump print)"
+bump tag >/dev/null
+check "branch-prefix/tag" "release-0.4.2" "$(git describe --tags --exact-match)"
+git checkout -q -b release/legacy
+check "branch-prefix/exact-wins" "legacy-0.4.2" "$(bump print)"
+
+# [hooks]: commands around bumps and tags see the new version; a failing pre_bump
+# command stops the bump before the bumpfile is written
+
+cat >> bump.toml <<'EOF'
+
+[hooks]
+pre_bump = ["echo pre_bump $BUMP_VERSION >> \"$HOOK_LOG\""]
+post_bump = ["echo post_bump $BUMP_VERSION >> \"$HOOK_LOG\""]
+pre_tag = ["echo pre_tag $BUMP_VERSION >> \"$HOOK_LOG\""]
+post_tag = ["echo post_tag $BUMP_VERSION >> \"$HOOK_LOG\""]
+EOF
+git commit -q -am "chore: add hooks"
+export HOOK_LOG="$WORK/hooks.log"
+bump --patch >/dev/null
+bump tag >/dev/null
+check "hooks/order" $'pre_bump legacy-0.4.3\npost_bump legacy-0.4.3\npre_tag legacy-0.4.3\npost_tag legacy-0.4.3' \
+    "$(cat "$HOOK_LOG")"
+cp bump.toml "$WORK/before-hook.toml"
+echo "[hooks/failing-pre-bump]"
+if HOOK_LOG="$WORK/missing/hooks.log" bump --patch >/dev/null 2>&1; then
+    echo "expected the failing pre_bump hook to stop the bump"
+    exit 1
+fi
+echo "ok"
+echo
+check "hooks/bumpfile-untouched" "" "$(diff "$WORK/before-hook.toml" bump.toml)"
+
+# [publish]: commands run once the tag exists, with the tag name; a dry run only lists them
+
+printf '\n[publish]\ncommands = ["echo published $BUMP_VERSION >> \\"$PUBLISH_LOG\\""]\n' >> bump.toml
+git commit -q -am "chore: add publish"
+bump --patch >/dev/null
+export PUBLISH_LOG="$WORK/published.log"
+check "publish/dry-run" "dry run: would run 'echo published \$BUMP_VERSION >> \"\$PUBLISH_LOG\"'" \
+    "$(bump tag --dry-run | grep 'would run .echo published')"
+check "publish/dry-run-runs-nothing" "no" "$([[ -e "$PUBLISH_LOG" ]] && echo yes || echo no)"
+bump tag >/dev/null
+check "publish/after-tag" "published legacy-0.4.4" "$(cat "$PUBLISH_LOG")"
+
+# [notify]: a webhook receives each bump and tag
+
+if command -v python3 >/dev/null; then
+    python3 -u -c '
+import http.server, sys
+class Receiver(http.server.BaseHTTPRequestHandler):
+    def do_POST(self):
+        body = self.rfile.read(int(self.headers["Content-Length"]))
+        with open(sys.argv[1], "ab") as received:
+            received.write(body + b"\n")
+        self.send_response(204)
+        self.end_headers()
+    def log_message(self, *args):
+        pass
+server = http.server.HTTPServer(("127.0.0.1", 0), Receiver)
+print(server.server_port)
+server.serve_forever()
+' "$WORK/received.jsonl" >"$WORK/receiver.port" &
+    RECEIVER_PID=$!
+    for _ in $(seq 50); do
+        [[ -s "$WORK/receiver.port" ]] && break
+        sleep 0.1
+    done
+    printf '\n[notify]\nurl = "http://127.0.0.1:%s/hook"\nmessage = "{event} {version} of tiger"\n' \
+        "$(cat "$WORK/receiver.port")" >> bump.toml
+    git commit -q -am "chore: add notify"
+    bump --patch >/dev/null
+    bump tag >/dev/null
+    kill "$RECEIVER_PID"
+    check "notify/generic" \
+        $'{"event":"bumped","message":"bumped legacy-0.4.5 of tiger","version":"legacy-0.4.5"}\n{"event":"tagged","message":"tagged legacy-0.4.5 of tiger","version":"legacy-0.4.5"}' \
+        "$(cat "$WORK/received.jsonl")"
+fi
+
+# [issues]: tracker references since the latest tag are appended to the annotation
+
+printf '\n[issues]\nkeys = ["JIRA", "OPS"]\nurl = "https://jira.example.com/browse/{id}"\n' >> bump.toml
+git commit -q -am "chore: link JIRA-12 issues"
+git commit -q --allow-empty -m "fix: retry uploads (OPS-3, JIRA-12, NOPE-1)"
+bump --patch >/dev/null 2>&1
+bump tag >/dev/null 2>&1
+check "issues/annotation" "chore(release): bump version to $(bump print)"$'\n\nIssues:\n- OPS-3: https://jira.example.com/browse/OPS-3\n- JIRA-12: https://jira.example.com/browse/JIRA-12' \
+    "$(git tag -l --format='%(contents)' "$(bump print)")"
+
+# patterns match other reference styles; changelog entries link every reference
+
+printf 'patterns = ["#[0-9]+"]\n' >> bump.toml
+git commit -q -am "chore: match GitHub issues"
+git commit -q --allow-empty -m "fix: handle empty uploads (#41, OPS-4)"
+bump --patch >/dev/null 2>&1
+check "issues/dry-run" $'dry run: issues:\n\nIssues:\n- #41: https://jira.example.com/browse/#41\n- OPS-4: https://jira.example.com/browse/OPS-4' \
+    "$(bump tag --dry-run | sed -n '/^dry run: issues:/,$p' | head -n 5)"
+bump tag --with-changelog >/dev/null 2>&1
+check "issues/changelog-links" $'- handle empty uploads ([#41](https://jira.example.com/browse/#41), [OPS-4](https://jira.example.com/browse/OPS-4))' \
+    "$(git tag -l --format='%(contents)' "$(bump print)" | grep 'empty uploads')"
+
+# --provenance: a statement with the builder, tagged commit and bumpfile digest
+
+bump --patch >/dev/null 2>&1
+env -u GITHUB_WORKFLOW_REF -u CI_JOB_URL -u BUILDKITE_BUILD_URL BUILD_URL=https://ci.example.com/job/43 \
+    "$BUMP_BIN" tag --provenance dist/provenance.json >/dev/null 2>&1
+check "provenance/statement" \
+    "https://ci.example.com/job/43 $(bump print) $(git rev-parse HEAD) $(sha256sum bump.toml | cut -d' ' -f1)" \
+    "$(for key in id tag commit sha256; do grep -m1 "\"$key\":" dist/provenance.json | cut -d'"' -f4; done | paste -sd' ')"
+
+echo "All tag tests passed."
@@ -0,0 +1,561 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for bump update on the supported file types.
+# Fixtures in tests/fixtures/update are copied to a temporary directory first.
+
+source "$(dirname "$0")/lib.sh"
+
+FIXTURES="$ROOT/tests/fixtures/update"
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+cp -r "$FIXTURES/." "$WORK/"
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+BUMPFILE="$WORK/bump.toml"
+bump init "$BUMPFILE" >/dev/null
+bump --major "$BUMPFILE" >/dev/null
+check "version" "v1.0.0" "$(bump print "$BUMPFILE")"
+
+# Unity ProjectSettings.asset
+
+UNITY="$WORK/unity/ProjectSettings/ProjectSettings.asset"
+bump update "$UNITY" "$BUMPFILE" >/dev/null
+check "unity/fields" $'  bundleVersion: 1.0.0\n    iPhone: com.example.tiger\n    iPhone: 42\n  AndroidBundleVersionCode: 42' \
+    "$(grep -E 'bundleVersion:|iPhone:|AndroidBundleVersionCode:' "$UNITY")"
+
+bump update "$UNITY" "$BUMPFILE" >/dev/null
+check "unity/unchanged-version-keeps-code" "  AndroidBundleVersionCode: 42" \
+    "$(grep AndroidBundleVersionCode "$UNITY")"
+
+# Unreal Config/DefaultGame.ini (CRLF line endings are kept)
+
+UNREAL="$WORK/unreal/Config/DefaultGame.ini"
+bump update "$UNREAL" "$BUMPFILE" >/dev/null
+check "unreal/project-version" $'ProjectVersion=1.0.0\r' "$(grep ProjectVersion "$UNREAL")"
+
+UNREAL_NEW="$WORK/unreal-new/Config/DefaultGame.ini"
+bump update "$UNREAL_NEW" "$BUMPFILE" >/dev/null
+check "unreal/added-under-section" $'[/Script/EngineSettings.GeneralProjectSettings]\nProjectVersion=1.0.0\nProjectName=Tiger' \
+    "$(tail -n 3 "$UNREAL_NEW")"
+
+# Android build.gradle / build.gradle.kts
+
+GRADLE="$WORK/android/app/build.gradle"
+bump update "$GRADLE" "$BUMPFILE" >/dev/null
+check "gradle/groovy" $'        versionCode 42\n        versionName "1.0.0"\n        versionNameSuffix "-dev"' \
+    "$(grep -E 'versionCode|versionName' "$GRADLE")"
+
+GRADLE_KTS="$WORK/android-kts/app/build.gradle.kts"
+bump update "$GRADLE_KTS" "$BUMPFILE" >/dev/null
+check "gradle/kotlin" $'        versionCode = 42\n        versionName = "1.0.0"' \
+    "$(grep -E 'versionCode|versionName' "$GRADLE_KTS")"
+
+# Electron: package.json, electron-builder config and the Windows .rc in one command
+
+ELECTRON="$WORK/electron"
+bump update "$ELECTRON" "$BUMPFILE" >/dev/null
+check "electron/package-json" $'  "version": "1.0.0",\n    "semver": { "version": "7.6.0" }\n    "buildVersion": "1.0.0",' \
+    "$(grep -E '"(version|buildVersion)"' "$ELECTRON/package.json")"
+check "electron/builder-yml" $'buildVersion: "1.0.0"\n  buildVersion: "keep"' \
+    "$(grep buildVersion "$ELECTRON/electron-builder.yml")"
+check "electron/rc" $' FILEVERSION 1,0,0,0\r\n PRODUCTVERSION 1,0,0,0\r\n            VALUE "FileVersion", "1.0.0.0"\r\n            VALUE "ProductVersion", "1.0.0.0"\r' \
+    "$(grep -E 'FILEVERSION|PRODUCTVERSION|Version"' "$ELECTRON/build/app.rc")"
+check "electron/rc-without-versioninfo-untouched" "$(cat "$FIXTURES/electron/build/dialogs.rc")" \
+    "$(cat "$ELECTRON/build/dialogs.rc")"
+
+# [rc] chooses the fourth VERSIONINFO field independently for file and product
+
+RC_BUMPFILE="$WORK/rc.toml"
+cp "$BUMPFILE" "$RC_BUMPFILE"
+bump --phase rc "$RC_BUMPFILE" >/dev/null
+printf '\n[rc]\nfile_build = "candidate"\nproduct_build = "zero"\n' >> "$RC_BUMPFILE"
+bump update "$ELECTRON/build/app.rc" "$RC_BUMPFILE" >/dev/null
+check "rc/file-candidate-product-zero" $' FILEVERSION 1,0,0,1\r\n PRODUCTVERSION 1,0,0,0\r\n            VALUE "FileVersion", "1.0.0.1"\r\n            VALUE "ProductVersion", "1.0.0.0"\r' \
+    "$(grep -E 'FILEVERSION|PRODUCTVERSION|Version"' "$ELECTRON/build/app.rc")"
+
+echo "[rc/counter-needs-version-code]"
+sed -i.bak 's/"zero"/"counter"/' "$RC_BUMPFILE"
+if bump update "$ELECTRON/build/app.rc" "$RC_BUMPFILE" 2>/dev/null; then
+    echo "expected failure without [version_code]"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Yocto/BitBake: PV assignments are rewritten, versioned recipe names are renamed
+
+YOCTO="$WORK/yocto"
+bump update "$YOCTO" "$BUMPFILE" >/dev/null
+check "bitbake/renamed" "tiger_1.0.0.bb" "$(ls "$YOCTO/recipes-tiger/tiger")"
+check "bitbake/inc-keeps-git-tail" 'PV ?= "1.0.0+git${SRCPV}"' \
+    "$(grep '^PV' "$YOCTO/recipes-lion/lion/lion.inc")"
+check "bitbake/git-recipe-untouched" "require lion.inc" \
+    "$(cat "$YOCTO/recipes-lion/lion/lion_git.bb")"
+
+bump update "$YOCTO/recipes-lion/lion/lion.inc" "$RC_BUMPFILE" >/dev/null
+check "bitbake/pre-release-tilde" 'PV ?= "1.0.0~rc.1+git${SRCPV}"' \
+    "$(grep '^PV' "$YOCTO/recipes-lion/lion/lion.inc")"
+
+# npm/yarn workspaces: --workspaces also updates each declared package.json
+
+NPM="$WORK/npm-workspaces"
+bump update "$NPM/package.json" "$BUMPFILE" >/dev/null
+check "npm/root-only" '    "version": "0.1.0",' "$(grep '"version"' "$NPM/packages/core/package.json")"
+
+bump update "$NPM/package.json" --workspaces "$BUMPFILE" >/dev/null
+check "npm/root" '  "version": "1.0.0",' "$(grep '"version"' "$NPM/package.json")"
+check "npm/workspace" '    "version": "1.0.0",' "$(grep '"version"' "$NPM/packages/core/package.json")"
+check "npm/exact-path" '{"name":"@tiger/web","version":"1.0.0","dependencies":{"@tiger/core":"0.1.0"}}' \
+    "$(cat "$NPM/apps/web/package.json")"
+check "npm/excluded" '  "version": "0.0.1"' "$(grep '"version"' "$NPM/packages/private-scripts/package.json")"
+check "npm/no-version" "" "$(grep '"version"' "$NPM/packages/docs/package.json" || true)"
+
+echo "[workspaces-needs-manifest]"
+if bump update "$WORK/unity/ProjectSettings/ProjectSettings.asset" --workspaces "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --workspaces without a package.json or Cargo.toml"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Cargo workspaces: --workspace updates [workspace.package] and members with their own
+# version; --path-deps also sets path dependency requirements between workspace crates
+
+CARGO="$WORK/cargo-workspace"
+bump update "$CARGO/Cargo.toml" --workspace "$BUMPFILE" >/dev/null
+check "cargo/workspace-package" 'version = "1.0.0" # shared by the crates' "$(grep '^version' "$CARGO/Cargo.toml")"
+check "cargo/inherits" "version.workspace = true" "$(grep '^version' "$CARGO/crates/tiger-core/Cargo.toml")"
+check "cargo/member" 'version = "1.0.0"' "$(grep '^version' "$CARGO/crates/tiger-macros/Cargo.toml")"
+check "cargo/excluded" 'version = "0.0.1"' "$(grep '^version' "$CARGO/examples/demo/Cargo.toml")"
+check "cargo/deps-untouched" 'tiger-core = { path = "crates/tiger-core", version = "0.1.0" }' \
+    "$(grep '^tiger-core' "$CARGO/Cargo.toml")"
+
+bump update "$CARGO/Cargo.toml" --workspace --path-deps "$BUMPFILE" >/dev/null
+check "cargo/workspace-deps" $'tiger-core = { path = "crates/tiger-core", version = "1.0.0" }\nserde = "1"' \
+    "$(grep -E '^(tiger-core|serde)' "$CARGO/Cargo.toml")"
+check "cargo/path-deps" $'macros = { package = "tiger-macros", path = "../tiger-macros", version = "=1.0.0" }\nserde = { version = "1.0.200", features = ["derive"] }\ntiger-core = { path = "../tiger-core", version = "^1.0.0" }' \
+    "$(grep -E '^(macros|serde|tiger-core =)' "$CARGO/crates/tiger-cli/Cargo.toml")"
+
+echo "[cargo/workspace-needs-workspace]"
+if bump update "$CARGO/crates/tiger-cli/Cargo.toml" --workspace "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for --workspace without a [workspace] table"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Python: [project] and [tool.poetry] versions, setup.cfg [metadata], PEP 440 spelling
+
+PYTHON="$WORK/python"
+bump update "$PYTHON" "$BUMPFILE" >/dev/null
+check "python/pep621" 'version = "1.0.0"  # set by bump' "$(grep '^version' "$PYTHON/pep621/pyproject.toml")"
+check "python/poetry" 'version = "1.0.0"' "$(grep '^version' "$PYTHON/poetry/pyproject.toml")"
+check "python/dynamic" $'dynamic = ["version"]\nversion = "1.0.0"' \
+    "$(grep -E '^(dynamic|version)' "$PYTHON/dynamic/pyproject.toml")"
+check "python/setup-cfg" $'version = 1.0.0\ndescription = Tiger' \
+    "$(grep -E '^(version|description)' "$PYTHON/setuptools/setup.cfg")"
+check "python/tool-only" $'[tool.black]\nline-length = 100' "$(cat "$PYTHON/tool-only/pyproject.toml")"
+
+PY_BUMPFILE="$WORK/python.toml"
+cp "$BUMPFILE" "$PY_BUMPFILE"
+bump --phase rc "$PY_BUMPFILE" >/dev/null
+bump update "$PYTHON/pep621/pyproject.toml" "$PY_BUMPFILE" >/dev/null
+check "python/rc" 'version = "1.0.0rc1"  # set by bump' "$(grep '^version' "$PYTHON/pep621/pyproject.toml")"
+bump --phase dev "$PY_BUMPFILE" >/dev/null
+bump update "$PYTHON/setuptools/setup.cfg" "$PY_BUMPFILE" >/dev/null
+check "python/dev" "version = 1.0.0.dev1" "$(grep '^version' "$PYTHON/setuptools/setup.cfg")"
+
+echo "[python/no-pep440-form]"
+bump --phase snapshot "$PY_BUMPFILE" >/dev/null
+if bump update "$PYTHON/poetry/pyproject.toml" "$PY_BUMPFILE" 2>/dev/null; then
+    echo "expected failure for a phase PEP 440 cannot express"
+    exit 1
+fi
+echo "ok"
+echo
+
+# CMake: only the VERSION argument of project() changes
+
+CMAKE="$WORK/cmake"
+cp "$CMAKE/CMakeLists.txt" "$WORK/CMakeLists.txt.orig"
+bump update "$CMAKE/CMakeLists.txt" "$BUMPFILE" >/dev/null
+check "cmake/version" "    VERSION 1.0.0 # bumped by bump" "$(grep '^    VERSION' "$CMAKE/CMakeLists.txt")"
+check "cmake/rest-untouched" "1" \
+    "$(diff "$WORK/CMakeLists.txt.orig" "$CMAKE/CMakeLists.txt" | grep -c '^>')"
+
+OUTPUT="$(bump update "$CMAKE/CMakeLists.txt" "$PY_BUMPFILE")"
+check "cmake/phase-dropped" "    VERSION 1.0.0 # bumped by bump" "$(grep '^    VERSION' "$CMAKE/CMakeLists.txt")"
+if [[ "$OUTPUT" != *"CMake versions are numeric"* ]]; then
+    echo "expected a note that the phase was dropped, got: $OUTPUT"
+    exit 1
+fi
+
+bump update "$CMAKE" "$BUMPFILE" >/dev/null
+check "cmake/subdirectory-skipped" 'add_library(tiger_project STATIC tiger.c)' "$(head -n 1 "$CMAKE/src/CMakeLists.txt")"
+
+echo "[cmake/variable-version]"
+if bump update "$WORK/cmake-var/CMakeLists.txt" "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for a VERSION taken from a variable"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Helm: Chart.yaml version and appVersion, or only the fields [helm] lists
+
+HELM="$WORK/helm"
+bump update "$HELM" "$BUMPFILE" >/dev/null
+check "helm/fields" $'version: 1.0.0 # chart version\nappVersion: \'1.0.0\'\n    version: 17.3.2' \
+    "$(grep -E 'version:|appVersion:' "$HELM/tiger/Chart.yaml")"
+check "helm/quotes-app-version" $'version: 1.0.0\nappVersion: "1.0.0"' \
+    "$(grep -E 'version:|appVersion:' "$HELM/lion/Chart.yaml")"
+
+HELM_BUMPFILE="$WORK/helm.toml"
+cp "$BUMPFILE" "$HELM_BUMPFILE"
+bump --minor "$HELM_BUMPFILE" >/dev/null
+printf '\n[helm]\nfields = ["appVersion"]\n' >> "$HELM_BUMPFILE"
+bump update "$HELM/lion/Chart.yaml" "$HELM_BUMPFILE" >/dev/null
+check "helm/app-version-only" $'version: 1.0.0\nappVersion: "1.1.0"' \
+    "$(grep -E 'version:|appVersion:' "$HELM/lion/Chart.yaml")"
+
+# Java: the pom.xml project <version>, and Gradle project versions
+
+MAVEN="$WORK/maven"
+bump update "$MAVEN" "$BUMPFILE" >/dev/null
+check "maven/project-only" $'    <version>3.2.0</version>\n  <!-- <version>9.9.9</version> -->\n  <version>1.0.0</version>\n      <version>2.0.9</version>' \
+    "$(grep '<version>' "$MAVEN/pom.xml")"
+check "maven/module-inherits" "    <version>0.1.0</version>" "$(grep '<version>' "$MAVEN/core/pom.xml")"
+
+echo "[maven/module-without-version]"
+if bump update "$MAVEN/core/pom.xml" "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for a module inheriting its version"
+    exit 1
+fi
+echo "ok"
+echo
+
+GRADLE_JAVA="$WORK/gradle-java"
+bump update "$GRADLE_JAVA" "$BUMPFILE" >/dev/null
+check "gradle/project-version" 'version = "1.0.0"' "$(grep '^version' "$GRADLE_JAVA/build.gradle.kts")"
+check "gradle/dependency-untouched" '    implementation("org.slf4j:slf4j-api:2.0.9")' \
+    "$(grep implementation "$GRADLE_JAVA/build.gradle.kts")"
+check "gradle/properties" $'org.gradle.jvmargs=-Xmx2g\nversion=1.0.0' "$(cat "$GRADLE_JAVA/gradle.properties")"
+
+# [version_code] strategies
+
+printf '\n[version_code]\nstrategy = "positional"\n' >> "$BUMPFILE"
+bump update "$GRADLE" "$BUMPFILE" >/dev/null
+check "version-code/positional" "        versionCode 1000000" "$(grep versionCode "$GRADLE")"
+
+bump --patch "$BUMPFILE" >/dev/null
+check "version-code/recorded" "last = 1000100" "$(grep '^last = [0-9]' "$BUMPFILE")"
+
+sed -i.bak 's/"positional"/"counter"/' "$BUMPFILE"
+bump --patch "$BUMPFILE" >/dev/null
+bump update "$GRADLE" "$BUMPFILE" >/dev/null
+check "version-code/counter" "        versionCode 1000101" "$(grep versionCode "$GRADLE")"
+
+sed -i.bak 's/^last = 1000101/last = 5/' "$BUMPFILE"
+echo "[version-code/never-decreases]"
+if bump update "$GRADLE" "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure when versionCode would go down"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Terraform: locals and variable defaults, never provider, module or chart versions
+
+TERRAFORM="$WORK/terraform"
+TERRAFORM_VERSION="$(bump print --no-prefix "$BUMPFILE")"
+bump update "$TERRAFORM/main.tf" "$BUMPFILE" >/dev/null
+check "terraform/locals" "  app_version = \"$TERRAFORM_VERSION\"" "$(grep app_version "$TERRAFORM/main.tf")"
+check "terraform/others-untouched" $'      version = "2.12.1"\n  version = "4.2.0"' \
+    "$(grep ' version =' "$TERRAFORM/main.tf")"
+bump update "$TERRAFORM/variables.tf" "$BUMPFILE" >/dev/null
+check "terraform/variable-default" $'  default = "'"$TERRAFORM_VERSION"$'"\n  default = "3"' \
+    "$(grep default "$TERRAFORM/variables.tf")"
+
+cp "$TERRAFORM/charts.tf" "$WORK/charts.tf.before"
+echo "[terraform/chart-version-only]"
+if bump update "$TERRAFORM/charts.tf" "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure for a file with only a helm_release chart version"
+    exit 1
+fi
+cmp "$TERRAFORM/charts.tf" "$WORK/charts.tf.before"
+echo "ok"
+echo
+
+check "terraform/directory-skips-unversioned" "updated 2 files under $TERRAFORM" \
+    "$(bump update "$TERRAFORM" "$BUMPFILE" | tail -n 1)"
+
+# Dockerfile OCI version labels and Compose image tags from [docker].images
+
+DOCKER="$WORK/docker"
+DOCKER_BUMPFILE="$WORK/docker.toml"
+cp "$BUMPFILE" "$DOCKER_BUMPFILE"
+DOCKER_VERSION="$(bump print "$DOCKER_BUMPFILE")"
+printf '\n[docker]\nimages = ["ghcr.io/tiger/*"]\n' >> "$DOCKER_BUMPFILE"
+bump update "$DOCKER" "$DOCKER_BUMPFILE" >/dev/null
+check "docker/label" $'      org.opencontainers.image.version="'"$DOCKER_VERSION"$'" \\\n# LABEL org.opencontainers.image.version=0.0.0' \
+    "$(grep 'image.version' "$DOCKER/Dockerfile")"
+check "docker/build-arg-skipped" 'LABEL org.opencontainers.image.version=$VERSION' \
+    "$(grep 'image.version' "$DOCKER/builder/Dockerfile")"
+check "docker/compose" "    image: ghcr.io/tiger/api:$DOCKER_VERSION  # released with bump"$'\n'"    image: \"ghcr.io/tiger/worker:$DOCKER_VERSION\""$'\n'"    image: postgres:16"$'\n'"    image: ghcr.io/tiger/cache@sha256:0123456789abcdef" \
+    "$(grep 'image:' "$DOCKER/docker-compose.yml")"
+
+printf 'tag = "{version}-alpine"\n' >> "$DOCKER_BUMPFILE"
+bump update "$DOCKER/docker-compose.yml" "$DOCKER_BUMPFILE" >/dev/null
+check "docker/tag-template" "    image: ghcr.io/tiger/api:${DOCKER_VERSION#v}-alpine  # released with bump" \
+    "$(grep 'tiger/api' "$DOCKER/docker-compose.yml")"
+
+echo "[docker/compose-needs-images]"
+if bump update "$DOCKER/docker-compose.yml" "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure without [docker].images"
+    exit 1
+fi
+echo "ok"
+echo
+
+# debian/changelog entries and RPM spec Version:/Release:/%changelog
+
+PACKAGING="$WORK/packaging"
+PACKAGING_BUMPFILE="$WORK/packaging.toml"
+cp "$BUMPFILE" "$PACKAGING_BUMPFILE"
+PACKAGING_VERSION="$(bump print --no-prefix "$PACKAGING_BUMPFILE")"
+export SOURCE_DATE_EPOCH=1776335400 DEBFULLNAME="Tiger Release" DEBEMAIL="release@example.org"
+export RPM_PACKAGER="Tiger Packaging <rpm@example.org>"
+bump update "$PACKAGING" "$PACKAGING_BUMPFILE" >/dev/null
+check "debian/entry" "tiger (1:$PACKAGING_VERSION-1) unstable; urgency=medium
+
+  * New upstream release $PACKAGING_VERSION.
+
+ -- Tiger Release <release@example.org>  Thu, 16 Apr 2026 10:30:00 +0000
+
+tiger (1:0.9.0-2) unstable; urgency=medium" "$(head -n 7 "$PACKAGING/debian/changelog")"
+check "rpm/tags" $'Version:        '"$PACKAGING_VERSION"$'\nRelease:        1%{?dist}' \
+    "$(grep -E '^(Version|Release):' "$PACKAGING/tiger.spec")"
+
+bump update "$PACKAGING/tiger.spec" "$PACKAGING_BUMPFILE" >/dev/null
+check "rpm/changelog-once" $'%changelog\n* Thu Apr 16 2026 Tiger Packaging <rpm@example.org> - '"$PACKAGING_VERSION"$'-1\n- Update to '"$PACKAGING_VERSION"$'\n\n* Mon Mar 02 2026 Old Packager <old@example.org> - 0.9.0-3' \
+    "$(sed -n '/^%changelog/,$p' "$PACKAGING/tiger.spec" | head -n 5)"
+
+bump --phase rc "$PACKAGING_BUMPFILE" >/dev/null
+RC_BASE="$(bump print --only-base "$PACKAGING_BUMPFILE")"
+bump update "$PACKAGING/debian/changelog" "$PACKAGING_BUMPFILE" >/dev/null
+bump update "$PACKAGING/debian/changelog" "$PACKAGING_BUMPFILE" >/dev/null
+check "debian/pre-release-tilde" "tiger (1:$RC_BASE~rc1-1) unstable; urgency=medium" \
+    "$(head -n 1 "$PACKAGING/debian/changelog")"
+check "debian/entry-once" "1" "$(grep -c '~rc1-1' "$PACKAGING/debian/changelog")"
+bump update "$PACKAGING/tiger.spec" "$PACKAGING_BUMPFILE" >/dev/null
+check "rpm/pre-release-tilde" "Version:        $RC_BASE~rc1" "$(grep '^Version:' "$PACKAGING/tiger.spec")"
+unset SOURCE_DATE_EPOCH DEBFULLNAME DEBEMAIL RPM_PACKAGER
+
+# pubspec.yaml, mix.exs, composer.json and *.gemspec
+
+PACKAGES="$WORK/packages"
+PACKAGES_BUMPFILE="$WORK/packages.toml"
+cp "$BUMPFILE" "$PACKAGES_BUMPFILE"
+PACKAGES_VERSION="$(bump print --no-prefix "$PACKAGES_BUMPFILE")"
+bump update "$PACKAGES" "$PACKAGES_BUMPFILE" >/dev/null
+check "pubspec/version-code" "version: $PACKAGES_VERSION+5 # build number for the stores" \
+    "$(grep '^version:' "$PACKAGES/dart/pubspec.yaml")"
+check "pubspec/dependency-untouched" "    version: ^1.2.0" "$(grep '  version:' "$PACKAGES/dart/pubspec.yaml")"
+check "mix/attribute" $'  @version "'"$PACKAGES_VERSION"$'"\n      version: @version,' \
+    "$(grep 'version' "$PACKAGES/elixir/mix.exs")"
+check "composer/top-level-only" $'    "version": "'"$PACKAGES_VERSION"$'",\n        "version": "keep"' \
+    "$(grep '"version"' "$PACKAGES/php/composer.json")"
+check "gemspec/version" "  spec.version       = \"$PACKAGES_VERSION\"" \
+    "$(grep 'spec.version' "$PACKAGES/ruby/tiger.gemspec")"
+
+bump init "$WORK/pubspec.toml" >/dev/null
+bump update "$PACKAGES/dart/pubspec.yaml" "$WORK/pubspec.toml" >/dev/null
+check "pubspec/keeps-build-number" "version: $(bump print --no-prefix "$WORK/pubspec.toml")+5 # build number for the stores" \
+    "$(grep '^version:' "$PACKAGES/dart/pubspec.yaml")"
+
+bump --phase rc "$PACKAGES_BUMPFILE" >/dev/null
+bump update "$PACKAGES/ruby/tiger.gemspec" "$PACKAGES_BUMPFILE" >/dev/null
+check "gemspec/pre-release-dots" "  spec.version       = \"$(bump print --only-base "$PACKAGES_BUMPFILE").rc.1\"" \
+    "$(grep 'spec.version' "$PACKAGES/ruby/tiger.gemspec")"
+
+printf 'defmodule Lion.MixProject do\n  def project, do: [app: :lion, version: "0.1.0"]\nend\n' > "$PACKAGES/elixir/mix.exs"
+bump update "$PACKAGES/elixir/mix.exs" "$PACKAGES_BUMPFILE" >/dev/null
+check "mix/keyword" "  def project, do: [app: :lion, version: \"$(bump print --no-prefix "$PACKAGES_BUMPFILE")\"]" \
+    "$(sed -n 2p "$PACKAGES/elixir/mix.exs")"
+
+printf '{\n    "name": "tiger/lib"\n}\n' > "$PACKAGES/php/composer.json"
+echo "[composer/needs-version]"
+if bump update "$PACKAGES/php/composer.json" "$PACKAGES_BUMPFILE" 2>/dev/null; then
+    echo "expected failure without a top-level version"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Version badges and install commands in Markdown with [markdown].patterns
+
+MARKDOWN="$WORK/markdown"
+MARKDOWN_BUMPFILE="$WORK/markdown.toml"
+bump init "$MARKDOWN_BUMPFILE" >/dev/null
+bump --minor "$MARKDOWN_BUMPFILE" >/dev/null
+bump --phase rc "$MARKDOWN_BUMPFILE" >/dev/null
+MARKDOWN_VERSION="$(bump print --no-prefix "$MARKDOWN_BUMPFILE")"
+cat >> "$MARKDOWN_BUMPFILE" <<'EOF'
+
+[markdown]
+patterns = [
+    "img.shields.io/badge/version-{version}-",
+    "cargo install tiger --version {version}",
+    "tiger-{tag}-linux",
+]
+EOF
+bump update "$MARKDOWN" "$MARKDOWN_BUMPFILE" >/dev/null
+check "markdown/badge-escaped" "![version](https://img.shields.io/badge/version-${MARKDOWN_VERSION//-/--}-blue)" \
+    "$(sed -n 3p "$MARKDOWN/README.md")"
+check "markdown/install" "Install the latest release with \`cargo install tiger --version $MARKDOWN_VERSION\`." \
+    "$(sed -n 6p "$MARKDOWN/README.md")"
+check "markdown/other-badges-untouched" "[![crates.io](https://img.shields.io/crates/v/tiger.svg)](https://crates.io/crates/tiger)" \
+    "$(sed -n 4p "$MARKDOWN/README.md")"
+check "markdown/nested-docs" $'cargo install tiger --version '"$MARKDOWN_VERSION"$'\nOr download tiger-v'"$MARKDOWN_VERSION"$'-linux.tar.gz from the releases page.' \
+    "$(grep -E 'cargo|download' "$MARKDOWN/docs/install.md")"
+check "markdown/changelog-skipped" "- \`cargo install tiger --version 0.9.0\` now works offline." \
+    "$(tail -n 1 "$MARKDOWN/CHANGELOG.md")"
+
+echo "[markdown/no-match]"
+if bump update "$MARKDOWN/CHANGELOG.md" "$BUMPFILE" 2>/dev/null; then
+    echo "expected failure when no pattern matches"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Several targets with --also: all are updated, or on any failure none is
+
+TXN="$WORK/transaction"
+mkdir -p "$TXN/app" "$TXN/broken"
+printf '[package]\nname = "tiger"\nversion = "0.1.0"\n' > "$TXN/app/Cargo.toml"
+printf '{\n  "name": "tiger",\n  "version": "0.1.0"\n}\n' > "$TXN/app/package.json"
+printf '[project\nversion = "0.1.0"\n' > "$TXN/broken/pyproject.toml"
+cp -r "$TXN/app" "$TXN/app.before"
+
+echo "[transaction/rollback]"
+if bump update "$TXN/app/Cargo.toml" --also "$TXN/app/package.json" --also "$TXN/broken/pyproject.toml" \
+    "$BUMPFILE" >/dev/null 2>&1; then
+    echo "expected failure for the unparsable pyproject.toml"
+    exit 1
+fi
+if ! diff -r "$TXN/app.before" "$TXN/app"; then
+    echo "files updated before the failure were not restored"
+    exit 1
+fi
+echo "ok"
+echo
+
+cp "$TXN/app/Cargo.toml" "$TXN/broken/Cargo.toml"
+echo "[transaction/directory-rollback]"
+if output="$(bump update "$TXN/broken" "$BUMPFILE" 2>&1)"; then
+    echo "expected failure for the unparsable pyproject.toml"
+    exit 1
+fi
+if ! cmp -s "$TXN/app.before/Cargo.toml" "$TXN/broken/Cargo.toml"; then
+    echo "the directory update was not rolled back"
+    exit 1
+fi
+if [[ "$output" =~ updated\ [0-9]+\ (of\ [0-9]+\ )?files || "$output" != *"1 of 2 files under $TXN/broken failed, no files changed"* ]]; then
+    echo "expected only the failure summary, got: $output"
+    exit 1
+fi
+echo "ok"
+echo
+
+echo "[transaction/rename-rollback]"
+mkdir -p "$TXN/recipe"
+printf 'SUMMARY = "tiger"\n' > "$TXN/recipe/tiger_0.1.0.bb"
+if bump update "$TXN/recipe/tiger_0.1.0.bb" --also "$TXN/broken/pyproject.toml" \
+    "$BUMPFILE" >/dev/null 2>&1; then
+    echo "expected failure for the unparsable pyproject.toml"
+    exit 1
+fi
+if [[ "$(ls "$TXN/recipe")" != "tiger_0.1.0.bb" ]]; then
+    echo "the recipe rename was not rolled back: $(ls "$TXN/recipe")"
+    exit 1
+fi
+echo "ok"
+echo
+
+echo "[bitbake/rename-keeps-existing]"
+cp "$TXN/recipe/tiger_0.1.0.bb" "$TXN/recipe/tiger_$(bump print --no-prefix "$BUMPFILE").bb"
+echo '# kept' >> "$TXN/recipe/tiger_$(bump print --no-prefix "$BUMPFILE").bb"
+if bump update "$TXN/recipe/tiger_0.1.0.bb" "$BUMPFILE" >/dev/null 2>&1; then
+    echo "expected failure when the renamed recipe already exists"
+    exit 1
+fi
+if ! grep -q '# kept' "$TXN/recipe/tiger_$(bump print --no-prefix "$BUMPFILE").bb" \
+    || [[ ! -f "$TXN/recipe/tiger_0.1.0.bb" ]]; then
+    echo "an existing recipe was replaced"
+    exit 1
+fi
+echo "ok"
+echo
+
+bump update "$TXN/app/Cargo.toml" --also "$TXN/app/package.json" "$BUMPFILE" >/dev/null
+TXN_VERSION="$(bump print --no-prefix "$BUMPFILE")"
+check "transaction/all-updated" "version = \"$TXN_VERSION\""$'\n'"  \"version\": \"$TXN_VERSION\"" \
+    "$(grep -h 'version' "$TXN/app/Cargo.toml" "$TXN/app/package.json")"
+check "transaction/no-staged-files" "" "$(find "$TXN" -name '*.bump-staged')"
+
+# [[update.file]]: search/replace templates rewritten on every formal bump
+
+RULES="$WORK/rules"
+mkdir -p "$RULES/docs"
+bump init "$RULES/bump.toml" >/dev/null
+printf 'Install with `cargo install tiger@0.1.0` or pin `tiger:0.1.0`.\n' > "$RULES/README.md"
+printf 'FROM tiger:v0.1.0\nLABEL major=0\n' > "$RULES/docs/Dockerfile"
+cat >> "$RULES/bump.toml" <<'EOF'
+
+[[update.file]]
+path = "README.md"
+
+[[update.file]]
+path = "docs/Dockerfile"
+search = "FROM tiger:{tag}"
+replace = "FROM tiger:{tag}"
+
+[[update.file]]
+path = "docs/Dockerfile"
+search = "major={major}"
+replace = "major={major}"
+EOF
+bump --major "$RULES/bump.toml" >/dev/null
+check "rules/default-template" 'Install with `cargo install tiger@1.0.0` or pin `tiger:1.0.0`.' "$(cat "$RULES/README.md")"
+check "rules/placeholders" $'FROM tiger:v1.0.0\nLABEL major=1' "$(cat "$RULES/docs/Dockerfile")"
+
+printf 'unrelated\n' > "$RULES/README.md"
+cp "$RULES/bump.toml" "$RULES/bump.toml.before"
+echo "[rules/no-match]"
+if bump --patch "$RULES/bump.toml" 2>/dev/null; then
+    echo "expected failure when the search text is missing"
+    exit 1
+fi
+if ! cmp -s "$RULES/bump.toml" "$RULES/bump.toml.before"; then
+    echo "the bumpfile changed although the bump failed"
+    exit 1
+fi
+echo "ok"
+echo
+
+echo "All update tests passed."
@@ -0,0 +1,145 @@
+#!/usr/bin/env bash
+
+set -euo pipefail
+
+# Integration tests for [workspace] member selection with --member and --all.
+
+source "$(dirname "$0")/lib.sh"
+
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+
+check() {
+    local name="$1"
+    local expected="$2"
+    local actual="$3"
+    echo "[$name]"
+    echo "expected: $expected"
+    echo "actual:   $actual"
+    if [[ "$actual" != "$expected" ]]; then
+        exit 1
+    fi
+    echo
+}
+
+cat > "$WORK/bump.toml" <<'TOML'
+[workspace]
+members = ["crates/core", "crates/cli"]
+TOML
+
+for member in core cli; do
+    bump init "$WORK/crates/$member/bump.toml" >/dev/null
+    bump --prefix "$member-v" "$WORK/crates/$member/bump.toml" >/dev/null
+done
+
+check "print-all" $'core core-v0.1.0\ncli cli-v0.1.0' "$(bump print --all "$WORK/bump.toml")"
+
+bump --patch --member core "$WORK/bump.toml" >/dev/null
+check "bump-one-member" $'core core-v0.1.1\ncli cli-v0.1.0' "$(bump print --all "$WORK/bump.toml")"
+
+bump --minor --all "$WORK/bump.toml" >/dev/null
+check "bump-all" $'core core-v0.2.0\ncli cli-v0.2.0' "$(bump print --all "$WORK/bump.toml")"
+
+check "member-by-directory" "cli 0.2.0" \
+    "$(bump print --no-prefix --member crates/cli "$WORK/bump.toml")"
+
+bump gen --all --lang c --output include/version.h "$WORK/bump.toml" >/dev/null
+for member in core cli; do
+    echo "[gen/$member]"
+    grep -q "$member-v0.2.0" "$WORK/crates/$member/include/version.h"
+    echo "ok"
+    echo
+done
+
+cat >> "$WORK/crates/core/bump.toml" <<'TOML'
+
+[[gen]]
+lang = "c"
+output = "gen/core.h"
+symbol_prefix = "CORE_"
+TOML
+cat >> "$WORK/crates/cli/bump.toml" <<'TOML'
+
+[[gen]]
+lang = "python"
+output = "gen/version.py"
+symbol_prefix = "CLI_"
+TOML
+
+bump gen --all "$WORK/bump.toml" >/dev/null
+check "gen-config/c" '#define CORE_VERSION_STRING "core-v0.2.0"' \
+    "$(grep VERSION_STRING "$WORK/crates/core/gen/core.h")"
+check "gen-config/python" 'CLI_VERSION_STRING = "cli-v0.2.0"' \
+    "$(grep VERSION_STRING "$WORK/crates/cli/gen/version.py")"
+
+echo "[unknown-member]"
+if bump print --member nope "$WORK/bump.toml" 2>/dev/null; then
+    echo "expected failure for unknown member"
+    exit 1
+fi
+echo "ok"
+echo
+
+# Cascade: bumping a member bumps the members that depend on it.
+
+CASCADE="$WORK/cascade"
+mkdir -p "$CASCADE"
+cat > "$CASCADE/bump.toml" <<'TOML'
+[workspace]
+members = ["lib", "app", "tool"]
+cascade = "minor"
+
+[workspace.depends]
+app = ["lib"]
+tool = ["app"]
+TOML
+for member in lib app tool; do
+    bump init "$CASCADE/$member/bump.toml" >/dev/null
+done
+
+bump --patch --member lib "$CASCADE/bump.toml" >/dev/null
+check "cascade/transitive" $'lib v0.1.1\napp v0.2.0\ntool v0.2.0' "$(bump print --all "$CASCADE/bump.toml")"
+
+bump --patch --member app --member tool "$CASCADE/bump.toml" >/dev/null
+check "cascade/already-bumped" $'lib v0.1.1\napp v0.2.1\ntool v0.2.1' "$(bump print --all "$CASCADE/bump.toml")"
+
+bump --phase rc --member lib "$CASCADE/bump.toml" >/dev/null
+check "cascade/phase-stays" $'lib v0.1.1-rc.1\napp v0.2.1\ntool v0.2.1' "$(bump print --all "$CASCADE/bump.toml")"
+
+# Fixed strategy: the root bumpfile holds the one shared version.
+
+FIXED="$WORK/fixed"
+mkdir -p "$FIXED/a" "$FIXED/b"
+bump init "$FIXED/bump.toml" >/dev/null
+printf '\n[workspace]\nmembers = ["a", "b"]\nstrategy = "fixed"\n' >> "$FIXED/bump.toml"
+
+bump --minor --member a "$FIXED/bump.toml" >/dev/null
+check "fixed/bump-member-moves-all" $'a v0.2.0\nb v0.2.0' "$(bump print --all "$FIXED/bump.toml")"
+
+bump --patch --all "$FIXED/bump.toml" >/dev/null
+check "fixed/bump-all-once" "v0.2.1" "$(bump print "$FIXED/bump.toml")"
+
+# bump auto: only members with commits since their release tag are bumped.
+
+REPO="$WORK/repo"
+BUMP_BIN="$(realpath "$BUMP_BIN")"
+mkdir -p "$REPO"
+cp -r "$WORK/bump.toml" "$WORK/crates" "$REPO/"
+rm -rf "$REPO"/crates/*/include "$REPO"/crates/*/gen
+(
+    cd "$REPO"
+    git init -q
+    git -c user.name=test -c user.email=test@example.com add -A
+    git -c user.name=test -c user.email=test@example.com commit -q -m init
+    git tag core-v0.2.0
+    git tag cli-v0.2.0
+    echo change > crates/cli/file
+    git add -A
+    git -c user.name=test -c user.email=test@example.com commit -q -m cli
+)
+
+check "auto/report" $'core: unchanged since core-v0.2.0, skipping\ncli: 1 commit(s) since cli-v0.2.0, bumping patch' \
+    "$(cd "$REPO" && bump auto | grep -v '^bumped')"
+check "auto/bumped-changed-only" $'core core-v0.2.0\ncli cli-v0.2.1' "$(bump print --all "$REPO/bump.toml")"
+
+echo "All workspace tests passed."